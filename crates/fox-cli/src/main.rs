@@ -4,8 +4,15 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use fox_core::{fetch::Fetcher, FetchConfig, ChromeConfig, ChromeManager};
-use std::io::{self, Read};
+use fox_core::accessibility::DEFAULT_AX_DEPTH;
+use fox_core::{
+    fetch::Fetcher, Auth, BrowserStatus, ChromeConfig, ChromeInfo, ChromeManager, ChromeSource,
+    DownloadProgress, FetchConfig, Link, LinkStyle, Page, ResourceType, UserAgentPreset,
+    WaitStrategy,
+};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Parser)]
@@ -30,7 +37,7 @@ enum Commands {
         /// URL to fetch
         url: String,
 
-        /// Output format: markdown, plain, json
+        /// Output format: markdown, plain, json, html (content re-rendered as clean HTML), raw (the HTML itself, unconverted)
         #[arg(short, long, default_value = "markdown")]
         format: OutputFormat,
 
@@ -41,11 +48,58 @@ enum Commands {
         /// Extraction method: accessibility (default) or readability
         #[arg(short, long, default_value = "accessibility")]
         extraction: String,
+
+        /// When a rendered page is ready: load, domcontentloaded, networkidle
+        #[arg(long, default_value = "load")]
+        wait: WaitStrategy,
+
+        /// Hard-wrap markdown/plain output at this many columns
+        #[arg(long, value_name = "COLS")]
+        wrap: Option<usize>,
+
+        /// Render links as plain text, without URLs
+        #[arg(long)]
+        no_links: bool,
+
+        /// Send HTTP Basic credentials
+        #[arg(long, value_name = "USER:PASS", conflicts_with = "bearer")]
+        basic_auth: Option<String>,
+
+        /// Send a bearer token
+        #[arg(long, value_name = "TOKEN")]
+        bearer: Option<String>,
+
+        /// User agent string to send (takes precedence over --ua-preset)
+        #[arg(long, value_name = "STRING")]
+        user_agent: Option<String>,
+
+        /// Send a common user agent: chrome, firefox, googlebot, fox
+        #[arg(long, value_name = "PRESET")]
+        ua_preset: Option<UserAgentPreset>,
+
+        /// Start markdown output with a YAML front matter block (title, url,
+        /// date, author, description)
+        #[arg(long)]
+        front_matter: bool,
+    },
+
+    /// List the links in a page's content
+    Links {
+        /// URL to fetch
+        url: String,
+
+        /// Output format: text (text and URL, tab-separated), json, csv
+        #[arg(short, long, default_value = "text")]
+        format: LinksFormat,
+
+        /// Skip JavaScript rendering (HTTP only)
+        #[arg(long)]
+        no_js: bool,
     },
 
     /// Open the interactive browser
     Browse {
-        /// URL to open (optional)
+        /// URL to open (optional); `-` opens a tab for each URL read from stdin
         url: Option<String>,
     },
 
@@ -55,16 +109,39 @@ enum Commands {
         #[arg(short, long)]
         base_url: Option<String>,
 
-        /// Output format: markdown, plain, json
+        /// Output format: markdown, plain, json, html (content re-rendered as clean HTML), raw (the HTML itself, unconverted)
         #[arg(short, long, default_value = "markdown")]
         format: OutputFormat,
+
+        /// Hard-wrap markdown/plain output at this many columns
+        #[arg(long, value_name = "COLS")]
+        wrap: Option<usize>,
+
+        /// Render links as plain text, without URLs
+        #[arg(long)]
+        no_links: bool,
     },
 
-    /// Debug: dump accessibility tree for a URL
+    /// Show version and Chrome installation details for bug reports
+    Info,
+
+    /// Import bookmarks exported from another browser
+    Import {
+        /// Netscape bookmarks file (the HTML export from Chrome or Firefox)
+        #[arg(long, value_name = "FILE")]
+        bookmarks: PathBuf,
+    },
+
+    /// Debug: dump accessibility tree for a URL or local HTML
     #[command(name = "debug-ax")]
     DebugAx {
         /// URL to fetch
-        url: String,
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        url: Option<String>,
+
+        /// Load HTML from a file instead of a URL (`-` reads stdin)
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
 
         /// Show full tree (include all nodes)
         #[arg(long)]
@@ -73,6 +150,14 @@ enum Commands {
         /// Also show markdown conversion
         #[arg(long, short)]
         markdown: bool,
+
+        /// Print the full tree as JSON instead of the indented listing
+        #[arg(long, conflicts_with_all = ["full", "markdown"])]
+        json: bool,
+
+        /// Maximum tree depth to fetch from Chrome
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_AX_DEPTH)]
+        depth: i64,
     },
 }
 
@@ -82,6 +167,10 @@ enum OutputFormat {
     Markdown,
     Plain,
     Json,
+    /// Extracted content rendered back to a clean HTML document
+    Html,
+    /// The page HTML exactly as fetched or rendered
+    Raw,
 }
 
 impl std::str::FromStr for OutputFormat {
@@ -92,6 +181,29 @@ impl std::str::FromStr for OutputFormat {
             "markdown" | "md" => Ok(OutputFormat::Markdown),
             "plain" | "text" | "txt" => Ok(OutputFormat::Plain),
             "json" => Ok(OutputFormat::Json),
+            "html" => Ok(OutputFormat::Html),
+            "raw" => Ok(OutputFormat::Raw),
+            _ => Err(format!("Unknown format: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum LinksFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for LinksFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "plain" | "txt" => Ok(LinksFormat::Text),
+            "json" => Ok(LinksFormat::Json),
+            "csv" => Ok(LinksFormat::Csv),
             _ => Err(format!("Unknown format: {}", s)),
         }
     }
@@ -110,17 +222,55 @@ async fn main() -> Result<()> {
     }
 
     match cli.command {
-        Some(Commands::Fetch { url, format, no_js, extraction }) => {
-            run_fetch(&url, format, no_js, &extraction).await?;
+        Some(Commands::Fetch {
+            url,
+            format,
+            no_js,
+            extraction,
+            wait,
+            wrap,
+            no_links,
+            basic_auth,
+            bearer,
+            user_agent,
+            ua_preset,
+            front_matter,
+        }) => {
+            let mut config = FetchConfig {
+                javascript: !no_js,
+                plain_text: matches!(format, OutputFormat::Plain),
+                link_style: link_style(no_links),
+                wait_strategy: wait,
+                auth: parse_auth(basic_auth.as_deref(), bearer)?,
+                ..Default::default()
+            };
+            if let Some(user_agent) = resolve_user_agent(user_agent, ua_preset) {
+                config.user_agent = user_agent;
+            }
+            run_fetch(&url, format, &extraction, wrap, front_matter, config).await?;
+        }
+        Some(Commands::Links { url, format, no_js }) => {
+            run_links(&url, format, no_js).await?;
+        }
+        Some(Commands::Browse { url }) if url.as_deref() == Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            fox_tui::run_with_tabs(parse_url_list(&input)).await?;
         }
         Some(Commands::Browse { url }) => {
             run_browse(url).await?;
         }
-        Some(Commands::Render { base_url, format }) => {
-            run_render(base_url, format).await?;
+        Some(Commands::Render { base_url, format, wrap, no_links }) => {
+            run_render(base_url, format, wrap, no_links).await?;
+        }
+        Some(Commands::Info) => {
+            print!("{}", info_report(&ChromeManager::new().info()));
         }
-        Some(Commands::DebugAx { url, full, markdown }) => {
-            run_debug_ax(&url, full, markdown).await?;
+        Some(Commands::Import { bookmarks }) => {
+            run_import_bookmarks(&bookmarks)?;
+        }
+        Some(Commands::DebugAx { url, file, full, markdown, json, depth }) => {
+            run_debug_ax(url.as_deref(), file.as_deref(), full, markdown, json, depth).await?;
         }
         None => {
             // If URL provided without subcommand, open browser
@@ -136,23 +286,31 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_fetch(url: &str, format: OutputFormat, no_js: bool, extraction: &str) -> Result<()> {
+async fn run_fetch(
+    url: &str,
+    format: OutputFormat,
+    extraction: &str,
+    wrap: Option<usize>,
+    front_matter: bool,
+    config: FetchConfig,
+) -> Result<()> {
     use fox_core::ExtractionMethod;
 
-    let config = FetchConfig {
-        javascript: !no_js,
-        ..Default::default()
-    };
-
+    let no_js = !config.javascript;
     let extraction_method = extraction.parse::<ExtractionMethod>()
         .unwrap_or(ExtractionMethod::Accessibility);
 
     let chrome_config = ChromeConfig {
         extraction_method,
+        block_resources: ResourceType::defaults_for(extraction_method),
         ..Default::default()
     };
 
-    let fetcher = Fetcher::with_config_and_chrome(config, chrome_config).await?;
+    let fetcher =
+        Fetcher::with_config_chrome_and_progress(config, chrome_config, report_download).await?;
+    if let BrowserStatus::Unavailable(reason) = fetcher.browser_status() {
+        eprintln!("note: JavaScript unavailable, fetching over HTTP only ({})", reason);
+    }
     let page = if no_js {
         fetcher.fetch_no_js(url).await?
     } else {
@@ -161,66 +319,356 @@ async fn run_fetch(url: &str, format: OutputFormat, no_js: bool, extraction: &st
 
     match format {
         OutputFormat::Markdown => {
-            println!("{}", page.to_markdown());
+            if front_matter {
+                print!("{}", front_matter_block(&page, SystemTime::now()));
+            }
+            println!("{}", reflow_output(page.to_markdown(), wrap));
         }
         OutputFormat::Plain => {
-            println!("{}", page.to_plain_text());
+            println!("{}", reflow_output(page.to_plain_text(), wrap));
         }
         OutputFormat::Json => {
-            let json = serde_json::json!({
-                "url": page.url.to_string(),
-                "title": page.title,
-                "content": page.to_markdown(),
-                "links": page.content.as_ref().map(|c| c.links.iter().map(|l| {
+            println!("{}", serde_json::to_string_pretty(&page.to_json())?);
+        }
+        OutputFormat::Html => {
+            print!("{}", page.to_html());
+        }
+        OutputFormat::Raw => {
+            write_raw(&mut io::stdout().lock(), &page)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_links(url: &str, format: LinksFormat, no_js: bool) -> Result<()> {
+    let config = FetchConfig {
+        javascript: !no_js,
+        ..Default::default()
+    };
+    let fetcher =
+        Fetcher::with_config_chrome_and_progress(config, ChromeConfig::default(), report_download)
+            .await?;
+    if let BrowserStatus::Unavailable(reason) = fetcher.browser_status() {
+        eprintln!("note: JavaScript unavailable, fetching over HTTP only ({})", reason);
+    }
+    let page = if no_js {
+        fetcher.fetch_no_js(url).await?
+    } else {
+        fetcher.fetch(url).await?
+    };
+
+    let links = page.content.as_ref().map(|c| c.links.as_slice()).unwrap_or_default();
+    write_links(&mut io::stdout().lock(), links, &format)?;
+    Ok(())
+}
+
+/// Write links in the `fox links` output format
+fn write_links(out: &mut impl Write, links: &[Link], format: &LinksFormat) -> Result<()> {
+    match format {
+        LinksFormat::Text => {
+            for link in links {
+                writeln!(out, "{}\t{}", link.text, link.url)?;
+            }
+        }
+        LinksFormat::Json => {
+            let json: Vec<_> = links
+                .iter()
+                .map(|l| {
                     serde_json::json!({
                         "text": l.text,
-                        "url": l.url
+                        "url": l.url,
+                        "title": l.title
                     })
-                }).collect::<Vec<_>>()).unwrap_or_default()
-            });
-            println!("{}", serde_json::to_string_pretty(&json)?);
+                })
+                .collect();
+            writeln!(out, "{}", serde_json::to_string_pretty(&json)?)?;
+        }
+        LinksFormat::Csv => {
+            writeln!(out, "text,url")?;
+            for link in links {
+                writeln!(out, "{},{}", csv_field(&link.text), csv_field(&link.url))?;
+            }
         }
     }
-
     Ok(())
 }
 
+/// Quote a CSV field if it contains a separator, quote, or line break
+fn csv_field(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+/// YAML front matter for `--front-matter`, ending with a blank line
+///
+/// Fields the page doesn't provide are left out, and `date` is when the page
+/// was fetched.
+fn front_matter_block(page: &Page, fetched_at: SystemTime) -> String {
+    let content = page.content.as_ref();
+    let title = page
+        .title
+        .as_deref()
+        .or_else(|| content.and_then(|c| c.title.as_deref()));
+    let fields = [
+        ("title", title.map(yaml_string)),
+        ("url", Some(yaml_string(page.url.as_str()))),
+        ("date", Some(utc_timestamp(fetched_at))),
+        ("author", content.and_then(|c| c.author.as_deref()).map(yaml_string)),
+        ("description", content.and_then(|c| c.description.as_deref()).map(yaml_string)),
+    ];
+
+    let mut block = String::from("---\n");
+    for (key, value) in fields {
+        if let Some(value) = value {
+            block.push_str(&format!("{}: {}\n", key, value));
+        }
+    }
+    block.push_str("---\n\n");
+    block
+}
+
+/// Double-quote a YAML scalar, escaping backslashes, quotes, and control
+/// characters so values like `Rust: "the book"` survive parsing
+fn yaml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Format a time as an RFC 3339 UTC timestamp, e.g. `2024-01-02T03:04:05Z`
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // Days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+/// Credentials from `--basic-auth user:pass` or `--bearer token`
+fn parse_auth(basic_auth: Option<&str>, bearer: Option<String>) -> Result<Option<Auth>> {
+    if let Some(credentials) = basic_auth {
+        let (user, pass) = credentials
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("--basic-auth expects user:pass"))?;
+        return Ok(Some(Auth::Basic {
+            user: user.to_string(),
+            pass: pass.to_string(),
+        }));
+    }
+    Ok(bearer.map(Auth::Bearer))
+}
+
+/// User agent from `--user-agent`, or else `--ua-preset`
+fn resolve_user_agent(custom: Option<String>, preset: Option<UserAgentPreset>) -> Option<String> {
+    custom.or_else(|| preset.map(UserAgentPreset::user_agent))
+}
+
+/// Map the `--no-links` flag to a link rendering style
+fn link_style(no_links: bool) -> LinkStyle {
+    if no_links {
+        LinkStyle::Hidden
+    } else {
+        LinkStyle::Inline
+    }
+}
+
+/// Write the page HTML verbatim, with no conversion or trailing newline
+fn write_raw(out: &mut impl Write, page: &Page) -> io::Result<()> {
+    out.write_all(page.html.as_bytes())?;
+    out.flush()
+}
+
+/// Apply the `--wrap` column limit to text output, if one was given
+fn reflow_output(text: String, wrap: Option<usize>) -> String {
+    match wrap {
+        Some(width) => fox_core::markdown::reflow(&text, width),
+        None => text,
+    }
+}
+
+/// Show first-run Chrome download progress on stderr
+fn report_download(progress: DownloadProgress) {
+    const MB: f64 = 1024.0 * 1024.0;
+    match progress {
+        DownloadProgress::Starting { version } => {
+            eprintln!("Downloading Chrome {} (first run only)...", version);
+        }
+        DownloadProgress::Downloading { downloaded, total: Some(total) } if total > 0 => {
+            eprint!(
+                "\r  {:.1} / {:.1} MB ({:.0}%)",
+                downloaded as f64 / MB,
+                total as f64 / MB,
+                downloaded as f64 * 100.0 / total as f64
+            );
+        }
+        DownloadProgress::Downloading { downloaded, .. } => {
+            eprint!("\r  {:.1} MB", downloaded as f64 / MB);
+        }
+        DownloadProgress::Extracting => eprintln!("\nExtracting Chrome..."),
+        DownloadProgress::Complete { path } => {
+            eprintln!("Chrome installed at {}", path.display());
+        }
+        DownloadProgress::Failed { error } => eprintln!("\nChrome download failed: {}", error),
+    }
+}
+
+/// Format the `fox info` report
+fn info_report(info: &ChromeInfo) -> String {
+    let chrome = match info.source {
+        ChromeSource::Bundled(ref path) => format!("bundled ({})", path.display()),
+        ChromeSource::System(ref path) => format!("system ({})", path.display()),
+        ChromeSource::None => "not found (downloaded on first JavaScript fetch)".to_string(),
+    };
+    let system = info
+        .system_chrome
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "not found".to_string());
+
+    let mut report = format!("fox {}\n", env!("CARGO_PKG_VERSION"));
+    report.push_str(&format!("platform: {}\n", info.platform));
+    report.push_str(&format!("chrome: {}\n", chrome));
+    if let Some(ref version) = info.bundled_version {
+        report.push_str(&format!("bundled chrome version: {}\n", version));
+    }
+    report.push_str(&format!("system chrome: {}\n", system));
+    report
+}
+
 async fn run_browse(url: Option<String>) -> Result<()> {
     fox_tui::run(url).await
 }
 
-async fn run_debug_ax(url: &str, full: bool, show_markdown: bool) -> Result<()> {
-    use fox_core::accessibility::{fetch_ax_tree, ax_tree_to_markdown};
+/// URLs from a newline-separated list, skipping blank lines and `#` comments
+fn parse_url_list(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+fn run_import_bookmarks(path: &Path) -> Result<()> {
+    let html = std::fs::read_to_string(path)?;
+    let mut bookmarks = fox_tui::bookmarks::Bookmarks::load()?;
+    let imported = bookmarks.import_netscape(&html);
+    bookmarks.save()?;
+    println!("Imported {} bookmarks from {}", imported, path.display());
+    Ok(())
+}
+
+async fn run_debug_ax(
+    url: Option<&str>,
+    file: Option<&Path>,
+    full: bool,
+    show_markdown: bool,
+    json: bool,
+    depth: i64,
+) -> Result<()> {
+    use fox_core::accessibility::{
+        ax_tree_to_markdown, fetch_ax_tree_with_depth, set_document_content,
+    };
+
+    // Read local HTML up front so a bad path fails before Chrome starts
+    let html = match file {
+        Some(path) if path == Path::new("-") => {
+            let mut html = String::new();
+            io::stdin().read_to_string(&mut html)?;
+            Some(html)
+        }
+        Some(path) => Some(std::fs::read_to_string(path)?),
+        None => None,
+    };
 
     // Initialize Chrome manager and get browser
     let mut chrome_manager = ChromeManager::with_config(ChromeConfig::default());
+    chrome_manager.set_progress_callback(report_download);
     let browser = chrome_manager.get_browser().await?;
     let browser_guard = browser.lock().await;
 
-    // Create a new page and navigate to URL
-    let url_parsed = if !url.contains("://") {
-        format!("https://{}", url)
-    } else {
-        url.to_string()
+    let page = match html {
+        Some(html) => {
+            // Progress messages go to stderr so --json output stays parseable
+            eprintln!("Loading {}...", file.unwrap_or(Path::new("-")).display());
+            let page = browser_guard
+                .browser
+                .new_page("about:blank")
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create page: {}", e))?;
+            set_document_content(&page, &html).await?;
+            page
+        }
+        None => {
+            let url = url.unwrap_or_default();
+            // Create a new page and navigate to URL
+            let url_parsed = if !url.contains("://") {
+                format!("https://{}", url)
+            } else {
+                url.to_string()
+            };
+
+            eprintln!("Navigating to {}...", url_parsed);
+            let page = browser_guard
+                .browser
+                .new_page(&url_parsed)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create page: {}", e))?;
+
+            page.wait_for_navigation()
+                .await
+                .map_err(|e| anyhow::anyhow!("Navigation failed: {}", e))?;
+            page
+        }
     };
 
-    println!("Navigating to {}...", url_parsed);
-    let page = browser_guard
-        .browser
-        .new_page(&url_parsed)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create page: {}", e))?;
-
-    page.wait_for_navigation()
-        .await
-        .map_err(|e| anyhow::anyhow!("Navigation failed: {}", e))?;
-
     // Wait a bit for the page to fully render
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
     // Fetch the accessibility tree
-    println!("Fetching accessibility tree...\n");
-    let tree = fetch_ax_tree(&page).await?;
+    eprintln!("Fetching accessibility tree (depth {})...", depth);
+    let tree = fetch_ax_tree_with_depth(&page, depth).await?;
+    eprintln!("Captured {} nodes at depth {}\n", tree.nodes.len(), depth);
+
+    if json {
+        println!("{}", tree.to_json()?);
+        let _ = page.close().await;
+        return Ok(());
+    }
 
     // Print the tree
     println!("Accessibility Tree ({} nodes):", tree.nodes.len());
@@ -286,12 +734,19 @@ async fn run_debug_ax(url: &str, full: bool, show_markdown: bool) -> Result<()>
     Ok(())
 }
 
-async fn run_render(base_url: Option<String>, format: OutputFormat) -> Result<()> {
+async fn run_render(
+    base_url: Option<String>,
+    format: OutputFormat,
+    wrap: Option<usize>,
+    no_links: bool,
+) -> Result<()> {
     let mut html = String::new();
     io::stdin().read_to_string(&mut html)?;
 
     let config = FetchConfig {
         javascript: false,
+        plain_text: matches!(format, OutputFormat::Plain),
+        link_style: link_style(no_links),
         ..Default::default()
     };
 
@@ -300,20 +755,182 @@ async fn run_render(base_url: Option<String>, format: OutputFormat) -> Result<()
 
     match format {
         OutputFormat::Markdown => {
-            println!("{}", page.to_markdown());
+            println!("{}", reflow_output(page.to_markdown(), wrap));
         }
         OutputFormat::Plain => {
-            println!("{}", page.to_plain_text());
+            println!("{}", reflow_output(page.to_plain_text(), wrap));
         }
         OutputFormat::Json => {
-            let json = serde_json::json!({
-                "url": page.url.to_string(),
-                "title": page.title,
-                "content": page.to_markdown()
-            });
-            println!("{}", serde_json::to_string_pretty(&json)?);
+            println!("{}", serde_json::to_string_pretty(&page.to_json())?);
+        }
+        OutputFormat::Html => {
+            print!("{}", page.to_html());
+        }
+        OutputFormat::Raw => {
+            write_raw(&mut io::stdout().lock(), &page)?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_auth() {
+        assert_eq!(
+            parse_auth(Some("alice:pa:ss"), None).unwrap(),
+            Some(Auth::Basic {
+                user: "alice".to_string(),
+                pass: "pa:ss".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_auth(None, Some("tok".to_string())).unwrap(),
+            Some(Auth::Bearer("tok".to_string()))
+        );
+        assert_eq!(parse_auth(None, None).unwrap(), None);
+        assert!(parse_auth(Some("alice"), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_url_list() {
+        let input = "# reading list\nhttps://example.com\n\n   \n  https://rust-lang.org  \n  # later\nnews.ycombinator.com\n";
+        assert_eq!(
+            parse_url_list(input),
+            vec!["https://example.com", "https://rust-lang.org", "news.ycombinator.com"]
+        );
+        assert!(parse_url_list("\n# nothing here\n").is_empty());
+    }
+
+    #[test]
+    fn test_links_csv_escaping() {
+        let link = |text: &str, url: &str| Link {
+            text: text.to_string(),
+            url: url.to_string(),
+            title: None,
+            position: 0,
+        };
+        let links = vec![
+            link("Home", "https://example.com/"),
+            link("Rust, \"the book\"", "https://doc.rust-lang.org/book/"),
+        ];
+
+        let mut out = Vec::new();
+        write_links(&mut out, &links, &LinksFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "text,url\n\
+             Home,https://example.com/\n\
+             \"Rust, \"\"the book\"\"\",https://doc.rust-lang.org/book/\n"
+        );
+
+        let cli = Cli::try_parse_from(["fox", "links", "https://example.com", "--format", "csv"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Links { format: LinksFormat::Csv, .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_user_agent() {
+        assert_eq!(resolve_user_agent(None, None), None);
+        assert_eq!(
+            resolve_user_agent(None, Some(UserAgentPreset::Googlebot)),
+            Some(UserAgentPreset::Googlebot.user_agent())
+        );
+        assert_eq!(
+            resolve_user_agent(Some("MyBot/1.0".to_string()), Some(UserAgentPreset::Chrome)),
+            Some("MyBot/1.0".to_string())
+        );
+
+        let cli = Cli::try_parse_from(["fox", "fetch", "https://example.com", "--ua-preset", "firefox"])
+            .unwrap();
+        match cli.command {
+            Some(Commands::Fetch { ua_preset, .. }) => {
+                assert_eq!(ua_preset, Some(UserAgentPreset::Firefox))
+            }
+            _ => panic!("Expected Fetch command"),
+        }
+    }
+
+    #[test]
+    fn test_info_report_without_chrome() {
+        let info = ChromeInfo {
+            platform: "linux64",
+            source: ChromeSource::None,
+            bundled_version: None,
+            system_chrome: None,
+        };
+        let report = info_report(&info);
+        assert!(report.starts_with(&format!("fox {}\n", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("platform: linux64\n"));
+        assert!(report.contains("chrome: not found"));
+        assert!(report.contains("system chrome: not found\n"));
+        assert!(!report.contains("bundled chrome version"));
+    }
+
+    #[test]
+    fn test_debug_ax_depth_flag() {
+        let args = ["fox", "debug-ax", "https://example.com", "--depth", "5"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Some(Commands::DebugAx { url, depth, .. }) => {
+                assert_eq!(url.as_deref(), Some("https://example.com"));
+                assert_eq!(depth, 5);
+            }
+            _ => panic!("expected debug-ax"),
+        }
+
+        let cli = Cli::try_parse_from(["fox", "debug-ax", "--file", "page.html"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::DebugAx { depth: DEFAULT_AX_DEPTH, url: None, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_raw_output_is_unchanged_html() {
+        let html = "<html><head><title>T</title></head>\n<body><p>Hello <b>raw</b></p></body></html>\n";
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let page = fetcher.render_html(html, None).unwrap();
+
+        let mut out = Vec::new();
+        write_raw(&mut out, &page).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), html);
+        assert!(matches!("raw".parse(), Ok(OutputFormat::Raw)));
+    }
+
+    #[tokio::test]
+    async fn test_front_matter_block() {
+        let html = r#"<html><head><title>Rust: "the book" \ notes</title>
+            <meta name="description" content="Line one
+            # not a comment"></head>
+            <body><article><p>Body text.</p></article></body></html>"#;
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let page = fetcher.render_html(html, Some("https://example.com/a?b=c#d")).unwrap();
+
+        let fetched_at = UNIX_EPOCH + std::time::Duration::from_secs(1_709_251_199);
+        let block = front_matter_block(&page, fetched_at);
+        assert_eq!(
+            block,
+            "---\n\
+             title: \"Rust: \\\"the book\\\" \\\\ notes\"\n\
+             url: \"https://example.com/a?b=c#d\"\n\
+             date: 2024-02-29T23:59:59Z\n\
+             description: \"Line one # not a comment\"\n\
+             ---\n\n"
+        );
+        assert_eq!(yaml_string("tab\there\u{7}"), "\"tab\\there\\u0007\"");
+    }
+}
@@ -6,14 +6,17 @@
 
 use crate::{FoxError, Result};
 use chromiumoxide::cdp::browser_protocol::accessibility::{
-    AxNode as CdpAxNode, AxProperty, AxValue, AxValueType, EnableParams, GetFullAxTreeParams,
+    AxNode as CdpAxNode, AxProperty, AxPropertyName, AxValue, AxValueType, EnableParams,
+    GetFullAxTreeParams,
 };
+use chromiumoxide::cdp::browser_protocol::page::SetDocumentContentParams;
 use chromiumoxide::Page;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tracing::debug;
 
 /// A node in the accessibility tree
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AXNode {
     /// Unique node ID
     pub node_id: String,
@@ -30,12 +33,16 @@ pub struct AXNode {
     /// URL for links
     pub url: Option<String>,
     /// Whether the node is focused
+    #[serde(default)]
     pub focused: bool,
     /// Whether the node is ignored (not rendered)
+    #[serde(default)]
     pub ignored: bool,
     /// Child node IDs
+    #[serde(default)]
     pub child_ids: Vec<String>,
     /// Additional properties
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub properties: HashMap<String, String>,
 }
 
@@ -85,6 +92,41 @@ impl AXNode {
         )
     }
 
+    /// Get a raw property value by key (e.g. "Expanded", "Level")
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(|v| v.as_str())
+    }
+
+    /// The accessible name (from aria-label, a label, or text content)
+    pub fn aria_label(&self) -> Option<&str> {
+        self.name.as_deref().map(str::trim).filter(|n| !n.is_empty())
+    }
+
+    /// Check if the node is disabled (`aria-disabled` or a disabled control)
+    pub fn is_disabled(&self) -> bool {
+        self.property("Disabled") == Some("true")
+    }
+
+    /// Check if the node is expanded (`aria-expanded`)
+    pub fn is_expanded(&self) -> bool {
+        self.property("Expanded") == Some("true")
+    }
+
+    /// Check if the node is a required form field
+    pub fn is_required(&self) -> bool {
+        self.property("Required") == Some("true")
+    }
+
+    /// Check if the node is selected (`aria-selected`)
+    pub fn is_selected(&self) -> bool {
+        self.property("Selected") == Some("true")
+    }
+
+    /// Check if the node is checked; "mixed" checkboxes are not
+    pub fn is_checked(&self) -> bool {
+        self.property("Checked") == Some("true")
+    }
+
     /// Check if this node has meaningful content
     pub fn has_content(&self) -> bool {
         self.name.as_ref().map(|n| !n.trim().is_empty()).unwrap_or(false)
@@ -92,10 +134,26 @@ impl AXNode {
     }
 }
 
+/// Serialize a map with its keys in sorted order
+fn serialize_sorted<S, V>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 /// The full accessibility tree for a page
-#[derive(Debug)]
+///
+/// Serializes as `{"nodes": {id: node, ...}, "root_id": ...}` with nodes
+/// keyed in sorted order, so dumps of the same page diff cleanly.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AXTree {
     /// All nodes indexed by ID
+    #[serde(serialize_with = "serialize_sorted")]
     pub nodes: HashMap<String, AXNode>,
     /// Root node ID
     pub root_id: Option<String>,
@@ -110,6 +168,17 @@ impl AXTree {
         }
     }
 
+    /// Serialize the tree to pretty-printed JSON, e.g. to cache it on disk
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| FoxError::Other(format!("Failed to serialize AX tree: {}", e)))
+    }
+
+    /// Load a tree saved by [`AXTree::to_json`]
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| FoxError::Other(format!("Invalid AX tree JSON: {}", e)))
+    }
+
     /// Get a node by ID
     pub fn get(&self, id: &str) -> Option<&AXNode> {
         self.nodes.get(id)
@@ -125,6 +194,16 @@ impl AXTree {
         DepthFirstIterator::new(self)
     }
 
+    /// All nodes with the given role, in document order
+    pub fn find_by_role(&self, role: &str) -> Vec<&AXNode> {
+        self.iter_depth_first().filter(|node| node.role == role).collect()
+    }
+
+    /// The first node with the given role, in document order
+    pub fn find_first_by_role(&self, role: &str) -> Option<&AXNode> {
+        self.iter_depth_first().find(|node| node.role == role)
+    }
+
     /// Get children of a node
     pub fn children(&self, node: &AXNode) -> Vec<&AXNode> {
         node.child_ids
@@ -133,6 +212,34 @@ impl AXTree {
             .collect()
     }
 
+    /// Concatenated text of all `StaticText` nodes beneath `node`
+    ///
+    /// `InlineTextBox` nodes are skipped since they duplicate their parent
+    /// `StaticText`. This is the same text the markdown converter uses.
+    pub fn text_of(&self, node: &AXNode) -> String {
+        let mut text = String::new();
+        self.collect_text(node, &mut text);
+        text
+    }
+
+    fn collect_text(&self, node: &AXNode, text: &mut String) {
+        if node.role == "StaticText" {
+            if let Some(name) = &node.name {
+                text.push_str(name);
+            }
+            return;
+        }
+
+        // Don't collect text from InlineTextBox (duplicates StaticText)
+        if node.role == "InlineTextBox" {
+            return;
+        }
+
+        for child in self.children(node) {
+            self.collect_text(child, text);
+        }
+    }
+
     /// Walk the tree, calling a function for each node with its depth
     pub fn walk<F>(&self, mut f: F)
     where
@@ -189,9 +296,37 @@ impl<'a> Iterator for DepthFirstIterator<'a> {
     }
 }
 
+/// Replace the document in `page` with `html`
+///
+/// Uses `Page.setDocumentContent`, so local HTML can be inspected without
+/// serving it over HTTP. Relative URLs in `html` resolve against whatever
+/// the page had loaded before (usually `about:blank`).
+pub async fn set_document_content(page: &Page, html: &str) -> Result<()> {
+    let frame_id = page
+        .mainframe()
+        .await
+        .map_err(|e| FoxError::BrowserError(format!("Failed to get main frame: {}", e)))?
+        .ok_or_else(|| FoxError::BrowserError("Page has no main frame".to_string()))?;
+
+    page.execute(SetDocumentContentParams::new(frame_id, html))
+        .await
+        .map_err(|e| FoxError::BrowserError(format!("Failed to set document content: {}", e)))?;
+    Ok(())
+}
+
+/// Default maximum depth of the accessibility tree fetched from Chrome
+pub const DEFAULT_AX_DEPTH: i64 = 100;
+
 /// Fetch the full accessibility tree from a page
 pub async fn fetch_ax_tree(page: &Page) -> Result<AXTree> {
-    debug!("Fetching accessibility tree");
+    fetch_ax_tree_with_depth(page, DEFAULT_AX_DEPTH).await
+}
+
+/// Fetch the accessibility tree from a page, at most `depth` levels deep
+///
+/// Nodes below the limit are left out, so a small depth truncates content.
+pub async fn fetch_ax_tree_with_depth(page: &Page, depth: i64) -> Result<AXTree> {
+    debug!("Fetching accessibility tree (depth {})", depth);
 
     // Enable the Accessibility domain first
     page.execute(EnableParams::default())
@@ -200,7 +335,7 @@ pub async fn fetch_ax_tree(page: &Page) -> Result<AXTree> {
 
     // Get the full AX tree
     // Using depth limit to potentially avoid issues with very large trees
-    let params = GetFullAxTreeParams::builder().depth(100).build();
+    let params = GetFullAxTreeParams::builder().depth(depth).build();
     let response = page
         .execute(params)
         .await
@@ -268,23 +403,21 @@ fn convert_cdp_node(cdp: &CdpAxNode) -> AXNode {
     if let Some(props) = &cdp.properties {
         for prop in props {
             if let Some(value) = extract_property_value(prop) {
-                let name = format!("{:?}", prop.name);
-                properties.insert(name.clone(), value.clone());
-
                 // Extract specific properties based on name
-                let name_str = format!("{:?}", prop.name);
-                match name_str.as_str() {
-                    "Level" => {
+                match prop.name {
+                    AxPropertyName::Level => {
                         level = value.parse().ok();
                     }
-                    "Url" => {
+                    AxPropertyName::Url => {
                         url = Some(value.clone());
                     }
-                    "Focused" => {
+                    AxPropertyName::Focused => {
                         focused = value == "true";
                     }
                     _ => {}
                 }
+
+                properties.insert(property_key(&prop.name), value);
             }
         }
     }
@@ -327,7 +460,12 @@ fn extract_string_value(value: &AxValue) -> Option<String> {
             value.value.as_ref().and_then(|v| v.as_f64().map(|f| f.to_string()))
         }
         AxValueType::Boolean | AxValueType::Tristate | AxValueType::BooleanOrUndefined => {
-            value.value.as_ref().and_then(|v| v.as_bool().map(|b| b.to_string()))
+            // Tristate values ("true"/"false"/"mixed") arrive as strings
+            value.value.as_ref().and_then(|v| {
+                v.as_bool()
+                    .map(|b| b.to_string())
+                    .or_else(|| v.as_str().map(String::from))
+            })
         }
         _ => value.value.as_ref().and_then(|v| {
             if let Some(s) = v.as_str() {
@@ -339,6 +477,36 @@ fn extract_string_value(value: &AxValue) -> Option<String> {
     }
 }
 
+/// Key under which a property is stored in `AXNode::properties`
+///
+/// Properties read by the converters and `AXNode` accessors are mapped
+/// explicitly; any other property uses its CDP protocol name with the first
+/// letter capitalized, so `hasPopup` becomes "HasPopup".
+fn property_key(name: &AxPropertyName) -> String {
+    let key = match name {
+        AxPropertyName::Level => "Level",
+        AxPropertyName::Url => "Url",
+        AxPropertyName::Focused => "Focused",
+        AxPropertyName::Checked => "Checked",
+        AxPropertyName::Expanded => "Expanded",
+        AxPropertyName::Disabled => "Disabled",
+        AxPropertyName::Required => "Required",
+        AxPropertyName::Selected => "Selected",
+        AxPropertyName::Editable => "Editable",
+        AxPropertyName::Multiline => "Multiline",
+        AxPropertyName::Live => "Live",
+        other => {
+            let raw: &str = other.as_ref();
+            let mut chars = raw.chars();
+            return match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            };
+        }
+    };
+    key.to_string()
+}
+
 /// Extract a value from an AxProperty
 fn extract_property_value(prop: &AxProperty) -> Option<String> {
     extract_string_value(&prop.value)
@@ -348,18 +516,97 @@ fn extract_property_value(prop: &AxProperty) -> Option<String> {
 // AX Tree to Markdown Conversion
 // ============================================================================
 
+use crate::markdown::{
+    compact_data_uri, escape_html, link_title_suffix, pad_to_width, truncate_to_width, LinkStyle,
+};
 use crate::Link;
+use unicode_width::UnicodeWidthStr;
+
+/// Options controlling which roles are rendered during AX conversion
+///
+/// Both sets are empty by default, which keeps the built-in behavior.
+#[derive(Debug, Clone, Default)]
+pub struct AxConversionOptions {
+    /// Roles to drop entirely, including their descendants
+    pub skip_roles: HashSet<String>,
+    /// Roles skipped by default (navigation, banner, ...) to render anyway
+    pub keep_roles: HashSet<String>,
+    /// Render each message in an ARIA live region (log, status, alert, ...)
+    /// as its own paragraph; otherwise live regions convert like any other
+    /// container
+    pub include_live_regions: bool,
+    /// Whether links render as `[text](url)` or as bare text
+    pub link_style: LinkStyle,
+}
 
 /// Convert an accessibility tree to markdown
 pub fn ax_tree_to_markdown(tree: &AXTree) -> (String, Vec<Link>) {
-    let mut converter = MarkdownConverter::new(tree);
+    ax_tree_to_markdown_with_options(tree, &AxConversionOptions::default())
+}
+
+/// Convert an accessibility tree to markdown with custom role handling
+pub fn ax_tree_to_markdown_with_options(
+    tree: &AXTree,
+    options: &AxConversionOptions,
+) -> (String, Vec<Link>) {
+    let mut converter = MarkdownConverter::new(tree, options);
     converter.convert();
     (converter.output, converter.links)
 }
 
+/// Convert an accessibility tree to plain text
+pub fn ax_tree_to_plain(tree: &AXTree) -> String {
+    ax_tree_to_plain_with_options(tree, &AxConversionOptions::default())
+}
+
+/// Convert an accessibility tree to plain text with custom role handling
+///
+/// Produces the same content selection as the markdown converter but emits
+/// no markdown markers: blocks are separated by blank lines, list items and
+/// table rows get their own line, and links keep only their text.
+pub fn ax_tree_to_plain_with_options(tree: &AXTree, options: &AxConversionOptions) -> String {
+    let mut converter = PlainTextConverter {
+        tree,
+        options,
+        output: String::new(),
+    };
+    if let Some(root) = tree.root() {
+        converter.convert_node(root);
+    }
+    converter.finish()
+}
+
+/// Convert an accessibility tree to a minimal HTML fragment
+pub fn ax_tree_to_html(tree: &AXTree) -> String {
+    ax_tree_to_html_with_options(tree, &AxConversionOptions::default())
+}
+
+/// Convert an accessibility tree to HTML with custom role handling
+///
+/// Selects the same content as the markdown converter but rebuilds it as
+/// plain semantic HTML (`<h1>`, `<p>`, `<a href>`, `<ul>`/`<li>`,
+/// `<table>`, ...) with no attributes besides link targets, titles, and
+/// image sources. Each block element starts on its own line.
+pub fn ax_tree_to_html_with_options(tree: &AXTree, options: &AxConversionOptions) -> String {
+    let mut converter = HtmlConverter {
+        tree,
+        options,
+        output: String::new(),
+    };
+    if let Some(root) = tree.root() {
+        converter.convert_node(root);
+    }
+    let mut html = converter.output.trim().to_string();
+    if !html.is_empty() {
+        html.push('\n');
+    }
+    html
+}
+
 /// State machine for converting AX tree to markdown
 struct MarkdownConverter<'a> {
     tree: &'a AXTree,
+    options: &'a AxConversionOptions,
     output: String,
     links: Vec<Link>,
     list_depth: usize,
@@ -369,9 +616,10 @@ struct MarkdownConverter<'a> {
 }
 
 impl<'a> MarkdownConverter<'a> {
-    fn new(tree: &'a AXTree) -> Self {
+    fn new(tree: &'a AXTree, options: &'a AxConversionOptions) -> Self {
         Self {
             tree,
+            options,
             output: String::new(),
             links: Vec::new(),
             list_depth: 0,
@@ -389,6 +637,24 @@ impl<'a> MarkdownConverter<'a> {
     }
 
     fn convert_node(&mut self, node: &AXNode) {
+        // Caller-requested role overrides take precedence over the defaults
+        if self.options.skip_roles.contains(&node.role) {
+            return;
+        }
+        if !node.ignored
+            && Self::is_skipped_by_default(&node.role)
+            && self.options.keep_roles.contains(&node.role)
+        {
+            self.convert_children(node);
+            return;
+        }
+
+        // Live regions (chat logs, status messages) hold their text in children
+        if !node.ignored && self.options.include_live_regions && Self::is_live_region(node) {
+            self.convert_live_region(node);
+            return;
+        }
+
         // For purely structural nodes, just process children
         if self.should_skip_but_recurse(node) {
             self.convert_children(node);
@@ -409,15 +675,7 @@ impl<'a> MarkdownConverter<'a> {
             // Headings
             "heading" => {
                 if let Some(text) = self.get_node_text(node) {
-                    // Skip accessibility-related or generic section headings
-                    let text_lower = text.to_lowercase();
-                    let is_skip_heading = text_lower.contains("accessibility")
-                        || text_lower == "skip links"
-                        || text_lower == "navigation"
-                        || text_lower == "main navigation"
-                        || text_lower.starts_with("skip to");
-
-                    if !is_skip_heading {
+                    if !Self::is_skip_heading(&text) {
                         self.ensure_block_spacing();
                         let level = node.level.unwrap_or(1).min(6) as usize;
                         let prefix = "#".repeat(level);
@@ -462,15 +720,7 @@ impl<'a> MarkdownConverter<'a> {
                 let text = self.get_node_text(node).unwrap_or_default();
                 let url = node.url.as_deref().unwrap_or("#");
 
-                // Skip internal anchor links (#) and skip-to-content style links
-                let is_skip_link = url == "#"
-                    || url.starts_with("#")
-                    || text.to_lowercase().contains("skip to")
-                    || text.to_lowercase().contains("skip navigation")
-                    || text.to_lowercase().contains("accessibility")
-                    || text.to_lowercase().starts_with("jump to");
-
-                if !text.is_empty() && !is_skip_link {
+                if !text.is_empty() && !Self::is_skip_link(&text, url) {
                     // Add space before link if needed (for consecutive links)
                     if let Some(last_char) = self.output.chars().last() {
                         if last_char != ' ' && last_char != '\n' && last_char != '(' && last_char != '[' {
@@ -478,16 +728,27 @@ impl<'a> MarkdownConverter<'a> {
                         }
                     }
 
+                    let title = Self::link_title(node, &text);
+
                     let position = self.output.len();
-                    self.output.push('[');
-                    self.output.push_str(&text);
-                    self.output.push_str("](");
-                    self.output.push_str(url);
-                    self.output.push(')');
+                    match self.options.link_style {
+                        LinkStyle::Inline => {
+                            self.output.push('[');
+                            self.output.push_str(&text);
+                            self.output.push_str("](");
+                            self.output.push_str(url);
+                            if let Some(ref title) = title {
+                                self.output.push_str(&link_title_suffix(title));
+                            }
+                            self.output.push(')');
+                        }
+                        LinkStyle::Hidden => self.output.push_str(&text),
+                    }
 
                     self.links.push(Link {
                         text: text.clone(),
                         url: url.to_string(),
+                        title,
                         position,
                     });
                 }
@@ -495,7 +756,10 @@ impl<'a> MarkdownConverter<'a> {
 
             // Lists
             "list" => {
-                self.ensure_block_spacing();
+                // Nested lists continue their parent item's block
+                if self.list_depth == 0 {
+                    self.ensure_block_spacing();
+                }
                 self.list_depth += 1;
                 self.list_counters.push(0);
                 self.convert_children(node);
@@ -530,13 +794,28 @@ impl<'a> MarkdownConverter<'a> {
                 self.output.push_str(&indent);
                 self.output.push_str(&marker);
 
-                // Get list item content
-                if let Some(text) = self.get_node_text(node) {
-                    self.output.push_str(&text);
+                let children = self.tree.children(node);
+                if children.iter().any(|c| c.role == "list") {
+                    // Render the item's own text, then nested lists on
+                    // their own lines rather than flattening them into it
+                    for child in children {
+                        if child.role == "list" && !self.output.ends_with('\n') {
+                            self.output.push('\n');
+                        }
+                        self.convert_node(child);
+                    }
+                    if !self.output.ends_with('\n') {
+                        self.output.push('\n');
+                    }
                 } else {
-                    self.convert_children(node);
+                    // Get list item content
+                    if let Some(text) = self.get_node_text(node) {
+                        self.output.push_str(&text);
+                    } else {
+                        self.convert_children(node);
+                    }
+                    self.output.push('\n');
                 }
-                self.output.push('\n');
             }
 
             // Blockquotes
@@ -586,6 +865,7 @@ impl<'a> MarkdownConverter<'a> {
             "image" | "img" => {
                 let alt = node.name.as_deref().unwrap_or("image");
                 let src = node.url.as_deref().unwrap_or("");
+                let src = compact_data_uri(src).unwrap_or(src);
                 if !src.is_empty() {
                     self.output.push_str("![");
                     self.output.push_str(alt);
@@ -595,6 +875,32 @@ impl<'a> MarkdownConverter<'a> {
                 }
             }
 
+            // Figures: content (usually an image) followed by an italic caption
+            "figure" => {
+                self.ensure_block_spacing();
+                let mut caption = None;
+                for child in self.tree.children(node) {
+                    if matches!(child.role.as_str(), "Figcaption" | "figcaption" | "caption") {
+                        caption = self.get_node_text(child);
+                    } else {
+                        self.convert_node(child);
+                    }
+                }
+                if let Some(caption) = caption {
+                    let caption = caption.trim();
+                    if !caption.is_empty() {
+                        if !self.output.is_empty() && !self.output.ends_with('\n') {
+                            self.output.push('\n');
+                        }
+                        self.output.push('*');
+                        self.output.push_str(caption);
+                        self.output.push('*');
+                    }
+                }
+                self.output.push_str("\n\n");
+                self.last_was_block = true;
+            }
+
             // Layout tables (Chrome distinguishes these from semantic data tables)
             // These are used for page layout, not for tabular data
             "LayoutTable" => {
@@ -672,6 +978,16 @@ impl<'a> MarkdownConverter<'a> {
                 self.convert_children(node);
             }
 
+            // Multi-line/contenteditable textboxes hold document text, not a form field
+            "textbox" if Self::is_multiline_textbox(node) && self.editable_text(node).is_some() => {
+                if let Some(text) = self.editable_text(node) {
+                    self.ensure_block_spacing();
+                    self.output.push_str(text.trim());
+                    self.output.push_str("\n\n");
+                    self.last_was_block = true;
+                }
+            }
+
             // Form elements - simplified display
             "textbox" | "searchbox" => {
                 // Only show if it has a meaningful label
@@ -704,16 +1020,13 @@ impl<'a> MarkdownConverter<'a> {
 
             "checkbox" => {
                 let label = node.name.as_deref().unwrap_or("checkbox");
-                let checked = node.properties.get("Checked")
-                    .map(|v| v == "true")
-                    .unwrap_or(false);
+                let checked = node.is_checked();
                 let marker = if checked { "[x]" } else { "[ ]" };
                 self.output.push_str(&format!("{} {}", marker, label));
             }
 
             // Skip these roles entirely
-            "navigation" | "banner" | "contentinfo" | "complementary" | "search"
-            | "form" | "toolbar" | "menubar" | "menu" | "menuitem" => {
+            role if Self::is_skipped_by_default(role) => {
                 // Skip navigation/structural elements
             }
 
@@ -736,6 +1049,115 @@ impl<'a> MarkdownConverter<'a> {
         }
     }
 
+    /// Check if a node is an ARIA live region (by role or `aria-live`)
+    fn is_live_region(node: &AXNode) -> bool {
+        matches!(
+            node.role.as_str(),
+            "log" | "status" | "alert" | "marquee" | "timer"
+        ) || node
+            .properties
+            .get("Live")
+            .map(|v| v == "polite" || v == "assertive")
+            .unwrap_or(false)
+    }
+
+    /// Check if a textbox is multi-line or contenteditable
+    fn is_multiline_textbox(node: &AXNode) -> bool {
+        node.properties.get("Multiline").map(|v| v == "true").unwrap_or(false)
+            || node
+                .properties
+                .get("Editable")
+                .map(|v| v == "richtext" || v == "plaintext")
+                .unwrap_or(false)
+    }
+
+    /// Text held by an editable region: its value, or its StaticText descendants
+    fn editable_text(&self, node: &AXNode) -> Option<String> {
+        if let Some(value) = node.value.as_ref().filter(|v| !v.trim().is_empty()) {
+            return Some(value.clone());
+        }
+        let mut text = String::new();
+        self.collect_text(node, &mut text);
+        if text.trim().is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    /// Render each child of a live region as its own paragraph
+    fn convert_live_region(&mut self, node: &AXNode) {
+        let mut paragraphs: Vec<String> = self
+            .tree
+            .children(node)
+            .iter()
+            .filter_map(|child| self.get_node_text(child))
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        // A region without structured children carries its text in the name
+        if paragraphs.is_empty() {
+            if let Some(text) = self.get_node_text(node) {
+                paragraphs.push(text.trim().to_string());
+            }
+        }
+
+        for paragraph in paragraphs {
+            self.ensure_block_spacing();
+            self.output.push_str(&paragraph);
+            self.output.push_str("\n\n");
+            self.last_was_block = true;
+        }
+    }
+
+    /// Accessibility-related or generic section headings, which are dropped
+    fn is_skip_heading(text: &str) -> bool {
+        let text_lower = text.to_lowercase();
+        text_lower.contains("accessibility")
+            || text_lower == "skip links"
+            || text_lower == "navigation"
+            || text_lower == "main navigation"
+            || text_lower.starts_with("skip to")
+    }
+
+    /// Internal anchor links (#) and skip-to-content style links, which are
+    /// dropped
+    fn is_skip_link(text: &str, url: &str) -> bool {
+        let text_lower = text.to_lowercase();
+        url.starts_with('#')
+            || text_lower.contains("skip to")
+            || text_lower.contains("skip navigation")
+            || text_lower.contains("accessibility")
+            || text_lower.starts_with("jump to")
+    }
+
+    /// A link's title attribute, carried in its description, unless it just
+    /// repeats the link text
+    fn link_title(node: &AXNode, text: &str) -> Option<String> {
+        node.description
+            .as_ref()
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty() && d != text)
+    }
+
+    /// Navigation/structural roles dropped unless listed in `keep_roles`
+    fn is_skipped_by_default(role: &str) -> bool {
+        matches!(
+            role,
+            "navigation"
+                | "banner"
+                | "contentinfo"
+                | "complementary"
+                | "search"
+                | "form"
+                | "toolbar"
+                | "menubar"
+                | "menu"
+                | "menuitem"
+        )
+    }
+
     /// Nodes we skip but still recurse into their children
     fn should_skip_but_recurse(&self, node: &AXNode) -> bool {
         matches!(
@@ -777,21 +1199,7 @@ impl<'a> MarkdownConverter<'a> {
     }
 
     fn collect_text(&self, node: &AXNode, text: &mut String) {
-        if node.role == "StaticText" {
-            if let Some(name) = &node.name {
-                text.push_str(name);
-            }
-            return;
-        }
-
-        // Don't collect text from InlineTextBox (duplicates StaticText)
-        if node.role == "InlineTextBox" {
-            return;
-        }
-
-        for child in self.tree.children(node) {
-            self.collect_text(child, text);
-        }
+        self.tree.collect_text(node, text);
     }
 
     fn ensure_block_spacing(&mut self) {
@@ -873,7 +1281,7 @@ impl<'a> MarkdownConverter<'a> {
             for (i, cell) in row.iter().enumerate() {
                 if i < col_widths.len() {
                     // Cap column width at MAX_COL_WIDTH
-                    col_widths[i] = col_widths[i].max(cell.len()).min(MAX_COL_WIDTH);
+                    col_widths[i] = col_widths[i].max(cell.width()).min(MAX_COL_WIDTH);
                 }
             }
         }
@@ -885,8 +1293,8 @@ impl<'a> MarkdownConverter<'a> {
                 let width = col_widths.get(col_idx).copied().unwrap_or(3);
                 self.output.push(' ');
                 // Truncate cell content if it exceeds max width
-                let display_cell = Self::truncate_cell_content(cell, width);
-                self.output.push_str(&format!("{:width$}", display_cell, width = width));
+                let display_cell = truncate_to_width(cell, width);
+                self.output.push_str(&pad_to_width(&display_cell, width));
                 self.output.push_str(" |");
             }
             // Pad missing cells
@@ -1121,14 +1529,6 @@ impl<'a> MarkdownConverter<'a> {
     }
 
     /// Truncate a string to max length with ellipsis
-    fn truncate_cell_content(content: &str, max_width: usize) -> String {
-        if content.len() <= max_width {
-            content.to_string()
-        } else {
-            format!("{}...", &content[..max_width.saturating_sub(3)])
-        }
-    }
-
     /// Convert a layout table (used for page layout, not data)
     /// Renders content as simple blocks without table formatting
     fn convert_layout_table(&mut self, table_node: &AXNode) {
@@ -1150,36 +1550,577 @@ impl<'a> MarkdownConverter<'a> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Walks an AX tree emitting plain text instead of markdown
+struct PlainTextConverter<'a> {
+    tree: &'a AXTree,
+    options: &'a AxConversionOptions,
+    output: String,
+}
 
-    #[test]
-    fn test_ax_node_should_skip() {
-        let mut node = AXNode {
-            node_id: "1".to_string(),
-            role: "heading".to_string(),
-            name: Some("Title".to_string()),
-            value: None,
-            description: None,
-            level: Some(1),
-            url: None,
-            focused: false,
-            ignored: false,
-            child_ids: vec![],
-            properties: HashMap::new(),
-        };
+impl<'a> PlainTextConverter<'a> {
+    fn convert_node(&mut self, node: &AXNode) {
+        if self.options.skip_roles.contains(&node.role) {
+            return;
+        }
+        if !node.ignored
+            && MarkdownConverter::is_skipped_by_default(&node.role)
+            && self.options.keep_roles.contains(&node.role)
+        {
+            self.convert_children(node);
+            return;
+        }
+        if !node.ignored
+            && self.options.include_live_regions
+            && MarkdownConverter::is_live_region(node)
+        {
+            self.convert_live_region(node);
+            return;
+        }
+        if node.ignored || matches!(node.role.as_str(), "LineBreak" | "InlineTextBox") {
+            return;
+        }
 
-        assert!(!node.should_skip());
+        match node.role.as_str() {
+            "heading" | "paragraph" | "blockquote" | "Figcaption" | "figcaption" | "caption" => {
+                if let Some(text) = self.node_text(node) {
+                    self.block(&text);
+                }
+            }
+            "list" | "table" | "grid" | "LayoutTable" | "figure" | "separator" => {
+                self.break_lines(2);
+                self.convert_children(node);
+                self.break_lines(2);
+            }
+            "listitem" => {
+                self.break_lines(1);
+                if let Some(text) = self.node_text(node) {
+                    self.output.push_str(text.trim());
+                }
+                self.break_lines(1);
+            }
+            "row" | "LayoutTableRow" => {
+                let cells: Vec<String> = self
+                    .tree
+                    .children(node)
+                    .iter()
+                    .filter_map(|cell| self.node_text(cell))
+                    .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .collect();
+                self.break_lines(1);
+                self.output.push_str(&cells.join("\t"));
+                self.break_lines(1);
+            }
+            "code" | "pre" => {
+                if let Some(text) = self.node_text(node) {
+                    if text.contains('\n') {
+                        self.break_lines(2);
+                        self.output.push_str(text.trim_end());
+                        self.break_lines(2);
+                    } else {
+                        self.output.push_str(&text);
+                    }
+                }
+            }
+            "link" => {
+                if let Some(text) = self.node_text(node) {
+                    if let Some(last_char) = self.output.chars().last() {
+                        if !last_char.is_whitespace() {
+                            self.output.push(' ');
+                        }
+                    }
+                    self.output.push_str(text.trim());
+                }
+            }
+            "StaticText" => {
+                if let Some(text) = &node.name {
+                    self.output.push_str(text);
+                }
+            }
+            "textbox" if MarkdownConverter::is_multiline_textbox(node) => {
+                let text = node
+                    .value
+                    .clone()
+                    .filter(|v| !v.trim().is_empty())
+                    .or_else(|| self.node_text(node));
+                if let Some(text) = text {
+                    self.block(&text);
+                }
+            }
 
-        node.role = "navigation".to_string();
-        assert!(node.should_skip());
+            // Images and form controls carry no readable content
+            "image" | "img" | "textbox" | "searchbox" | "button" | "checkbox" => {}
+            role if MarkdownConverter::is_skipped_by_default(role) => {}
 
-        node.role = "paragraph".to_string();
+            "RootWebArea" | "WebArea" | "document" | "none" | "presentation" | "generic"
+            | "group" | "section" | "article" | "main" | "region" | "LayoutTableCell" => {
+                self.convert_children(node);
+            }
+            _ => {
+                if let Some(text) = node.name.as_ref().filter(|n| !n.trim().is_empty()) {
+                    self.output.push_str(text);
+                } else {
+                    self.convert_children(node);
+                }
+            }
+        }
+    }
+
+    fn convert_children(&mut self, node: &AXNode) {
+        for child in self.tree.children(node) {
+            self.convert_node(child);
+        }
+    }
+
+    fn convert_live_region(&mut self, node: &AXNode) {
+        let children = self.tree.children(node);
+        if children.is_empty() {
+            if let Some(text) = self.node_text(node) {
+                self.block(&text);
+            }
+        }
+        for child in children {
+            if let Some(text) = self.node_text(child) {
+                self.block(&text);
+            }
+        }
+    }
+
+    /// A node's name, or the StaticText beneath it
+    fn node_text(&self, node: &AXNode) -> Option<String> {
+        if let Some(name) = node.name.as_ref().filter(|n| !n.trim().is_empty()) {
+            return Some(name.clone());
+        }
+        let text = self.tree.text_of(node);
+        if text.trim().is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    /// Emit text as a paragraph surrounded by blank lines
+    fn block(&mut self, text: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        self.break_lines(2);
+        self.output.push_str(text);
+        self.break_lines(2);
+    }
+
+    /// Make sure the output ends with at least `count` newlines
+    fn break_lines(&mut self, count: usize) {
+        if self.output.is_empty() {
+            return;
+        }
+        let trimmed = self.output.trim_end_matches([' ', '\t']).len();
+        self.output.truncate(trimmed);
+        let trailing = self.output.chars().rev().take_while(|&c| c == '\n').count();
+        for _ in trailing..count {
+            self.output.push('\n');
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut result = String::new();
+        let mut blank_run = 0;
+        for line in self.output.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank_run += 1;
+                if blank_run > 1 {
+                    continue;
+                }
+            } else {
+                blank_run = 0;
+            }
+            result.push_str(line);
+            result.push('\n');
+        }
+        let mut result = result.trim().to_string();
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result
+    }
+}
+
+/// Walks an AX tree emitting semantic HTML elements
+struct HtmlConverter<'a> {
+    tree: &'a AXTree,
+    options: &'a AxConversionOptions,
+    output: String,
+}
+
+impl<'a> HtmlConverter<'a> {
+    fn convert_node(&mut self, node: &AXNode) {
+        if self.options.skip_roles.contains(&node.role) {
+            return;
+        }
+        if !node.ignored
+            && MarkdownConverter::is_skipped_by_default(&node.role)
+            && self.options.keep_roles.contains(&node.role)
+        {
+            self.convert_children(node);
+            return;
+        }
+        if !node.ignored
+            && self.options.include_live_regions
+            && MarkdownConverter::is_live_region(node)
+        {
+            self.convert_live_region(node);
+            return;
+        }
+        if node.ignored || matches!(node.role.as_str(), "LineBreak" | "InlineTextBox") {
+            return;
+        }
+
+        match node.role.as_str() {
+            "heading" => {
+                if let Some(text) = self.node_text(node) {
+                    if !MarkdownConverter::is_skip_heading(&text) {
+                        let tag = format!("h{}", node.level.unwrap_or(1).clamp(1, 6));
+                        self.open_block(&tag);
+                        self.output.push_str(&escape_html(text.trim()));
+                        self.close_block(&tag);
+                    }
+                }
+            }
+            "paragraph" => self.block_element("p", node),
+            "blockquote" => self.block_element("blockquote", node),
+            "link" => {
+                let text = self.node_text(node).unwrap_or_default();
+                let url = node.url.as_deref().unwrap_or("#");
+                if !text.is_empty() && !MarkdownConverter::is_skip_link(&text, url) {
+                    self.output.push_str(&format!("<a href=\"{}\"", escape_html(url)));
+                    if let Some(title) = MarkdownConverter::link_title(node, &text) {
+                        self.output.push_str(&format!(" title=\"{}\"", escape_html(&title)));
+                    }
+                    self.output.push('>');
+                    self.output.push_str(&escape_html(text.trim()));
+                    self.output.push_str("</a>");
+                }
+            }
+            "list" => {
+                let ordered = self.tree.children(node).iter().any(|item| {
+                    item.properties.contains_key("SetSize")
+                        || item.properties.contains_key("PosInSet")
+                });
+                let tag = if ordered { "ol" } else { "ul" };
+                self.open_block(tag);
+                self.convert_children(node);
+                self.close_block(tag);
+            }
+            "listitem" => self.block_element("li", node),
+            "code" | "pre" => {
+                if let Some(text) = self.node_text(node) {
+                    if text.contains('\n') {
+                        self.open_block("pre");
+                        self.output.push_str("<code>");
+                        self.output.push_str(&escape_html(text.trim_end()));
+                        self.output.push_str("</code>");
+                        self.close_block("pre");
+                    } else {
+                        self.output.push_str("<code>");
+                        self.output.push_str(&escape_html(&text));
+                        self.output.push_str("</code>");
+                    }
+                }
+            }
+            "image" | "img" => {
+                let src = node.url.as_deref().unwrap_or("").trim();
+                if !src.is_empty() {
+                    let alt = node.name.as_deref().unwrap_or("image");
+                    self.output.push_str(&format!(
+                        "<img src=\"{}\" alt=\"{}\">",
+                        escape_html(src),
+                        escape_html(alt)
+                    ));
+                }
+            }
+            "figure" => {
+                self.open_block("figure");
+                self.convert_children(node);
+                self.close_block("figure");
+            }
+            "Figcaption" | "figcaption" | "caption" => self.block_element("figcaption", node),
+            "table" | "grid" => {
+                self.open_block("table");
+                self.convert_children(node);
+                self.close_block("table");
+            }
+            "row" => {
+                let start = self.output.len();
+                self.open_block("tr");
+                let cells = self.output.len();
+                for cell in self.tree.children(node) {
+                    let tag = match cell.role.as_str() {
+                        "columnheader" | "rowheader" => "th",
+                        "cell" | "gridcell" => "td",
+                        _ => continue,
+                    };
+                    self.output.push_str(&format!("<{}>", tag));
+                    self.inline_content(cell);
+                    self.output.push_str(&format!("</{}>", tag));
+                }
+                if self.output.len() == cells {
+                    self.output.truncate(start);
+                    return;
+                }
+                self.close_block("tr");
+            }
+            "separator" => {
+                self.open_block("hr");
+                self.output.push('\n');
+            }
+            "strong" => self.inline_element("strong", node),
+            "emphasis" => self.inline_element("em", node),
+            "StaticText" => {
+                if let Some(text) = &node.name {
+                    self.output.push_str(&escape_html(text));
+                }
+            }
+            "textbox" if MarkdownConverter::is_multiline_textbox(node) => {
+                let text = node
+                    .value
+                    .clone()
+                    .filter(|v| !v.trim().is_empty())
+                    .or_else(|| self.node_text(node));
+                if let Some(text) = text {
+                    self.open_block("p");
+                    self.output.push_str(&escape_html(text.trim()));
+                    self.close_block("p");
+                }
+            }
+
+            // Form controls have no content worth rebuilding
+            "textbox" | "searchbox" | "button" | "checkbox" => {}
+            role if MarkdownConverter::is_skipped_by_default(role) => {}
+
+            "RootWebArea" | "WebArea" | "document" | "none" | "presentation" | "generic"
+            | "group" | "section" | "article" | "main" | "region" | "rowgroup"
+            | "LayoutTable" | "LayoutTableRow" | "LayoutTableCell" => {
+                self.convert_children(node);
+            }
+            _ => self.inline_content(node),
+        }
+    }
+
+    fn convert_children(&mut self, node: &AXNode) {
+        for child in self.tree.children(node) {
+            self.convert_node(child);
+        }
+    }
+
+    fn convert_live_region(&mut self, node: &AXNode) {
+        let mut paragraphs: Vec<String> = self
+            .tree
+            .children(node)
+            .iter()
+            .filter_map(|child| self.node_text(child))
+            .collect();
+        if paragraphs.is_empty() {
+            paragraphs.extend(self.node_text(node));
+        }
+        for paragraph in paragraphs {
+            self.open_block("p");
+            self.output.push_str(&escape_html(paragraph.trim()));
+            self.close_block("p");
+        }
+    }
+
+    /// Wrap a node's content in a block element, dropping the element if
+    /// the node renders nothing
+    fn block_element(&mut self, tag: &str, node: &AXNode) {
+        let start = self.output.len();
+        self.open_block(tag);
+        let content = self.output.len();
+        self.inline_content(node);
+        if self.output[content..].trim().is_empty() {
+            self.output.truncate(start);
+            return;
+        }
+        self.close_block(tag);
+    }
+
+    fn inline_element(&mut self, tag: &str, node: &AXNode) {
+        self.output.push_str(&format!("<{}>", tag));
+        self.inline_content(node);
+        self.output.push_str(&format!("</{}>", tag));
+    }
+
+    /// Render a node's children, keeping their links and emphasis, or its
+    /// name when it has no children
+    fn inline_content(&mut self, node: &AXNode) {
+        if node.child_ids.is_empty() {
+            if let Some(name) = node.name.as_ref().filter(|n| !n.trim().is_empty()) {
+                self.output.push_str(&escape_html(name.trim()));
+            }
+        } else {
+            self.convert_children(node);
+        }
+    }
+
+    /// A node's name, or the StaticText beneath it
+    fn node_text(&self, node: &AXNode) -> Option<String> {
+        if let Some(name) = node.name.as_ref().filter(|n| !n.trim().is_empty()) {
+            return Some(name.clone());
+        }
+        let text = self.tree.text_of(node);
+        if text.trim().is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    /// Start a block element on its own line
+    fn open_block(&mut self, tag: &str) {
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+        self.output.push_str(&format!("<{}>", tag));
+        if matches!(tag, "ul" | "ol" | "table" | "figure") {
+            self.output.push('\n');
+        }
+    }
+
+    /// End a block element and the line it is on
+    fn close_block(&mut self, tag: &str) {
+        if matches!(tag, "ul" | "ol" | "table" | "figure") && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+        self.output.push_str(&format!("</{}>\n", tag));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a tree from `(id, role, name, children)` rows, rooted at the first row
+    fn tree_from_rows(rows: &[(&str, &str, Option<&str>, &[&str])]) -> AXTree {
+        let mut tree = AXTree::new();
+        for &(id, role, name, children) in rows {
+            tree.nodes.insert(
+                id.to_string(),
+                AXNode {
+                    node_id: id.to_string(),
+                    role: role.to_string(),
+                    name: name.map(String::from),
+                    value: None,
+                    description: None,
+                    level: None,
+                    url: None,
+                    focused: false,
+                    ignored: false,
+                    child_ids: children.iter().map(|c| c.to_string()).collect(),
+                    properties: HashMap::new(),
+                },
+            );
+        }
+        tree.root_id = rows.first().map(|row| row.0.to_string());
+        tree
+    }
+
+    #[test]
+    fn test_ax_node_should_skip() {
+        let mut node = AXNode {
+            node_id: "1".to_string(),
+            role: "heading".to_string(),
+            name: Some("Title".to_string()),
+            value: None,
+            description: None,
+            level: Some(1),
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        assert!(!node.should_skip());
+
+        node.role = "navigation".to_string();
+        assert!(node.should_skip());
+
+        node.role = "paragraph".to_string();
         node.ignored = true;
         assert!(node.should_skip());
     }
 
+    #[test]
+    fn test_ax_node_state_accessors() {
+        let mut node = AXNode {
+            node_id: "1".to_string(),
+            role: "button".to_string(),
+            name: Some("  Menu  ".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        assert_eq!(node.aria_label(), Some("Menu"));
+        assert!(!node.is_disabled());
+        assert!(!node.is_expanded());
+        assert!(!node.is_required());
+        assert!(!node.is_selected());
+
+        for key in ["Disabled", "Expanded", "Required", "Selected"] {
+            node.properties.insert(key.to_string(), "true".to_string());
+        }
+        node.properties.insert("Checked".to_string(), "mixed".to_string());
+
+        assert!(node.is_disabled());
+        assert!(node.is_expanded());
+        assert!(node.is_required());
+        assert!(node.is_selected());
+        assert!(!node.is_checked());
+
+        node.properties.insert("Expanded".to_string(), "false".to_string());
+        assert!(!node.is_expanded());
+    }
+
+    #[test]
+    fn test_property_key() {
+        assert_eq!(property_key(&AxPropertyName::Level), "Level");
+        assert_eq!(property_key(&AxPropertyName::HasPopup), "HasPopup");
+        assert_eq!(property_key(&AxPropertyName::Expanded), "Expanded");
+        assert_eq!(property_key(&AxPropertyName::Checked), "Checked");
+    }
+
+    #[test]
+    fn test_convert_cdp_node_properties() {
+        let cdp: CdpAxNode = serde_json::from_value(serde_json::json!({
+            "nodeId": "7",
+            "ignored": false,
+            "role": { "type": "role", "value": "heading" },
+            "name": { "type": "computedString", "value": "Title" },
+            "properties": [
+                { "name": "level", "value": { "type": "integer", "value": 2 } },
+                { "name": "focused", "value": { "type": "boolean", "value": true } },
+                { "name": "url", "value": { "type": "string", "value": "https://example.com/" } },
+                { "name": "checked", "value": { "type": "tristate", "value": "mixed" } },
+                { "name": "expanded", "value": { "type": "booleanOrUndefined", "value": false } }
+            ]
+        }))
+        .unwrap();
+
+        let node = convert_cdp_node(&cdp);
+        assert_eq!(node.role, "heading");
+        assert_eq!(node.level, Some(2));
+        assert!(node.focused);
+        assert_eq!(node.url.as_deref(), Some("https://example.com/"));
+        assert_eq!(node.property("Checked"), Some("mixed"));
+        assert!(!node.is_expanded());
+    }
+
     #[test]
     fn test_ax_node_is_block() {
         let mut node = AXNode {
@@ -1263,6 +2204,115 @@ mod tests {
         assert_eq!(nodes[2].node_id, "child2");
     }
 
+    #[test]
+    fn test_ax_tree_text_of() {
+        let tree = tree_from_rows(&[
+            ("p", "paragraph", None, &["t1", "em"]),
+            ("t1", "StaticText", Some("Hello, "), &["box1"]),
+            ("box1", "InlineTextBox", Some("Hello, "), &[]),
+            ("em", "emphasis", None, &["t2", "t3"]),
+            ("t2", "StaticText", Some("big "), &[]),
+            ("t3", "StaticText", Some("world"), &[]),
+        ]);
+
+        let root = tree.root().unwrap();
+        assert_eq!(tree.text_of(root), "Hello, big world");
+        assert_eq!(tree.text_of(tree.get("em").unwrap()), "big world");
+    }
+
+    #[test]
+    fn test_ax_tree_find_by_role() {
+        let tree = tree_from_rows(&[
+            ("root", "RootWebArea", None, &["h1", "section", "h3"]),
+            ("h1", "heading", Some("Intro"), &[]),
+            ("section", "section", None, &["link1", "h2"]),
+            ("link1", "link", Some("First link"), &[]),
+            ("h2", "heading", Some("Details"), &[]),
+            ("h3", "heading", Some("Summary"), &[]),
+        ]);
+
+        let headings: Vec<_> = tree.find_by_role("heading").iter().map(|n| n.node_id.as_str()).collect();
+        assert_eq!(headings, vec!["h1", "h2", "h3"]);
+        assert_eq!(tree.find_first_by_role("link").map(|n| n.node_id.as_str()), Some("link1"));
+        assert!(tree.find_first_by_role("table").is_none());
+        assert!(tree.find_by_role("table").is_empty());
+    }
+
+    #[test]
+    fn test_ax_tree_serializes_to_json() {
+        let mut tree = tree_from_rows(&[
+            ("1", "RootWebArea", Some("Page"), &["2", "3"]),
+            ("2", "heading", Some("Title"), &[]),
+            ("3", "link", Some("Home"), &[]),
+        ]);
+        let heading = tree.nodes.get_mut("2").unwrap();
+        heading.level = Some(1);
+        heading.properties.insert("Live".to_string(), "polite".to_string());
+        heading.properties.insert("Expanded".to_string(), "false".to_string());
+
+        let json = serde_json::to_value(&tree).unwrap();
+        assert_eq!(json["root_id"], "1");
+        assert_eq!(json["nodes"]["1"]["role"], "RootWebArea");
+        assert_eq!(json["nodes"]["1"]["child_ids"], serde_json::json!(["2", "3"]));
+        assert_eq!(json["nodes"]["2"]["role"], "heading");
+        assert_eq!(json["nodes"]["2"]["level"], 1);
+        assert_eq!(json["nodes"]["3"]["role"], "link");
+        assert_eq!(json["nodes"]["3"]["properties"], serde_json::json!({}));
+
+        // Properties are emitted in sorted key order
+        let text = serde_json::to_string(&tree).unwrap();
+        assert!(text.contains(r#""properties":{"Expanded":"false","Live":"polite"}"#));
+
+        // And the saved tree loads back unchanged
+        let restored = AXTree::from_json(&tree.to_json().unwrap()).unwrap();
+        assert_eq!(restored.root_id.as_deref(), Some("1"));
+        assert_eq!(restored.nodes.len(), tree.nodes.len());
+        for (id, node) in &tree.nodes {
+            let copy = restored.get(id).unwrap();
+            assert_eq!(copy.role, node.role);
+            assert_eq!(copy.name, node.name);
+            assert_eq!(copy.child_ids, node.child_ids);
+            assert_eq!(copy.level, node.level);
+            assert_eq!(copy.properties, node.properties);
+        }
+        assert_eq!(ax_tree_to_markdown(&restored).0, ax_tree_to_markdown(&tree).0);
+
+        assert!(AXTree::from_json("{\"nodes\": 3}").is_err());
+    }
+
+    #[test]
+    fn test_ax_table_pads_wide_characters() {
+        let cell = |id: &str, role: &str, name: &str| {
+            format!(r#""{id}": {{"node_id": "{id}", "role": "{role}", "name": "{name}"}}"#)
+        };
+        let nodes = [
+            r#""root": {"node_id": "root", "role": "RootWebArea", "child_ids": ["table"]}"#.to_string(),
+            r#""table": {"node_id": "table", "role": "table", "child_ids": ["r1", "r2"]}"#.to_string(),
+            r#""r1": {"node_id": "r1", "role": "row", "child_ids": ["h1", "h2"]}"#.to_string(),
+            r#""r2": {"node_id": "r2", "role": "row", "child_ids": ["c1", "c2"]}"#.to_string(),
+            cell("h1", "columnheader", "都市"),
+            cell("h2", "columnheader", "Café"),
+            cell("c1", "cell", "東京"),
+            cell("c2", "cell", "Crème brûlée"),
+        ];
+        let json = format!(r#"{{"nodes": {{{}}}, "root_id": "root"}}"#, nodes.join(", "));
+        let tree = AXTree::from_json(&json).unwrap();
+
+        let (markdown, _) = ax_tree_to_markdown(&tree);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "| 都市 | Café         |",
+                "| ---- | ------------ |",
+                "| 東京 | Crème brûlée |",
+            ]
+        );
+        for line in &lines {
+            assert_eq!(line.width(), lines[1].width());
+        }
+    }
+
     #[test]
     fn test_ax_to_markdown_heading() {
         let mut tree = AXTree::new();
@@ -1362,7 +2412,7 @@ mod tests {
     }
 
     #[test]
-    fn test_ax_to_markdown_list() {
+    fn test_ax_to_markdown_hidden_links() {
         let mut tree = AXTree::new();
 
         let root = AXNode {
@@ -1375,43 +2425,145 @@ mod tests {
             url: None,
             focused: false,
             ignored: false,
-            child_ids: vec!["list".to_string()],
+            child_ids: vec!["p".to_string()],
             properties: HashMap::new(),
         };
 
-        let list = AXNode {
-            node_id: "list".to_string(),
-            role: "list".to_string(),
-            name: None,
+        let paragraph = AXNode {
+            node_id: "p".to_string(),
+            role: "paragraph".to_string(),
+            name: Some("".to_string()),
             value: None,
             description: None,
             level: None,
             url: None,
             focused: false,
             ignored: false,
-            child_ids: vec!["item1".to_string(), "item2".to_string()],
+            child_ids: vec!["link".to_string()],
             properties: HashMap::new(),
         };
 
-        let item1 = AXNode {
-            node_id: "item1".to_string(),
-            role: "listitem".to_string(),
-            name: Some("First item".to_string()),
+        let link = AXNode {
+            node_id: "link".to_string(),
+            role: "link".to_string(),
+            name: Some("Click here".to_string()),
             value: None,
             description: None,
             level: None,
-            url: None,
+            url: Some("https://example.com".to_string()),
             focused: false,
             ignored: false,
             child_ids: vec![],
             properties: HashMap::new(),
         };
 
-        let item2 = AXNode {
-            node_id: "item2".to_string(),
-            role: "listitem".to_string(),
-            name: Some("Second item".to_string()),
-            value: None,
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), root);
+        tree.nodes.insert("p".to_string(), paragraph);
+        tree.nodes.insert("link".to_string(), link);
+
+        let options = AxConversionOptions {
+            link_style: LinkStyle::Hidden,
+            ..Default::default()
+        };
+        let (markdown, links) = ax_tree_to_markdown_with_options(&tree, &options);
+        assert_eq!(markdown.trim(), "Click here");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com");
+    }
+
+    #[test]
+    fn test_ax_to_markdown_link_title() {
+        let mut tree = AXTree::new();
+
+        let root = AXNode {
+            node_id: "root".to_string(),
+            role: "RootWebArea".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["link".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let link = AXNode {
+            node_id: "link".to_string(),
+            role: "link".to_string(),
+            name: Some("Docs".to_string()),
+            value: None,
+            description: Some("Read the documentation".to_string()),
+            level: None,
+            url: Some("https://example.com/docs".to_string()),
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), root);
+        tree.nodes.insert("link".to_string(), link);
+
+        let (markdown, links) = ax_tree_to_markdown(&tree);
+        assert!(markdown.contains(r#"[Docs](https://example.com/docs "Read the documentation")"#));
+        assert_eq!(links[0].title.as_deref(), Some("Read the documentation"));
+    }
+
+    #[test]
+    fn test_ax_to_markdown_list() {
+        let mut tree = AXTree::new();
+
+        let root = AXNode {
+            node_id: "root".to_string(),
+            role: "RootWebArea".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["list".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let list = AXNode {
+            node_id: "list".to_string(),
+            role: "list".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["item1".to_string(), "item2".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let item1 = AXNode {
+            node_id: "item1".to_string(),
+            role: "listitem".to_string(),
+            name: Some("First item".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        let item2 = AXNode {
+            node_id: "item2".to_string(),
+            role: "listitem".to_string(),
+            name: Some("Second item".to_string()),
+            value: None,
             description: None,
             level: None,
             url: None,
@@ -1431,4 +2583,370 @@ mod tests {
         assert!(markdown.contains("- First item"));
         assert!(markdown.contains("- Second item"));
     }
+
+    #[test]
+    fn test_ax_to_markdown_keep_navigation() {
+        let mut tree = AXTree::new();
+
+        let root = AXNode {
+            node_id: "root".to_string(),
+            role: "RootWebArea".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["nav".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let nav = AXNode {
+            node_id: "nav".to_string(),
+            role: "navigation".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["link".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let link = AXNode {
+            node_id: "link".to_string(),
+            role: "link".to_string(),
+            name: Some("Home".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: Some("https://example.com/".to_string()),
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), root);
+        tree.nodes.insert("nav".to_string(), nav);
+        tree.nodes.insert("link".to_string(), link);
+
+        let (markdown, links) = ax_tree_to_markdown(&tree);
+        assert!(!markdown.contains("[Home]"));
+        assert!(links.is_empty());
+
+        let mut options = AxConversionOptions::default();
+        options.keep_roles.insert("navigation".to_string());
+        let (markdown, links) = ax_tree_to_markdown_with_options(&tree, &options);
+        assert!(markdown.contains("[Home](https://example.com/)"));
+        assert_eq!(links.len(), 1);
+    }
+
+    #[test]
+    fn test_ax_to_markdown_live_region() {
+        let mut tree = AXTree::new();
+
+        let root = AXNode {
+            node_id: "root".to_string(),
+            role: "RootWebArea".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["log".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let log = AXNode {
+            node_id: "log".to_string(),
+            role: "log".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["msg1".to_string(), "msg2".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let msg1 = AXNode {
+            node_id: "msg1".to_string(),
+            role: "StaticText".to_string(),
+            name: Some("Hello there".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        let msg2 = AXNode {
+            node_id: "msg2".to_string(),
+            role: "StaticText".to_string(),
+            name: Some("General Kenobi".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), root);
+        tree.nodes.insert("log".to_string(), log);
+        tree.nodes.insert("msg1".to_string(), msg1);
+        tree.nodes.insert("msg2".to_string(), msg2);
+
+        // Without the option the log's text is kept, just not split up
+        let (markdown, _links) = ax_tree_to_markdown(&tree);
+        assert_eq!(markdown, "Hello thereGeneral Kenobi\n");
+        assert!(ax_tree_to_plain(&tree).contains("Hello there"));
+        assert!(ax_tree_to_html(&tree).contains("Hello there"));
+
+        let options = AxConversionOptions {
+            include_live_regions: true,
+            ..Default::default()
+        };
+        let (markdown, _links) = ax_tree_to_markdown_with_options(&tree, &options);
+        assert_eq!(markdown, "Hello there\n\nGeneral Kenobi\n");
+    }
+
+    #[test]
+    fn test_ax_to_markdown_figure() {
+        let mut tree = AXTree::new();
+
+        let root = AXNode {
+            node_id: "root".to_string(),
+            role: "RootWebArea".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["p".to_string(), "figure".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let paragraph = AXNode {
+            node_id: "p".to_string(),
+            role: "paragraph".to_string(),
+            name: Some("Intro".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        let figure = AXNode {
+            node_id: "figure".to_string(),
+            role: "figure".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["img".to_string(), "caption".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let image = AXNode {
+            node_id: "img".to_string(),
+            role: "image".to_string(),
+            name: Some("A cat".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: Some("https://example.com/cat.jpg".to_string()),
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        let caption = AXNode {
+            node_id: "caption".to_string(),
+            role: "Figcaption".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["caption_text".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let caption_text = AXNode {
+            node_id: "caption_text".to_string(),
+            role: "StaticText".to_string(),
+            name: Some("My cat".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), root);
+        tree.nodes.insert("p".to_string(), paragraph);
+        tree.nodes.insert("figure".to_string(), figure);
+        tree.nodes.insert("img".to_string(), image);
+        tree.nodes.insert("caption".to_string(), caption);
+        tree.nodes.insert("caption_text".to_string(), caption_text);
+
+        let (markdown, _links) = ax_tree_to_markdown(&tree);
+        assert_eq!(
+            markdown,
+            "Intro\n\n![A cat](https://example.com/cat.jpg)\n*My cat*\n"
+        );
+    }
+
+    #[test]
+    fn test_ax_to_markdown_data_uri_image() {
+        let mut tree = AXTree::new();
+
+        let root = AXNode {
+            node_id: "root".to_string(),
+            role: "RootWebArea".to_string(),
+            name: None,
+            value: None,
+            description: None,
+            level: None,
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: vec!["img".to_string()],
+            properties: HashMap::new(),
+        };
+
+        let image = AXNode {
+            node_id: "img".to_string(),
+            role: "image".to_string(),
+            name: Some("Logo".to_string()),
+            value: None,
+            description: None,
+            level: None,
+            url: Some(format!("data:image/gif;base64,{}", "R0lGODlh".repeat(200))),
+            focused: false,
+            ignored: false,
+            child_ids: vec![],
+            properties: HashMap::new(),
+        };
+
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), root);
+        tree.nodes.insert("img".to_string(), image);
+
+        let (markdown, _links) = ax_tree_to_markdown(&tree);
+        assert_eq!(markdown, "![Logo](data:image/gif)\n");
+    }
+
+    #[test]
+    fn test_ax_to_plain() {
+        let mut tree = tree_from_rows(&[
+            ("root", "RootWebArea", None, &["h1", "p", "list"]),
+            ("h1", "heading", Some("Title"), &[]),
+            ("p", "paragraph", None, &["t1", "link", "t3"]),
+            ("t1", "StaticText", Some("Read the "), &[]),
+            ("link", "link", None, &["t2"]),
+            ("t2", "StaticText", Some("docs"), &[]),
+            ("t3", "StaticText", Some(" now."), &[]),
+            ("list", "list", None, &["li1"]),
+            ("li1", "listitem", Some("One"), &[]),
+        ]);
+        for node in tree.nodes.values_mut() {
+            node.level = Some(1);
+        }
+        tree.nodes.get_mut("link").unwrap().url = Some("https://example.com/docs".to_string());
+
+        let plain = ax_tree_to_plain(&tree);
+        assert_eq!(plain, "Title\n\nRead the docs now.\n\nOne\n");
+    }
+
+    #[test]
+    fn test_ax_to_html() {
+        let mut tree = tree_from_rows(&[
+            ("root", "RootWebArea", None, &["h2", "p", "list", "table"]),
+            ("h2", "heading", Some("Tips & <Tricks>"), &[]),
+            ("p", "paragraph", None, &["t1", "link", "skip"]),
+            ("t1", "StaticText", Some("Read the "), &[]),
+            ("link", "link", None, &["t2"]),
+            ("t2", "StaticText", Some("docs"), &[]),
+            ("skip", "link", Some("Skip to content"), &[]),
+            ("list", "list", None, &["li1", "li2"]),
+            ("li1", "listitem", Some("One"), &[]),
+            ("li2", "listitem", None, &["strong"]),
+            ("strong", "strong", Some("Two"), &[]),
+            ("table", "table", None, &["r1", "r2"]),
+            ("r1", "row", None, &["th"]),
+            ("th", "columnheader", Some("Name"), &[]),
+            ("r2", "row", Some("Fox"), &[]),
+        ]);
+        for node in tree.nodes.values_mut() {
+            node.level = Some(2);
+        }
+        let link = tree.nodes.get_mut("link").unwrap();
+        link.url = Some("https://example.com/docs?a=1&b=2".to_string());
+        link.description = Some("The \"docs\"".to_string());
+        tree.nodes.get_mut("skip").unwrap().url = Some("#main".to_string());
+
+        let html = ax_tree_to_html(&tree);
+        assert_eq!(
+            html,
+            "<h2>Tips &amp; &lt;Tricks&gt;</h2>\n\
+             <p>Read the <a href=\"https://example.com/docs?a=1&amp;b=2\" title=\"The &quot;docs&quot;\">docs</a></p>\n\
+             <ul>\n\
+             <li>One</li>\n\
+             <li><strong>Two</strong></li>\n\
+             </ul>\n\
+             <table>\n\
+             <tr><th>Name</th></tr>\n\
+             </table>\n"
+        );
+    }
+
+    #[cfg(feature = "live-browser-tests")]
+    #[tokio::test]
+    async fn test_set_document_content_live() {
+        let mut chrome = crate::ChromeManager::with_config(crate::ChromeConfig::default());
+        let browser = chrome.get_browser().await.unwrap();
+        let handle = browser.lock().await;
+        let page = handle.browser.new_page("about:blank").await.unwrap();
+
+        set_document_content(&page, "<h1>Hello fox</h1><p>Some text</p>").await.unwrap();
+        let tree = fetch_ax_tree(&page).await.unwrap();
+        let _ = page.close().await;
+
+        let heading = tree.find_first_by_role("heading").expect("heading node");
+        assert_eq!(heading.name.as_deref(), Some("Hello fox"));
+        assert_eq!(heading.level, Some(1));
+    }
 }
@@ -0,0 +1,74 @@
+//! Host blocklists for ads and trackers
+
+use crate::Result;
+use std::path::Path;
+use url::Url;
+
+/// Check whether `host` is covered by a blocklist entry
+///
+/// An entry blocks its own host and every subdomain; a leading `*.` is
+/// accepted and means the same thing. Matching ignores case.
+pub fn host_is_blocked(host: &str, blocklist: &[String]) -> bool {
+    let host = host.trim_end_matches('.').to_lowercase();
+    blocklist.iter().any(|entry| {
+        let entry = entry.to_lowercase();
+        let domain = entry.strip_prefix("*.").unwrap_or(&entry);
+        !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+    })
+}
+
+/// Check whether the host of `url` is blocklisted
+///
+/// URLs that don't parse or have no host are never blocked.
+pub fn url_is_blocked(url: &str, blocklist: &[String]) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host_is_blocked(host, blocklist)))
+        .unwrap_or(false)
+}
+
+/// Parse a blocklist, one host per line
+///
+/// Blank lines and `#` comments are skipped. Hosts-file lines such as
+/// `0.0.0.0 ads.example.com` are accepted, keeping just the host.
+pub fn parse_blocklist(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter_map(|line| line.split_whitespace().last())
+        .filter(|host| !matches!(*host, "localhost" | "0.0.0.0" | "127.0.0.1"))
+        .map(|host| host.to_lowercase())
+        .collect()
+}
+
+/// Load a blocklist file in the format accepted by [`parse_blocklist`]
+pub fn load_blocklist(path: &Path) -> Result<Vec<String>> {
+    Ok(parse_blocklist(&std::fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_is_blocked() {
+        let blocklist = vec!["doubleclick.net".to_string(), "*.tracker.io".to_string()];
+        assert!(host_is_blocked("doubleclick.net", &blocklist));
+        assert!(host_is_blocked("stats.g.DoubleClick.net", &blocklist));
+        assert!(host_is_blocked("tracker.io", &blocklist));
+        assert!(host_is_blocked("a.tracker.io", &blocklist));
+        assert!(!host_is_blocked("notdoubleclick.net", &blocklist));
+        assert!(!host_is_blocked("example.com", &blocklist));
+
+        assert!(url_is_blocked("https://ad.doubleclick.net/x.js", &blocklist));
+        assert!(!url_is_blocked("data:text/plain,hi", &blocklist));
+    }
+
+    #[test]
+    fn test_parse_blocklist() {
+        let text = "# ads\nads.example.com\n\n0.0.0.0 tracker.example.net # pixel\n127.0.0.1 localhost\n";
+        assert_eq!(
+            parse_blocklist(text),
+            vec!["ads.example.com".to_string(), "tracker.example.net".to_string()]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Character encoding detection for fetched HTML
+
+use encoding_rs::{Encoding, UTF_8};
+use regex::Regex;
+
+/// How many leading bytes are scanned for a `<meta charset>` declaration
+const META_SCAN_BYTES: usize = 4096;
+
+/// Decode an HTML response body to UTF-8
+///
+/// The encoding is taken from a byte order mark, then the `Content-Type`
+/// header's `charset`, then a `<meta>` declaration near the start of the
+/// document, falling back to UTF-8. Invalid sequences become U+FFFD.
+pub fn decode_html(bytes: &[u8], content_type: Option<&str>) -> String {
+    let encoding = content_type
+        .and_then(charset_from_content_type)
+        .or_else(|| charset_from_meta(bytes))
+        .unwrap_or(UTF_8);
+
+    // `decode` lets a BOM override the declared encoding
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+/// Encoding named by the `charset` parameter of a `Content-Type` value
+fn charset_from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case("charset") {
+            Encoding::for_label(value.trim().trim_matches(|c| c == '"' || c == '\'').as_bytes())
+        } else {
+            None
+        }
+    })
+}
+
+/// Encoding declared by `<meta charset>` or `<meta http-equiv="Content-Type">`
+fn charset_from_meta(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(META_SCAN_BYTES)]);
+    let re = Regex::new(r#"(?i)<meta[^>]+charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).unwrap();
+    let label = re.captures(&head)?.get(1)?.as_str();
+    Encoding::for_label(label.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_latin1_from_header() {
+        // "Café crème" in ISO-8859-1
+        let bytes = b"<p>Caf\xe9 cr\xe8me</p>";
+        let html = decode_html(bytes, Some("text/html; charset=ISO-8859-1"));
+        assert_eq!(html, "<p>Café crème</p>");
+    }
+
+    #[test]
+    fn test_decode_meta_charset() {
+        // "日本" in Shift_JIS
+        let mut bytes = br#"<html><head><meta charset="Shift_JIS"></head><body>"#.to_vec();
+        bytes.extend_from_slice(b"\x93\xfa\x96\x7b");
+        bytes.extend_from_slice(b"</body></html>");
+        let html = decode_html(&bytes, Some("text/html"));
+        assert!(html.contains("<body>日本</body>"));
+
+        let bytes = b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1252\"><p>\x93quoted\x94</p>";
+        assert!(decode_html(bytes, None).contains("\u{201c}quoted\u{201d}"));
+    }
+
+    #[test]
+    fn test_decode_defaults_to_utf8() {
+        let html = decode_html("<p>naïve</p>".as_bytes(), None);
+        assert_eq!(html, "<p>naïve</p>");
+    }
+}
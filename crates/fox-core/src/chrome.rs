@@ -5,6 +5,8 @@
 
 use crate::{FoxError, Result};
 use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::fetch::{RequestPattern, RequestStage};
+use chromiumoxide::cdp::browser_protocol::network;
 use futures::StreamExt;
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
@@ -72,6 +74,82 @@ impl std::fmt::Display for ExtractionMethod {
     }
 }
 
+/// Kinds of subresource that can be blocked while rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceType {
+    Image,
+    Font,
+    Media,
+    Stylesheet,
+}
+
+impl ResourceType {
+    /// Resources blocked by default for an extraction method
+    ///
+    /// Images and media never affect the accessibility tree, so AX
+    /// extraction skips them. Readability keeps everything.
+    pub fn defaults_for(method: ExtractionMethod) -> Vec<ResourceType> {
+        match method {
+            ExtractionMethod::Accessibility => vec![ResourceType::Image, ResourceType::Media],
+            ExtractionMethod::Readability => Vec::new(),
+        }
+    }
+
+    /// The matching CDP resource type
+    pub(crate) fn to_cdp(self) -> network::ResourceType {
+        match self {
+            ResourceType::Image => network::ResourceType::Image,
+            ResourceType::Font => network::ResourceType::Font,
+            ResourceType::Media => network::ResourceType::Media,
+            ResourceType::Stylesheet => network::ResourceType::Stylesheet,
+        }
+    }
+}
+
+impl std::str::FromStr for ResourceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "image" | "images" => Ok(ResourceType::Image),
+            "font" | "fonts" => Ok(ResourceType::Font),
+            "media" => Ok(ResourceType::Media),
+            "stylesheet" | "stylesheets" | "css" => Ok(ResourceType::Stylesheet),
+            _ => Err(format!(
+                "Unknown resource type: {}. Use 'image', 'font', 'media', or 'stylesheet'",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceType::Image => write!(f, "image"),
+            ResourceType::Font => write!(f, "font"),
+            ResourceType::Media => write!(f, "media"),
+            ResourceType::Stylesheet => write!(f, "stylesheet"),
+        }
+    }
+}
+
+/// Request interception patterns that pause requests of the blocked types
+///
+/// Empty when nothing is blocked, in which case interception stays off.
+pub fn block_patterns(block: &[ResourceType]) -> Vec<RequestPattern> {
+    block
+        .iter()
+        .map(|kind| {
+            RequestPattern::builder()
+                .url_pattern("*")
+                .resource_type(kind.to_cdp())
+                .request_stage(RequestStage::Request)
+                .build()
+        })
+        .collect()
+}
+
 /// Configuration for Chrome browser management
 #[derive(Debug, Clone)]
 pub struct ChromeConfig {
@@ -85,6 +163,14 @@ pub struct ChromeConfig {
     pub auto_update: bool,
     /// Content extraction method
     pub extraction_method: ExtractionMethod,
+    /// Persistent Chrome profile directory
+    ///
+    /// When set, cookies, localStorage, and logins survive between runs.
+    /// Chrome locks a profile while it is open, so two browser instances
+    /// cannot share one directory. `None` uses an ephemeral profile.
+    pub user_data_dir: Option<PathBuf>,
+    /// Subresources aborted during rendering to save bandwidth and time
+    pub block_resources: Vec<ResourceType>,
 }
 
 impl Default for ChromeConfig {
@@ -99,10 +185,25 @@ impl Default for ChromeConfig {
             data_dir,
             auto_update: true,
             extraction_method: ExtractionMethod::default(),
+            user_data_dir: None,
+            block_resources: ResourceType::defaults_for(ExtractionMethod::default()),
         }
     }
 }
 
+/// Chrome installation details, as reported by `fox info`
+#[derive(Debug, Clone)]
+pub struct ChromeInfo {
+    /// Chrome for Testing platform string for this machine
+    pub platform: &'static str,
+    /// Where Chrome would be taken from under the configured mode
+    pub source: ChromeSource,
+    /// Version of the bundled Chrome, from its `version.txt` marker
+    pub bundled_version: Option<String>,
+    /// System Chrome, whether or not the configured mode uses it
+    pub system_chrome: Option<PathBuf>,
+}
+
 /// Handle to a running browser instance
 pub struct BrowserHandle {
     pub browser: Browser,
@@ -116,6 +217,7 @@ pub struct ChromeManager {
     source: ChromeSource,
     browser: Option<Arc<Mutex<BrowserHandle>>>,
     progress_callback: Option<Arc<ProgressCallback>>,
+    manifest_url: String,
 }
 
 impl ChromeManager {
@@ -135,9 +237,21 @@ impl ChromeManager {
             source: ChromeSource::None,
             browser: None,
             progress_callback: None,
+            manifest_url: Self::MANIFEST_URL.to_string(),
         }
     }
 
+    /// Fetch the Chrome for Testing manifest from `url` instead
+    #[cfg(test)]
+    pub(crate) fn set_manifest_url(&mut self, url: String) {
+        self.manifest_url = url;
+    }
+
+    /// The configuration this manager was created with
+    pub fn config(&self) -> &ChromeConfig {
+        &self.config
+    }
+
     /// Set a callback for download progress updates
     pub fn set_progress_callback<F>(&mut self, callback: F)
     where
@@ -248,7 +362,7 @@ impl ChromeManager {
         // Fetch manifest
         let client = reqwest::Client::new();
         let manifest: ChromeManifest = client
-            .get(Self::MANIFEST_URL)
+            .get(&self.manifest_url)
             .send()
             .await
             .map_err(|e| FoxError::BrowserError(format!("Failed to fetch Chrome manifest: {}", e)))?
@@ -386,13 +500,50 @@ impl ChromeManager {
         let chrome_path = self.ensure_chrome().await?;
 
         // Launch browser
-        let handle = launch_browser(&chrome_path).await?;
+        let handle = launch_browser(&chrome_path, self.config.user_data_dir.as_deref()).await?;
         let browser = Arc::new(Mutex::new(handle));
         self.browser = Some(Arc::clone(&browser));
 
         Ok(browser)
     }
 
+    /// Describe the Chrome installation for diagnostics
+    ///
+    /// Resolves the source the configured mode would pick from what is
+    /// already installed; nothing is downloaded or launched.
+    pub fn info(&self) -> ChromeInfo {
+        let bundled = self.find_bundled_chrome();
+        let system = find_system_chrome();
+        let configured = self.config.chrome_path.clone().filter(|path| path.exists());
+
+        let source = match self.config.mode.as_str() {
+            "none" => ChromeSource::None,
+            "system" => configured
+                .or_else(|| system.clone())
+                .map_or(ChromeSource::None, ChromeSource::System),
+            "bundled" => bundled.clone().map_or(ChromeSource::None, ChromeSource::Bundled),
+            _ => bundled
+                .clone()
+                .map(ChromeSource::Bundled)
+                .or_else(|| system.clone().map(ChromeSource::System))
+                .unwrap_or(ChromeSource::None),
+        };
+
+        let bundled_version = bundled.and_then(|_| {
+            std::fs::read_to_string(self.config.data_dir.join("chrome").join("version.txt"))
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        });
+
+        ChromeInfo {
+            platform: get_platform(),
+            source,
+            bundled_version,
+            system_chrome: system,
+        }
+    }
+
     /// Check if Chrome is available (without downloading)
     pub fn is_chrome_available(&self) -> bool {
         matches!(
@@ -419,30 +570,13 @@ impl Default for ChromeManager {
 }
 
 /// Launch a browser instance with the given Chrome path
-async fn launch_browser(chrome_path: &Path) -> Result<BrowserHandle> {
+async fn launch_browser(chrome_path: &Path, user_data_dir: Option<&Path>) -> Result<BrowserHandle> {
     debug!("Launching browser from {:?}", chrome_path);
 
-    // Use a realistic Chrome user agent to avoid bot detection
-    let user_agent = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
-
     let (browser, mut handler) = Browser::launch(
         BrowserConfig::builder()
             .chrome_executable(chrome_path)
-            // Basic Chrome arguments
-            .arg("--disable-gpu")
-            .arg("--no-sandbox")
-            .arg("--disable-dev-shm-usage")
-            .arg("--disable-software-rasterizer")
-            // Anti-bot-detection arguments
-            .arg("--disable-blink-features=AutomationControlled")
-            .arg(format!("--user-agent={}", user_agent))
-            // Reduce fingerprinting signals
-            .arg("--disable-extensions")
-            .arg("--disable-infobars")
-            .arg("--disable-popup-blocking")
-            .arg("--disable-default-apps")
-            // Window size for consistent rendering
-            .arg("--window-size=1920,1080")
+            .args(launch_args(user_data_dir))
             .build()
             .map_err(|e| FoxError::BrowserError(e.to_string()))?,
     )
@@ -456,6 +590,37 @@ async fn launch_browser(chrome_path: &Path) -> Result<BrowserHandle> {
     Ok(BrowserHandle { browser, handle })
 }
 
+/// Command-line arguments passed to Chrome at launch
+fn launch_args(user_data_dir: Option<&Path>) -> Vec<String> {
+    // Use a realistic Chrome user agent to avoid bot detection
+    let user_agent = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+
+    let mut args = vec![
+        // Basic Chrome arguments
+        "--disable-gpu".to_string(),
+        "--no-sandbox".to_string(),
+        "--disable-dev-shm-usage".to_string(),
+        "--disable-software-rasterizer".to_string(),
+        // Anti-bot-detection arguments
+        "--disable-blink-features=AutomationControlled".to_string(),
+        format!("--user-agent={}", user_agent),
+        // Reduce fingerprinting signals
+        "--disable-extensions".to_string(),
+        "--disable-infobars".to_string(),
+        "--disable-popup-blocking".to_string(),
+        "--disable-default-apps".to_string(),
+        // Window size for consistent rendering
+        "--window-size=1920,1080".to_string(),
+    ];
+
+    // Persistent profile so cookies and logins survive between runs
+    if let Some(dir) = user_data_dir {
+        args.push(format!("--user-data-dir={}", dir.display()));
+    }
+
+    args
+}
+
 /// Get the platform identifier for Chrome for Testing downloads
 fn get_platform() -> &'static str {
     match (std::env::consts::OS, std::env::consts::ARCH) {
@@ -583,6 +748,61 @@ struct Download {
     url: String,
 }
 
+/// Serve a fake Chrome for Testing manifest and download from a local
+/// server, returning the manifest URL
+///
+/// The "binary" is a stub script, so it downloads and installs but cannot
+/// be launched.
+#[cfg(test)]
+pub(crate) async fn serve_fake_chrome(version: &str) -> String {
+    use std::io::Write;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let platform = get_platform();
+    let binary = if cfg!(windows) {
+        "chrome-headless-shell.exe"
+    } else {
+        "chrome-headless-shell"
+    };
+    let mut archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    archive
+        .start_file(
+            format!("chrome-headless-shell-{}/{}", platform, binary),
+            zip::write::FileOptions::default().unix_permissions(0o755),
+        )
+        .unwrap();
+    archive.write_all(b"#!/bin/sh\nexit 1\n").unwrap();
+    let zip_bytes = archive.finish().unwrap().into_inner();
+
+    // Serve a manifest pointing at the zip, both from a local server
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let manifest = format!(
+        r#"{{"channels":{{"Stable":{{"version":"{}","downloads":{{"chrome-headless-shell":[{{"platform":"{}","url":"http://{}/chrome.zip"}}]}}}}}}}}"#,
+        version, platform, addr
+    );
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let body = if request.starts_with("GET /chrome.zip") {
+                zip_bytes.clone()
+            } else {
+                manifest.clone().into_bytes()
+            };
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(&body).await;
+        }
+    });
+
+    format!("http://{}/manifest.json", addr)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -604,6 +824,90 @@ mod tests {
         assert_eq!(config.mode, "auto");
         assert!(config.chrome_path.is_none());
         assert!(config.auto_update);
+        assert!(config.user_data_dir.is_none());
+        assert_eq!(config.block_resources, vec![ResourceType::Image, ResourceType::Media]);
+    }
+
+    #[test]
+    fn test_block_patterns() {
+        assert!(block_patterns(&[]).is_empty());
+        assert!(ResourceType::defaults_for(ExtractionMethod::Readability).is_empty());
+
+        let patterns = block_patterns(&[ResourceType::Image, ResourceType::Font]);
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].resource_type, Some(network::ResourceType::Image));
+        assert_eq!(patterns[1].resource_type, Some(network::ResourceType::Font));
+        assert!(patterns
+            .iter()
+            .all(|p| p.request_stage == Some(RequestStage::Request)));
+    }
+
+    #[tokio::test]
+    async fn test_download_reports_progress_in_order() {
+        let manifest_url = serve_fake_chrome("1.2.3").await;
+
+        let data_dir =
+            std::env::temp_dir().join(format!("fox-download-test-{}", std::process::id()));
+        let mut manager = ChromeManager::with_config(ChromeConfig {
+            mode: "bundled".to_string(),
+            data_dir: data_dir.clone(),
+            ..Default::default()
+        });
+        manager.set_manifest_url(manifest_url);
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        manager.set_progress_callback(move |progress| {
+            let kind = match progress {
+                DownloadProgress::Starting { version } => format!("starting {}", version),
+                DownloadProgress::Downloading { .. } => "downloading".to_string(),
+                DownloadProgress::Extracting => "extracting".to_string(),
+                DownloadProgress::Complete { .. } => "complete".to_string(),
+                DownloadProgress::Failed { error } => format!("failed {}", error),
+            };
+            let mut events = sink.lock().unwrap();
+            if events.last() != Some(&kind) {
+                events.push(kind);
+            }
+        });
+
+        let path = manager.ensure_chrome().await.unwrap();
+        assert!(path.exists());
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["starting 1.2.3", "downloading", "extracting", "complete"]
+        );
+        assert_eq!(manager.info().bundled_version.as_deref(), Some("1.2.3"));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn test_info_without_chrome() {
+        let config = ChromeConfig {
+            mode: "bundled".to_string(),
+            data_dir: std::env::temp_dir().join(format!("fox-info-test-{}", std::process::id())),
+            ..Default::default()
+        };
+        let info = ChromeManager::with_config(config).info();
+        assert!(matches!(info.source, ChromeSource::None));
+        assert!(info.bundled_version.is_none());
+        assert_eq!(info.platform, get_platform());
+
+        let config = ChromeConfig {
+            mode: "none".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(ChromeManager::with_config(config).info().source, ChromeSource::None));
+    }
+
+    #[test]
+    fn test_launch_args_user_data_dir() {
+        let args = launch_args(None);
+        assert!(!args.iter().any(|a| a.starts_with("--user-data-dir")));
+
+        let args = launch_args(Some(Path::new("/tmp/fox-profile")));
+        assert!(args.contains(&"--user-data-dir=/tmp/fox-profile".to_string()));
     }
 
     #[test]
@@ -0,0 +1,178 @@
+//! File downloads
+//!
+//! Streams non-HTML resources (archives, PDFs, images) to disk instead of
+//! feeding them through the HTML pipeline.
+
+use crate::Result;
+use reqwest::header::CONTENT_DISPOSITION;
+use reqwest::Client;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+use url::Url;
+
+/// Filename used when neither the URL nor the response suggests one
+const DEFAULT_FILENAME: &str = "download";
+
+/// Download a URL into `dir`, returning the path of the saved file
+///
+/// The filename comes from the `Content-Disposition` header when present,
+/// otherwise from the last URL path segment. Existing files are never
+/// overwritten; a numeric suffix is added instead.
+pub async fn download_to_dir(client: &Client, url: &Url, dir: &Path) -> Result<PathBuf> {
+    use futures::TryStreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    debug!("Downloading: {}", url);
+    let response = client.get(url.as_str()).send().await?.error_for_status()?;
+
+    let disposition = response
+        .headers()
+        .get(CONTENT_DISPOSITION)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let filename = disposition
+        .as_deref()
+        .and_then(filename_from_content_disposition)
+        .unwrap_or_else(|| filename_from_url(url));
+
+    tokio::fs::create_dir_all(dir).await?;
+    let path = unique_path(dir, &filename);
+
+    let mut file = tokio::fs::File::create(&path).await?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.try_next().await? {
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    info!("Downloaded {} to {:?}", url, path);
+    Ok(path)
+}
+
+/// Derive a filename from the last non-empty segment of a URL's path
+pub fn filename_from_url(url: &Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+        .map(percent_decode)
+        .and_then(|name| sanitize_filename(&name))
+        .unwrap_or_else(|| DEFAULT_FILENAME.to_string())
+}
+
+/// Extract the filename from a `Content-Disposition` header value
+///
+/// Prefers the RFC 5987 `filename*=` form over plain `filename=`.
+pub fn filename_from_content_disposition(header: &str) -> Option<String> {
+    let mut plain = None;
+    let mut extended = None;
+
+    for part in header.split(';') {
+        let part = part.trim();
+        if let Some((key, value)) = part.split_once('=') {
+            let key = key.trim().to_lowercase();
+            let value = value.trim().trim_matches('"');
+            match key.as_str() {
+                "filename*" => {
+                    // charset'language'percent-encoded-name
+                    let encoded = value.splitn(3, '\'').nth(2).unwrap_or(value);
+                    extended = Some(percent_decode(encoded));
+                }
+                "filename" => plain = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    extended
+        .or(plain)
+        .and_then(|name| sanitize_filename(&name))
+}
+
+/// Strip any directory components and reject names that aren't usable
+fn sanitize_filename(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name).trim();
+    if name.is_empty() || name == "." || name == ".." {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Decode `%XX` escapes, leaving malformed sequences as-is
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 3 <= bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Pick a path in `dir` that doesn't exist yet, adding " (n)" if needed
+fn unique_path(dir: &Path, filename: &str) -> PathBuf {
+    let candidate = dir.join(filename);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, ext) = match filename.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (filename, String::new()),
+    };
+
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap_or(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filename_from_url() {
+        let url = Url::parse("https://example.com/files/report%202024.pdf?dl=1").unwrap();
+        assert_eq!(filename_from_url(&url), "report 2024.pdf");
+
+        let url = Url::parse("https://example.com/").unwrap();
+        assert_eq!(filename_from_url(&url), "download");
+    }
+
+    #[test]
+    fn test_percent_decode_trailing_escape() {
+        assert_eq!(percent_decode("report%20"), "report ");
+        assert_eq!(percent_decode("%41%42"), "AB");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("50%2"), "50%2");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_filename_from_content_disposition() {
+        assert_eq!(
+            filename_from_content_disposition(r#"attachment; filename="archive.zip""#),
+            Some("archive.zip".to_string())
+        );
+        assert_eq!(
+            filename_from_content_disposition(
+                r#"attachment; filename="fallback.txt"; filename*=UTF-8''na%C3%AFve.txt"#
+            ),
+            Some("naïve.txt".to_string())
+        );
+        assert_eq!(
+            filename_from_content_disposition(r#"attachment; filename="../../etc/passwd""#),
+            Some("passwd".to_string())
+        );
+        assert_eq!(filename_from_content_disposition("inline"), None);
+    }
+}
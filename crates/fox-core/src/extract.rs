@@ -1,11 +1,21 @@
 //! Readability-style content extraction
 
+use crate::markdown::LinkStyle;
 use crate::{markdown, ExtractedContent, FoxError, Link, Result};
+use regex::{Captures, Regex};
 use scraper::{ElementRef, Html, Selector};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use tracing::debug;
+use unicode_normalization::UnicodeNormalization;
 use url::Url;
 
+/// Marker appended to content cut short by `max_content_bytes`
+pub const TRUNCATION_MARKER: &str = "\n\n[content truncated]";
+
+/// Separators commonly placed between an article title and the site name
+const TITLE_SEPARATORS: &[&str] = &[" | ", " - ", " – ", " — ", " :: ", " · ", " » ", " / "];
+
 /// Extract the page title from HTML
 pub fn extract_title(html: &str) -> Option<String> {
     let document = Html::parse_document(html);
@@ -17,20 +27,625 @@ pub fn extract_title(html: &str) -> Option<String> {
         .map(|el| el.text().collect::<String>().trim().to_string())
 }
 
+/// Truncate extracted content to at most `max_bytes` of text
+///
+/// The cut is made at a UTF-8 character boundary and followed by
+/// [`TRUNCATION_MARKER`]. A link the limit falls inside is cut whole, so no
+/// broken markup is left behind, and links from the cut on are dropped.
+/// Content already within the limit is left untouched.
+pub fn truncate_content(content: &mut ExtractedContent, max_bytes: usize) {
+    if content.text.len() > max_bytes {
+        let mut cut = floor_char_boundary(&content.text, max_bytes);
+        for link in &content.links {
+            if link.position < cut && link_end(&content.text, link) > cut {
+                cut = link.position;
+            }
+        }
+        content.text.truncate(cut);
+        content.text.push_str(TRUNCATION_MARKER);
+        content.links.retain(|link| link.position < cut);
+    }
+
+    if let Some(ref mut plain) = content.plain_text {
+        if plain.len() > max_bytes {
+            plain.truncate(floor_char_boundary(plain, max_bytes));
+            plain.push_str(TRUNCATION_MARKER);
+        }
+    }
+}
+
+/// Replace `<iframe>` elements with the body HTML of the frames they load
+///
+/// `frames` maps absolute frame URLs to their body HTML. Iframes whose
+/// resolved `src` is not in the map are left as they are, so they still
+/// render as an `[IFRAME: ...]` placeholder.
+pub fn inline_iframes(html: &str, base_url: &Url, frames: &HashMap<String, String>) -> String {
+    if frames.is_empty() {
+        return html.to_string();
+    }
+
+    let iframe_re = Regex::new(r"(?is)<iframe\b[^>]*>.*?</iframe\s*>").unwrap();
+    let src_re = Regex::new(r#"(?i)\ssrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).unwrap();
+
+    iframe_re
+        .replace_all(html, |caps: &Captures| {
+            let tag = &caps[0];
+            let body = src_re
+                .captures(tag)
+                .and_then(|c| c.get(1).or_else(|| c.get(2)).or_else(|| c.get(3)))
+                .and_then(|src| base_url.join(&src.as_str().replace("&amp;", "&")).ok())
+                .and_then(|url| frames.get(url.as_str()));
+            match body {
+                Some(body) => format!("<div>{}</div>", body),
+                None => tag.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Strip invisible characters from extracted content
+///
+/// Applies [`markdown::sanitize`] to the text, plain text, title, and link
+/// text. Link positions are shifted back by the bytes removed before them.
+pub fn sanitize_content(content: &mut ExtractedContent) {
+    let mut removed = Vec::new();
+    for (i, c) in content.text.char_indices() {
+        if markdown::is_invisible(c) {
+            removed.push((i, c.len_utf8()));
+        }
+    }
+    if !removed.is_empty() {
+        content.text = markdown::sanitize(&content.text);
+        for link in &mut content.links {
+            let shift: usize = removed
+                .iter()
+                .take_while(|(i, _)| *i < link.position)
+                .map(|(_, len)| len)
+                .sum();
+            link.position -= shift;
+        }
+    }
+
+    for link in &mut content.links {
+        link.text = markdown::sanitize(&link.text);
+    }
+    if let Some(ref mut plain) = content.plain_text {
+        *plain = markdown::sanitize(plain);
+    }
+    if let Some(ref mut title) = content.title {
+        *title = markdown::sanitize(title);
+    }
+}
+
+/// Byte offset just past a link's markup in `text`: the closing `)` of
+/// `[text](url "title")`, or the end of the text for links shown as text only
+///
+/// A quoted title is skipped whole, so parentheses inside it don't end the
+/// link early.
+fn link_end(text: &str, link: &Link) -> usize {
+    let start = link.position.min(text.len());
+    let rest = &text[start..];
+    if !rest.starts_with('[') {
+        return start + link.text.len();
+    }
+    rest.find(&format!("]({}", link.url))
+        .map(|i| i + 2 + link.url.len())
+        .and_then(|url_end| {
+            let after = &rest[url_end..];
+            let title_len = match after.strip_prefix(" \"") {
+                Some(title) => closing_quote(title)? + 3,
+                None => 0,
+            };
+            after[title_len..].find(')').map(|i| start + url_end + title_len + i + 1)
+        })
+        .unwrap_or(start + link.text.len() + 2)
+}
+
+/// Byte offset of the first `"` in `text` not escaped with a backslash
+fn closing_quote(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Largest char boundary in `text` that is not past `index`
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    (0..=index.min(text.len()))
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+/// Extract the page title and clean it up for display
+///
+/// Whitespace is collapsed and the result is NFC-normalized. Site suffixes
+/// such as "Article | Big Site" are trimmed when the page's `og:title` or
+/// first `<h1>` confirms the shorter form. Use [`extract_title`] for the raw
+/// `<title>` text.
+pub fn extract_clean_title(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let raw = extract_title(html).filter(|t| !t.is_empty());
+
+    let og_title = Selector::parse(r#"meta[property="og:title"]"#)
+        .ok()
+        .and_then(|sel| document.select(&sel).next())
+        .and_then(|el| el.value().attr("content").map(String::from));
+    let h1 = Selector::parse("h1")
+        .ok()
+        .and_then(|sel| document.select(&sel).next())
+        .map(|el| el.text().collect::<String>());
+    let hints: Vec<String> = og_title.into_iter().chain(h1).collect();
+
+    match raw {
+        Some(raw) => Some(clean_title(&raw, &hints)),
+        None => hints
+            .iter()
+            .map(|hint| normalize_title(hint))
+            .find(|hint| !hint.is_empty()),
+    }
+}
+
+/// Clean a raw title, trimming site affixes confirmed by one of the `hints`
+pub fn clean_title(raw: &str, hints: &[String]) -> String {
+    let title = normalize_title(raw);
+
+    for hint in hints.iter().map(|h| normalize_title(h)) {
+        if hint.is_empty() || hint.len() >= title.len() {
+            continue;
+        }
+        for sep in TITLE_SEPARATORS {
+            let is_prefix = title
+                .strip_prefix(hint.as_str())
+                .map(|rest| rest.starts_with(sep))
+                .unwrap_or(false);
+            let is_suffix = title
+                .strip_suffix(hint.as_str())
+                .map(|rest| rest.ends_with(sep))
+                .unwrap_or(false);
+            if is_prefix || is_suffix {
+                return hint;
+            }
+        }
+    }
+
+    title
+}
+
+/// Collapse internal whitespace and NFC-normalize a title
+fn normalize_title(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join(" ").nfc().collect()
+}
+
 /// Extract main content from HTML using readability-style algorithm
 pub fn extract_content(html: &str, base_url: &Url) -> Result<ExtractedContent> {
+    extract_content_with_link_style(html, base_url, LinkStyle::Inline)
+}
+
+/// Extract main content, rendering links in the given style
+pub fn extract_content_with_link_style(
+    html: &str,
+    base_url: &Url,
+    link_style: LinkStyle,
+) -> Result<ExtractedContent> {
+    extract_content_with_rules(html, base_url, link_style, &[])
+}
+
+/// Extract main content, trying site-specific `selectors` before the
+/// generic content heuristics
+pub fn extract_content_with_rules(
+    html: &str,
+    base_url: &Url,
+    link_style: LinkStyle,
+    selectors: &[&str],
+) -> Result<ExtractedContent> {
     let document = Html::parse_document(html);
 
     // Try to find the main content container
-    let content_html = find_main_content(&document)?;
+    let content_html = find_main_content_with_selectors(&document, selectors)?;
 
     // Convert to markdown
-    let (text, links) = html_to_markdown_with_links(&content_html, base_url);
+    let (text, links) = html_to_markdown_with_links(&content_html, base_url, link_style);
+
+    // Prefer structured data for article metadata when the page provides it
+    let structured_data = extract_jsonld(&document);
+    let metadata = jsonld_metadata(&structured_data);
+
+    // Extract title from structured data, content, or page
+    let title = metadata
+        .headline
+        .clone()
+        .or_else(|| extract_content_title(&document));
+
+    // Detection from the text, if enabled, happens in the fetcher
+    let lang = extract_lang(&document);
+
+    Ok(ExtractedContent {
+        text,
+        title,
+        links,
+        author: metadata.author,
+        published: metadata.date_published,
+        description: extract_description(&document),
+        structured_data,
+        plain_text: None,
+        content_html: None,
+        lang,
+    })
+}
+
+/// Extract main content from HTML as plain text, skipping markdown entirely
+pub fn extract_plain_text(html: &str) -> Result<String> {
+    extract_plain_text_with_rules(html, &[])
+}
+
+/// Extract main content as plain text, trying site-specific `selectors` first
+pub fn extract_plain_text_with_rules(html: &str, selectors: &[&str]) -> Result<String> {
+    let document = Html::parse_document(html);
+    let content_html = find_main_content_with_selectors(&document, selectors)?;
+    Ok(markdown::html_to_plain(&content_html))
+}
 
-    // Extract title from content or page
-    let title = extract_content_title(&document);
+/// Extract the raw HTML of the main-content region
+pub fn extract_content_html(html: &str) -> Result<String> {
+    extract_content_html_with_rules(html, &[])
+}
 
-    Ok(ExtractedContent { text, title, links })
+/// Extract the raw main-content HTML, trying site-specific `selectors` first
+pub fn extract_content_html_with_rules(html: &str, selectors: &[&str]) -> Result<String> {
+    let document = Html::parse_document(html);
+    find_main_content_with_selectors(&document, selectors)
+}
+
+/// Remove elements matching any of `selectors` from an HTML document
+///
+/// Used to drop site noise such as cookie banners before extraction.
+/// Invalid selectors are ignored. The HTML is returned unchanged when there
+/// is nothing to remove.
+pub fn strip_elements<'a>(html: &'a str, selectors: &[String]) -> Cow<'a, str> {
+    if selectors.is_empty() {
+        return Cow::Borrowed(html);
+    }
+
+    let mut document = Html::parse_document(html);
+    let mut ids = Vec::new();
+    for selector_str in selectors {
+        match Selector::parse(selector_str) {
+            Ok(selector) => ids.extend(document.select(&selector).map(|el| el.id())),
+            Err(_) => debug!("Ignoring invalid strip selector: {}", selector_str),
+        }
+    }
+    if ids.is_empty() {
+        return Cow::Borrowed(html);
+    }
+
+    for id in ids {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    Cow::Owned(document.html())
+}
+
+/// A user-supplied content selector for pages on matching hosts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteRule {
+    /// Host to match: an exact host, or `*.example.com` for any subdomain
+    pub host: String,
+    /// CSS selector for the main content on that host
+    pub selector: String,
+}
+
+impl SiteRule {
+    /// Check whether this rule applies to `host`
+    pub fn matches(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        let pattern = self.host.to_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host == domain || host.ends_with(&format!(".{}", domain)),
+            None => host == pattern || host.strip_prefix("www.") == Some(pattern.as_str()),
+        }
+    }
+}
+
+/// Selectors from the rules that apply to `url`, in rule order
+pub fn site_selectors<'a>(rules: &'a [SiteRule], url: &Url) -> Vec<&'a str> {
+    let host = match url.host_str() {
+        Some(host) => host,
+        None => return Vec::new(),
+    };
+    rules
+        .iter()
+        .filter(|rule| rule.matches(host))
+        .map(|rule| rule.selector.as_str())
+        .collect()
+}
+
+/// Read the `<link rel="canonical">` URL, resolved against `base_url`
+///
+/// Only http(s) URLs are returned.
+pub fn extract_canonical_url(html: &str, base_url: &Url) -> Option<Url> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(r#"link[rel~="canonical"][href]"#).unwrap();
+    let href = document.select(&selector).next()?.value().attr("href")?;
+    let url = base_url.join(href.trim()).ok()?;
+    matches!(url.scheme(), "http" | "https").then_some(url)
+}
+
+/// Read the declared page language from `<html lang="...">` as a BCP 47
+/// tag (see [`normalize_lang_tag`])
+pub fn extract_lang(document: &Html) -> Option<String> {
+    let selector = Selector::parse("html[lang]").unwrap();
+    document
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr("lang"))
+        .map(normalize_lang_tag)
+        .filter(|lang| !lang.is_empty())
+}
+
+/// Put a language tag in canonical BCP 47 form
+///
+/// Underscores become hyphens and subtags get their conventional case
+/// (`en_us` becomes `en-US`, `zh-hant-tw` becomes `zh-Hant-TW`). A
+/// three-letter ISO 639-3 language with a two-letter code is shortened to
+/// it, as BCP 47 requires, so `eng` becomes `en`. Subtags that are not
+/// ASCII letters and digits are not valid BCP 47 and are dropped.
+pub fn normalize_lang_tag(tag: &str) -> String {
+    tag.trim()
+        .split(['-', '_'])
+        .filter(|subtag| !subtag.is_empty() && subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+        .enumerate()
+        .map(|(i, subtag)| {
+            let lower = subtag.to_ascii_lowercase();
+            match (i, subtag.chars().count()) {
+                (0, 3) => iso_639_1(&lower).map(String::from).unwrap_or(lower),
+                (0, _) => lower,
+                (_, 2) => subtag.to_ascii_uppercase(),
+                (_, 4) => {
+                    let mut chars = lower.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                        .unwrap_or_default()
+                }
+                _ => lower,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Two-letter ISO 639-1 code for the ISO 639-3 codes whatlang detects
+fn iso_639_1(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "afr" => "af",
+        "aka" => "ak",
+        "amh" => "am",
+        "ara" => "ar",
+        "aze" => "az",
+        "bel" => "be",
+        "ben" => "bn",
+        "bul" => "bg",
+        "cat" => "ca",
+        "ces" => "cs",
+        "cmn" => "zh",
+        "dan" => "da",
+        "deu" => "de",
+        "ell" => "el",
+        "eng" => "en",
+        "epo" => "eo",
+        "est" => "et",
+        "fin" => "fi",
+        "fra" => "fr",
+        "guj" => "gu",
+        "heb" => "he",
+        "hin" => "hi",
+        "hrv" => "hr",
+        "hun" => "hu",
+        "hye" => "hy",
+        "ind" => "id",
+        "ita" => "it",
+        "jav" => "jv",
+        "jpn" => "ja",
+        "kan" => "kn",
+        "kat" => "ka",
+        "khm" => "km",
+        "kor" => "ko",
+        "lat" => "la",
+        "lav" => "lv",
+        "lit" => "lt",
+        "mal" => "ml",
+        "mar" => "mr",
+        "mkd" => "mk",
+        "mya" => "my",
+        "nep" => "ne",
+        "nld" => "nl",
+        "nob" => "nb",
+        "ori" => "or",
+        "pan" => "pa",
+        "pes" => "fa",
+        "pol" => "pl",
+        "por" => "pt",
+        "ron" => "ro",
+        "rus" => "ru",
+        "sin" => "si",
+        "slk" => "sk",
+        "slv" => "sl",
+        "sna" => "sn",
+        "spa" => "es",
+        "srp" => "sr",
+        "swe" => "sv",
+        "tam" => "ta",
+        "tel" => "te",
+        "tgl" => "tl",
+        "tha" => "th",
+        "tuk" => "tk",
+        "tur" => "tr",
+        "ukr" => "uk",
+        "urd" => "ur",
+        "uzb" => "uz",
+        "vie" => "vi",
+        "yid" => "yi",
+        "zul" => "zu",
+        _ => return None,
+    })
+}
+
+/// Read the page summary from `<meta name="description">`, falling back to
+/// the Open Graph `og:description`
+pub fn extract_description(document: &Html) -> Option<String> {
+    ["meta[name=\"description\"]", "meta[property=\"og:description\"]"]
+        .iter()
+        .filter_map(|selector| {
+            let selector = Selector::parse(selector).unwrap();
+            document
+                .select(&selector)
+                .filter_map(|el| el.value().attr("content"))
+                .map(|content| content.split_whitespace().collect::<Vec<_>>().join(" "))
+                .find(|content| !content.is_empty())
+        })
+        .next()
+}
+
+/// Guess the language of a text, returning it as a BCP 47 tag such as `en`
+///
+/// Only confident detections are returned, so short or mixed snippets
+/// yield `None`.
+pub fn detect_language(text: &str) -> Option<String> {
+    let info = whatlang::detect(text)?;
+    if info.is_reliable() {
+        Some(normalize_lang_tag(info.lang().code()))
+    } else {
+        None
+    }
+}
+
+/// Article metadata pulled from JSON-LD structured data
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonLdMetadata {
+    /// Article headline
+    pub headline: Option<String>,
+    /// Author name(s), comma-separated when there are several
+    pub author: Option<String>,
+    /// Publication date as given by the page
+    pub date_published: Option<String>,
+}
+
+/// Collect and parse all `<script type="application/ld+json">` blocks
+///
+/// Blocks that fail to parse are skipped rather than failing the whole page.
+pub fn extract_jsonld(document: &Html) -> Vec<serde_json::Value> {
+    let selector = Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+
+    document
+        .select(&selector)
+        .filter_map(|el| {
+            let raw: String = el.text().collect();
+            match serde_json::from_str(raw.trim()) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    debug!("Skipping malformed JSON-LD block: {}", e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Pull headline, author, and publication date out of parsed JSON-LD blocks
+pub fn jsonld_metadata(values: &[serde_json::Value]) -> JsonLdMetadata {
+    let mut objects = Vec::new();
+    for value in values {
+        collect_jsonld_objects(value, &mut objects);
+    }
+
+    let mut metadata = JsonLdMetadata::default();
+    for obj in objects {
+        if metadata.headline.is_none() {
+            metadata.headline = obj
+                .get("headline")
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty());
+        }
+        if metadata.author.is_none() {
+            metadata.author = obj.get("author").and_then(jsonld_author_name);
+        }
+        if metadata.date_published.is_none() {
+            metadata.date_published = obj
+                .get("datePublished")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+        }
+    }
+    metadata
+}
+
+/// Flatten top-level arrays and `@graph` containers into individual objects
+fn collect_jsonld_objects<'a>(
+    value: &'a serde_json::Value,
+    out: &mut Vec<&'a serde_json::Map<String, serde_json::Value>>,
+) {
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_jsonld_objects(item, out);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            out.push(obj);
+            if let Some(graph) = obj.get("@graph") {
+                collect_jsonld_objects(graph, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Resolve a JSON-LD `author` value (string, Person object, or list) to a name
+fn jsonld_author_name(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(name) => Some(name.trim().to_string()).filter(|s| !s.is_empty()),
+        serde_json::Value::Object(obj) => obj.get("name").and_then(jsonld_author_name),
+        serde_json::Value::Array(items) => {
+            let names: Vec<String> = items.iter().filter_map(jsonld_author_name).collect();
+            if names.is_empty() {
+                None
+            } else {
+                Some(names.join(", "))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Find the main content container, trying site-specific selectors first
+///
+/// A site selector wins as long as it matches an element with any text,
+/// since the user asked for it explicitly.
+fn find_main_content_with_selectors(document: &Html, selectors: &[&str]) -> Result<String> {
+    for selector_str in selectors {
+        match Selector::parse(selector_str) {
+            Ok(selector) => {
+                if let Some(element) = document.select(&selector).next() {
+                    if !element.text().collect::<String>().trim().is_empty() {
+                        debug!("Found content using site rule: {}", selector_str);
+                        return Ok(element.html());
+                    }
+                }
+            }
+            Err(_) => debug!("Ignoring invalid site rule selector: {}", selector_str),
+        }
+    }
+    find_main_content(document)
 }
 
 /// Find the main content container using readability-style scoring
@@ -187,9 +802,13 @@ fn extract_content_title(document: &Html) -> Option<String> {
 }
 
 /// Convert HTML to markdown and extract links
-fn html_to_markdown_with_links(html: &str, base_url: &Url) -> (String, Vec<Link>) {
+fn html_to_markdown_with_links(
+    html: &str,
+    base_url: &Url,
+    link_style: LinkStyle,
+) -> (String, Vec<Link>) {
     let mut links = Vec::new();
-    let md = markdown::html_to_markdown_with_base(html, base_url, &mut links);
+    let md = markdown::html_to_markdown_with_base(html, base_url, &mut links, link_style);
     (md, links)
 }
 
@@ -209,6 +828,288 @@ mod tests {
         assert_eq!(extract_title(html), None);
     }
 
+    #[test]
+    fn test_clean_title_trims_site_suffix() {
+        let hints = vec!["Article Name".to_string()];
+        assert_eq!(clean_title("Article Name | Big Site", &hints), "Article Name");
+        assert_eq!(clean_title("Big Site — Article Name", &hints), "Article Name");
+
+        // Without a confirming hint the title is left intact
+        assert_eq!(clean_title("Article Name | Big Site", &[]), "Article Name | Big Site");
+    }
+
+    #[test]
+    fn test_clean_title_collapses_whitespace() {
+        assert_eq!(clean_title("  Hello \n\t  World  ", &[]), "Hello World");
+        // Decomposed "é" (e + combining acute) is composed by NFC
+        assert_eq!(clean_title("Caf\u{0065}\u{0301}", &[]), "Caf\u{00e9}");
+    }
+
+    #[test]
+    fn test_extract_clean_title() {
+        let html = r#"<html><head><title>Big News   | Example Times</title>
+            <meta property="og:title" content="Big News"></head><body></body></html>"#;
+        assert_eq!(extract_clean_title(html), Some("Big News".to_string()));
+        assert_eq!(extract_title(html), Some("Big News   | Example Times".to_string()));
+    }
+
+    #[test]
+    fn test_sanitize_content_shifts_links() {
+        let text = "Zero\u{200B}width [link](https://example.com)".to_string();
+        let position = text.find('[').unwrap();
+        let mut content = ExtractedContent {
+            links: vec![Link {
+                text: "link".to_string(),
+                url: "https://example.com".to_string(),
+                title: None,
+                position,
+            }],
+            text,
+            title: Some("\u{FEFF}Title".to_string()),
+            author: None,
+            published: None,
+            description: None,
+            structured_data: Vec::new(),
+            plain_text: None,
+            content_html: None,
+            lang: None,
+        };
+
+        sanitize_content(&mut content);
+        assert_eq!(content.text, "Zerowidth [link](https://example.com)");
+        assert!(content.text[content.links[0].position..].starts_with("[link]"));
+        assert_eq!(content.title.as_deref(), Some("Title"));
+    }
+
+    #[test]
+    fn test_inline_iframes() {
+        let html = r#"<p>Intro</p><iframe src="/embed/story" title="Story"></iframe><iframe src="https://other.com/ad"></iframe>"#;
+        let base_url = Url::parse("https://example.com/article").unwrap();
+        let mut frames = HashMap::new();
+        frames.insert(
+            "https://example.com/embed/story".to_string(),
+            "<p>Embedded article text</p>".to_string(),
+        );
+
+        let inlined = inline_iframes(html, &base_url, &frames);
+        assert!(inlined.contains("<div><p>Embedded article text</p></div>"));
+        assert!(!inlined.contains("/embed/story"));
+
+        let md = markdown::html_to_markdown_with_base(
+            &inlined,
+            &base_url,
+            &mut Vec::new(),
+            LinkStyle::Inline,
+        );
+        assert!(md.contains("Intro\n\nEmbedded article text"));
+        assert!(md.contains("[IFRAME: embedded content (https://other.com/ad)]"));
+    }
+
+    #[test]
+    fn test_strip_elements() {
+        let html = r#"<html><body><article><div class="cookie-banner">We use cookies</div><p>Article body text</p><div id="newsletter">Subscribe now</div></article></body></html>"#;
+        let selectors = vec![".cookie-banner".to_string(), "#newsletter".to_string(), "[".to_string()];
+        let stripped = strip_elements(html, &selectors);
+        let content = extract_content(&stripped, &Url::parse("https://example.com").unwrap()).unwrap();
+        assert!(content.text.contains("Article body text"));
+        assert!(!content.text.contains("We use cookies"));
+        assert!(!content.text.contains("Subscribe now"));
+
+        assert!(matches!(strip_elements(html, &[]), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_truncate_content() {
+        let text = format!("{}[link](https://example.com) {}", "é".repeat(10), "a".repeat(100));
+        let mut content = ExtractedContent {
+            links: vec![
+                Link {
+                    text: "link".to_string(),
+                    url: "https://example.com".to_string(),
+                    title: None,
+                    position: 20,
+                },
+                Link {
+                    text: "late".to_string(),
+                    url: "https://example.com/late".to_string(),
+                    title: None,
+                    position: 100,
+                },
+            ],
+            text,
+            title: None,
+            author: None,
+            published: None,
+            description: None,
+            structured_data: Vec::new(),
+            plain_text: None,
+            content_html: None,
+            lang: None,
+        };
+
+        // 50 bytes is past the first link and before the second
+        let mut long = content.clone();
+        truncate_content(&mut long, 50);
+        assert_eq!(long.text.len(), 50 + TRUNCATION_MARKER.len());
+        assert_eq!(long.links.len(), 1);
+
+        // 5 would split a two-byte "é"
+        truncate_content(&mut content, 5);
+        assert!(content.text.starts_with("éé\n\n[content truncated]"));
+        assert!(content.links.is_empty());
+    }
+
+    #[test]
+    fn test_truncate_content_mid_link() {
+        let text = format!("Intro [the docs](https://example.com/docs \"Docs\") {}", "a".repeat(100));
+        let link = |text: &str, position| Link {
+            text: text.to_string(),
+            url: "https://example.com/docs".to_string(),
+            title: None,
+            position,
+        };
+        let mut content = ExtractedContent {
+            links: vec![link("the docs", 6)],
+            text: text.clone(),
+            title: None,
+            author: None,
+            published: None,
+            description: None,
+            structured_data: Vec::new(),
+            plain_text: None,
+            content_html: None,
+            lang: None,
+        };
+        let mut hidden = content.clone();
+
+        // The limit falls inside the URL: the whole link goes
+        truncate_content(&mut content, 30);
+        assert_eq!(content.text, format!("Intro {}", TRUNCATION_MARKER));
+        assert!(content.links.is_empty());
+
+        // Just past the closing parenthesis keeps it
+        let mut kept = hidden.clone();
+        truncate_content(&mut kept, 49);
+        assert_eq!(kept.text, format!("{}{}", &text[..49], TRUNCATION_MARKER));
+        assert_eq!(kept.links.len(), 1);
+
+        // Parentheses inside a title don't end the link
+        let titled = r#"Intro [the docs](https://example.com/docs "Docs (v2) \"new\"") "#.to_string()
+            + &"a".repeat(100);
+        let end = titled.find(") aaa").unwrap() + 1;
+        for limit in [titled.find("(v2)").unwrap() + 4, end - 1] {
+            let mut cut = hidden.clone();
+            cut.text = titled.clone();
+            truncate_content(&mut cut, limit);
+            assert_eq!(cut.text, format!("Intro {}", TRUNCATION_MARKER));
+            assert!(cut.links.is_empty());
+        }
+        let mut kept = hidden.clone();
+        kept.text = titled.clone();
+        truncate_content(&mut kept, end);
+        assert_eq!(kept.links.len(), 1);
+
+        // Links rendered as bare text are not split mid-word either
+        hidden.text = format!("Intro the docs {}", "a".repeat(100));
+        truncate_content(&mut hidden, 10);
+        assert_eq!(hidden.text, format!("Intro {}", TRUNCATION_MARKER));
+        assert!(hidden.links.is_empty());
+    }
+
+    #[test]
+    fn test_lang_from_html_attribute() {
+        let html = format!(
+            r#"<html lang="de-CH"><body><article><p>{}</p></article></body></html>"#,
+            "This paragraph is written in English anyway. ".repeat(5)
+        );
+        let url = Url::parse("https://example.com/").unwrap();
+        let content = extract_content(&html, &url).unwrap();
+        assert_eq!(content.lang.as_deref(), Some("de-CH"));
+    }
+
+    #[test]
+    fn test_normalize_lang_tag() {
+        assert_eq!(normalize_lang_tag("en_us"), "en-US");
+        assert_eq!(normalize_lang_tag(" DE-ch "), "de-CH");
+        assert_eq!(normalize_lang_tag("zh-hant-tw"), "zh-Hant-TW");
+        assert_eq!(normalize_lang_tag("eng"), "en");
+        assert_eq!(normalize_lang_tag("yue"), "yue");
+        assert_eq!(normalize_lang_tag(""), "");
+        // Non-ASCII subtags are invalid and must not panic the casing
+        assert_eq!(normalize_lang_tag("en-éab"), "en");
+        assert_eq!(normalize_lang_tag("fr-ÉÉ-ca"), "fr-CA");
+    }
+
+    #[test]
+    fn test_lang_detected_from_text() {
+        let text = "The quick brown fox jumps over the lazy dog while the farmer watches from the porch. "
+            .repeat(3);
+        assert_eq!(detect_language(&text).as_deref(), Some("en"));
+        assert_eq!(detect_language("ok"), None);
+    }
+
+    #[test]
+    fn test_description_prefers_meta_over_open_graph() {
+        let html = r#"<html><head>
+            <meta property="og:description" content="Shared summary">
+            <meta name="description" content="  Page
+                summary ">
+            </head><body></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(extract_description(&document).as_deref(), Some("Page summary"));
+
+        let html = r#"<html><head><meta name="description" content="">
+            <meta property="og:description" content="Shared summary"></head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(extract_description(&document).as_deref(), Some("Shared summary"));
+    }
+
+    #[test]
+    fn test_site_rule_matching() {
+        let exact = SiteRule {
+            host: "example.com".to_string(),
+            selector: ".story".to_string(),
+        };
+        assert!(exact.matches("example.com"));
+        assert!(exact.matches("www.example.com"));
+        assert!(!exact.matches("news.example.com"));
+        assert!(!exact.matches("notexample.com"));
+
+        let wildcard = SiteRule {
+            host: "*.example.com".to_string(),
+            selector: ".story".to_string(),
+        };
+        assert!(wildcard.matches("news.example.com"));
+        assert!(wildcard.matches("example.com"));
+        assert!(!wildcard.matches("example.org"));
+    }
+
+    #[test]
+    fn test_site_rule_selector_tried_first() {
+        let html = format!(
+            r#"<html><body><article><p>{}</p></article><div class="story-body"><p>Paywalled story</p></div></body></html>"#,
+            "Teaser text that is long enough to count as content. ".repeat(3)
+        );
+        let rules = vec![SiteRule {
+            host: "news.example.com".to_string(),
+            selector: ".story-body".to_string(),
+        }];
+
+        let url = Url::parse("https://news.example.com/a").unwrap();
+        let selectors = site_selectors(&rules, &url);
+        assert_eq!(selectors, vec![".story-body"]);
+        let content = extract_content_with_rules(&html, &url, LinkStyle::Inline, &selectors).unwrap();
+        assert!(content.text.contains("Paywalled story"));
+        assert!(!content.text.contains("Teaser"));
+
+        let other = Url::parse("https://other.example.org/a").unwrap();
+        let selectors = site_selectors(&rules, &other);
+        assert!(selectors.is_empty());
+        let content = extract_content_with_rules(&html, &other, LinkStyle::Inline, &selectors).unwrap();
+        assert!(content.text.contains("Teaser"));
+        assert!(!content.text.contains("Paywalled story"));
+    }
+
     #[test]
     fn test_meaningful_content() {
         let short = "<p>Hi</p>";
@@ -216,4 +1117,33 @@ mod tests {
         assert!(!has_meaningful_content(short));
         assert!(has_meaningful_content(&long));
     }
+
+    #[test]
+    fn test_extract_jsonld_single_block() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">
+            {"@type": "NewsArticle", "headline": "Big News", "author": {"@type": "Person", "name": "Jane Doe"}, "datePublished": "2024-01-02"}
+            </script>
+            </head><body></body></html>"#;
+        let document = Html::parse_document(html);
+        let values = extract_jsonld(&document);
+        assert_eq!(values.len(), 1);
+
+        let metadata = jsonld_metadata(&values);
+        assert_eq!(metadata.headline.as_deref(), Some("Big News"));
+        assert_eq!(metadata.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.date_published.as_deref(), Some("2024-01-02"));
+    }
+
+    #[test]
+    fn test_extract_jsonld_skips_malformed() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">{"@type": "Article", "headline": </script>
+            <script type="application/ld+json">{"@type": "WebSite", "name": "Site"}</script>
+            </head><body></body></html>"#;
+        let document = Html::parse_document(html);
+        let values = extract_jsonld(&document);
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0]["name"], "Site");
+    }
 }
@@ -1,22 +1,277 @@
 //! HTTP and headless browser fetching
 
-use crate::accessibility::{ax_tree_to_markdown, fetch_ax_tree};
-use crate::chrome::{BrowserHandle, ChromeConfig, ChromeManager, ExtractionMethod};
-use crate::{extract, ExtractedContent, FetchConfig, FoxError, Page, Result};
+use crate::accessibility::{
+    ax_tree_to_markdown_with_options, ax_tree_to_plain_with_options, fetch_ax_tree, AXTree,
+    AxConversionOptions,
+};
+use crate::chrome::{
+    block_patterns, BrowserHandle, ChromeConfig, ChromeManager, DownloadProgress, ExtractionMethod,
+    ResourceType,
+};
+use crate::{
+    blocklist, charset, download, extract, links, ExtractedContent, FetchConfig, FoxError, Link, LinkStyle,
+    Page, Result,
+};
+use crate::ratelimit::HostRateLimiter;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use reqwest::Client;
+use scraper::Html;
+use chromiumoxide::cdp::browser_protocol::fetch as cdp_fetch;
+use chromiumoxide::cdp::browser_protocol::emulation;
+use chromiumoxide::cdp::browser_protocol::network;
+use chromiumoxide::cdp::browser_protocol::target::{
+    CreateBrowserContextParams, CreateTargetParams, DisposeBrowserContextParams, GetTargetInfoParams,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 use url::Url;
 
+/// How long to wait with no requests in flight before the network counts
+/// as idle
+const NETWORK_QUIET_PERIOD: Duration = Duration::from_millis(500);
+
+/// How often `document.readyState` is polled while waiting for the DOM
+const READY_STATE_POLL: Duration = Duration::from_millis(50);
+
+/// Bytes of a page read by [`Fetcher::fetch_title`] before giving up
+const TITLE_SCAN_LIMIT: usize = 64 * 1024;
+
+tokio::task_local! {
+    /// Redirects followed by the HTTP request in flight on this task
+    ///
+    /// The redirect policy runs inside the request future, so it can record
+    /// into whichever fetch is polling it.
+    static REDIRECT_CHAIN: std::cell::RefCell<Vec<Url>>;
+
+    /// Where [`Fetcher::fetch_with_progress`] reports the stages of the
+    /// fetch running on this task
+    static FETCH_PROGRESS: ProgressReporter;
+}
+
+/// Callback receiving [`FetchProgress`] updates
+type ProgressReporter = Arc<dyn Fn(FetchProgress) + Send + Sync>;
+
+/// A slow stage of a page load, reported by [`Fetcher::fetch_with_progress`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchProgress {
+    /// Converting an accessibility tree of `nodes` nodes to markdown
+    Converting { nodes: usize },
+}
+
+/// Report `progress` to the caller of the fetch running on this task, if it
+/// asked for updates
+fn report_progress(progress: FetchProgress) {
+    let _ = FETCH_PROGRESS.try_with(|report| report(progress));
+}
+
+/// HTTP credentials attached to requests
+#[derive(Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// HTTP Basic authentication
+    Basic { user: String, pass: String },
+    /// A bearer token, as used by most APIs
+    Bearer(String),
+}
+
+impl Auth {
+    /// The `Authorization` header value for these credentials
+    pub fn header_value(&self) -> String {
+        use base64::Engine;
+        match self {
+            Auth::Basic { user, pass } => format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, pass))
+            ),
+            Auth::Bearer(token) => format!("Bearer {}", token),
+        }
+    }
+}
+
+// Keep secrets out of logs and `{:?}` output
+impl std::fmt::Debug for Auth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Auth::Basic { user, .. } => f
+                .debug_struct("Basic")
+                .field("user", user)
+                .field("pass", &"<redacted>")
+                .finish(),
+            Auth::Bearer(_) => f.debug_tuple("Bearer").field(&"<redacted>").finish(),
+        }
+    }
+}
+
+/// When a browser-rendered page is considered ready to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitStrategy {
+    /// Wait for the `load` event
+    #[default]
+    Load,
+    /// Stop as soon as the DOM is parsed, without waiting for subresources
+    DomContentLoaded,
+    /// Wait for `load`, then until no requests have been in flight for a
+    /// short quiet period; pages that keep requests open hit the timeout
+    NetworkIdle,
+}
+
+impl WaitStrategy {
+    /// Whether to wait for the page's `load` event
+    pub fn waits_for_load(self) -> bool {
+        !matches!(self, WaitStrategy::DomContentLoaded)
+    }
+
+    /// Whether to wait for network activity to die down after loading
+    pub fn waits_for_network_idle(self) -> bool {
+        matches!(self, WaitStrategy::NetworkIdle)
+    }
+}
+
+impl std::str::FromStr for WaitStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "load" => Ok(WaitStrategy::Load),
+            "domcontentloaded" | "dom" => Ok(WaitStrategy::DomContentLoaded),
+            "networkidle" | "idle" => Ok(WaitStrategy::NetworkIdle),
+            _ => Err(format!(
+                "Unknown wait strategy: {}. Use 'load', 'domcontentloaded', or 'networkidle'",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for WaitStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WaitStrategy::Load => write!(f, "load"),
+            WaitStrategy::DomContentLoaded => write!(f, "domcontentloaded"),
+            WaitStrategy::NetworkIdle => write!(f, "networkidle"),
+        }
+    }
+}
+
+/// How accessibility extraction and readability are combined
+///
+/// Site rules and strip selectors always use readability, whatever the
+/// strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtractionStrategy {
+    /// Use the accessibility tree, falling back to readability when the
+    /// tree can't be read or yields less than `ax_min_content_chars`
+    #[default]
+    AxThenReadability,
+    /// Use only the configured extraction method, with no fallback
+    SingleMethod,
+    /// Run both accessibility and readability extraction and keep
+    /// whichever produced more text
+    PreferLonger,
+}
+
+impl std::str::FromStr for ExtractionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ax-then-readability" | "fallback" => Ok(ExtractionStrategy::AxThenReadability),
+            "single" | "single-method" => Ok(ExtractionStrategy::SingleMethod),
+            "prefer-longer" | "longer" => Ok(ExtractionStrategy::PreferLonger),
+            _ => Err(format!(
+                "Unknown extraction strategy: {}. Use 'ax-then-readability', 'single', or 'prefer-longer'",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ExtractionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractionStrategy::AxThenReadability => write!(f, "ax-then-readability"),
+            ExtractionStrategy::SingleMethod => write!(f, "single"),
+            ExtractionStrategy::PreferLonger => write!(f, "prefer-longer"),
+        }
+    }
+}
+
+/// Common user agent strings, for `FetchConfig::user_agent`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAgentPreset {
+    /// Desktop Chrome on Windows
+    Chrome,
+    /// Desktop Firefox on Windows
+    Firefox,
+    /// Google's search crawler
+    Googlebot,
+    /// Fox's own user agent, the default
+    Fox,
+}
+
+impl UserAgentPreset {
+    /// The user agent string this preset sends
+    pub fn user_agent(self) -> String {
+        match self {
+            UserAgentPreset::Chrome => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36".to_string(),
+            UserAgentPreset::Firefox => "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:133.0) Gecko/20100101 Firefox/133.0".to_string(),
+            UserAgentPreset::Googlebot => "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)".to_string(),
+            UserAgentPreset::Fox => crate::default_user_agent(),
+        }
+    }
+}
+
+impl std::str::FromStr for UserAgentPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "chrome" => Ok(UserAgentPreset::Chrome),
+            "firefox" => Ok(UserAgentPreset::Firefox),
+            "googlebot" => Ok(UserAgentPreset::Googlebot),
+            "fox" => Ok(UserAgentPreset::Fox),
+            _ => Err(format!(
+                "Unknown user agent preset: {}. Use 'chrome', 'firefox', 'googlebot', or 'fox'",
+                s
+            )),
+        }
+    }
+}
+
+/// Whether JavaScript rendering is available to a [`Fetcher`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserStatus {
+    /// A headless browser is running
+    Running,
+    /// JavaScript rendering was turned off in the configuration
+    Disabled,
+    /// JavaScript was requested but the browser could not be started, so
+    /// fetches fall back to HTTP; holds the reason
+    Unavailable(String),
+}
+
+/// Result of checking a URL with [`Fetcher::check_url`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlStatus {
+    /// HTTP status code of the final response, after redirects
+    pub status: u16,
+    /// The `Content-Type` header, if any
+    pub content_type: Option<String>,
+}
+
 /// Fetcher for web pages
 pub struct Fetcher {
     client: Client,
     chrome_manager: ChromeManager,
     browser: Option<Arc<Mutex<BrowserHandle>>>,
+    browser_status: BrowserStatus,
     config: FetchConfig,
     extraction_method: ExtractionMethod,
+    block_resources: Vec<ResourceType>,
+    rate_limiter: HostRateLimiter,
 }
 
 impl Fetcher {
@@ -35,42 +290,122 @@ impl Fetcher {
         config: FetchConfig,
         chrome_config: ChromeConfig,
     ) -> Result<Self> {
+        Self::with_chrome_manager(config, ChromeManager::with_config(chrome_config)).await
+    }
+
+    /// Create a new fetcher, reporting any first-run Chrome download to
+    /// `callback`
+    pub async fn with_config_chrome_and_progress<F>(
+        config: FetchConfig,
+        chrome_config: ChromeConfig,
+        callback: F,
+    ) -> Result<Self>
+    where
+        F: Fn(DownloadProgress) + Send + Sync + 'static,
+    {
+        let mut chrome_manager = ChromeManager::with_config(chrome_config);
+        chrome_manager.set_progress_callback(callback);
+        Self::with_chrome_manager(config, chrome_manager).await
+    }
+
+    /// Create a new fetcher around an existing Chrome manager
+    ///
+    /// Lets callers register a download progress callback before a
+    /// first-run Chrome download starts.
+    pub async fn with_chrome_manager(
+        config: FetchConfig,
+        mut chrome_manager: ChromeManager,
+    ) -> Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(auth) = &config.auth {
+            let mut value = reqwest::header::HeaderValue::from_str(&auth.header_value())
+                .map_err(|e| FoxError::Other(format!("Invalid credentials: {}", e)))?;
+            value.set_sensitive(true);
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+
         let client = Client::builder()
             .user_agent(&config.user_agent)
+            .default_headers(headers)
             .timeout(Duration::from_secs(config.timeout_secs))
-            .cookie_store(true)
+            .cookie_store(config.enable_cookies)
+            .redirect(redirect_policy(config.blocklist.clone(), config.max_redirects))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
             .build()?;
 
-        let extraction_method = chrome_config.extraction_method;
-        let mut chrome_manager = ChromeManager::with_config(chrome_config);
+        let extraction_method = chrome_manager.config().extraction_method;
+        let block_resources = chrome_manager.config().block_resources.clone();
 
-        let browser = if config.javascript {
+        let (browser, browser_status) = if config.javascript {
             match chrome_manager.get_browser().await {
-                Ok(b) => Some(b),
+                Ok(b) => (Some(b), BrowserStatus::Running),
                 Err(e) => {
                     warn!(
                         "Failed to initialize browser, falling back to HTTP-only: {}",
                         e
                     );
-                    None
+                    (None, BrowserStatus::Unavailable(e.to_string()))
                 }
             }
         } else {
-            None
+            (None, BrowserStatus::Disabled)
         };
 
+        let rate_limiter = HostRateLimiter::new(config.requests_per_second_per_host);
+
         Ok(Self {
             client,
             chrome_manager,
             browser,
+            browser_status,
             config,
             extraction_method,
+            block_resources,
+            rate_limiter,
         })
     }
 
     /// Fetch a page by URL
+    ///
+    /// With `follow_canonical` enabled, a page declaring a different
+    /// canonical URL is replaced by one fetch of that URL. The canonical
+    /// page's own canonical link is not followed, so redirects can't loop.
     pub async fn fetch(&self, url: &str) -> Result<Page> {
         let url = Url::parse(url)?;
+        let page = self.fetch_url(&url).await?;
+
+        if self.config.follow_canonical {
+            if let Some(canonical) = page.canonical_url() {
+                if !same_document(&canonical, &url) && !same_document(&canonical, &page.url) {
+                    info!("Following canonical URL: {}", canonical);
+                    match self.fetch_url(&canonical).await {
+                        Ok(canonical_page) => return Ok(canonical_page),
+                        Err(e) => warn!("Failed to fetch canonical URL {}: {}", canonical, e),
+                    }
+                }
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Fetch a URL like [`Fetcher::fetch`], reporting slow stages to
+    /// `callback` as they start
+    ///
+    /// Lets an interactive caller show more than "loading" while a large
+    /// page is converted.
+    pub async fn fetch_with_progress<F>(&self, url: &str, callback: F) -> Result<Page>
+    where
+        F: Fn(FetchProgress) + Send + Sync + 'static,
+    {
+        FETCH_PROGRESS.scope(Arc::new(callback), self.fetch(url)).await
+    }
+
+    /// Fetch a single URL without following canonical links
+    async fn fetch_url(&self, url: &Url) -> Result<Page> {
+        let url = url.clone();
+        self.throttle(&url).await;
         info!("Fetching: {}", url);
 
         // Determine if we should use accessibility tree extraction
@@ -84,9 +419,9 @@ impl Fetcher {
         } else if self.config.javascript && self.browser.is_some() {
             // Use browser but with readability extraction
             let html = self.fetch_with_browser(&url).await?;
-            let title = extract::extract_title(&html);
+            let title = self.page_title(&html);
             let content = if self.config.extract_content {
-                Some(extract::extract_content(&html, &url)?)
+                Some(self.post_process(self.extract_readability(&html, &url)?))
             } else {
                 None
             };
@@ -95,13 +430,15 @@ impl Fetcher {
                 title,
                 html,
                 content,
+                redirect_chain: Vec::new(),
+                markdown_cache: Default::default(),
             })
         } else {
             // HTTP-only mode with readability extraction
-            let html = self.fetch_with_http(&url).await?;
-            let title = extract::extract_title(&html);
+            let (html, redirect_chain) = self.fetch_http_with_redirects(&url).await?;
+            let title = self.page_title(&html);
             let content = if self.config.extract_content {
-                Some(extract::extract_content(&html, &url)?)
+                Some(self.post_process(self.extract_readability(&html, &url)?))
             } else {
                 None
             };
@@ -110,16 +447,152 @@ impl Fetcher {
                 title,
                 html,
                 content,
+                redirect_chain,
+                markdown_cache: Default::default(),
             })
         }
     }
 
+    /// Fetch many URLs, yielding each result as soon as it completes
+    ///
+    /// At most `concurrency` fetches run at once. Results arrive in completion
+    /// order, paired with the URL they came from; a failing URL yields its
+    /// error without affecting the others.
+    pub fn fetch_stream<'a>(
+        &'a self,
+        urls: impl IntoIterator<Item = String> + 'a,
+        concurrency: usize,
+    ) -> impl Stream<Item = (String, Result<Page>)> + 'a {
+        stream::iter(urls)
+            .map(move |url| async move {
+                let result = self.fetch(&url).await;
+                (url, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
+
+    /// Fetch many URLs and collect the results in input order
+    ///
+    /// At most `concurrency` fetches run at once, as with
+    /// [`Fetcher::fetch_stream`]. Errors are per URL: a failing URL gets its
+    /// error in its own slot and every other URL is still fetched. As with
+    /// [`Fetcher::fetch`], a page served with an error status such as 404
+    /// still comes back as a page.
+    pub async fn fetch_many(&self, urls: &[&str], concurrency: usize) -> Vec<(String, Result<Page>)> {
+        stream::iter(urls)
+            .map(|url| async move { (url.to_string(), self.fetch(url).await) })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
     /// Fetch using HTTP only (no JavaScript)
     pub async fn fetch_with_http(&self, url: &Url) -> Result<String> {
+        Ok(self.fetch_http_with_redirects(url).await?.0)
+    }
+
+    /// Fetch over HTTP, also returning the redirects that were followed
+    async fn fetch_http_with_redirects(&self, url: &Url) -> Result<(String, Vec<Url>)> {
         debug!("Fetching with HTTP: {}", url);
+        REDIRECT_CHAIN
+            .scope(std::cell::RefCell::new(Vec::new()), async {
+                let response = self.client.get(url.as_str()).send().await.map_err(|e| {
+                    if e.is_redirect() {
+                        FoxError::TooManyRedirects {
+                            url: url.to_string(),
+                            limit: self.config.max_redirects,
+                        }
+                    } else {
+                        e.into()
+                    }
+                })?;
+                let content_type = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(String::from);
+                let bytes = response.bytes().await?;
+                let chain = REDIRECT_CHAIN.with(|chain| chain.take());
+                Ok((charset::decode_html(&bytes, content_type.as_deref()), chain))
+            })
+            .await
+    }
+
+    /// Fetch just a page's title over HTTP
+    ///
+    /// Reads the body only until `</title>` (or `</head>`) arrives, skipping
+    /// extraction and rendering entirely. Returns `None` when the page has no
+    /// title in its first 64 KiB.
+    pub async fn fetch_title(&self, url: &str) -> Result<Option<String>> {
+        let url = Url::parse(url)?;
+        self.throttle(&url).await;
+        debug!("Fetching title: {}", url);
         let response = self.client.get(url.as_str()).send().await?;
-        let html = response.text().await?;
-        Ok(html)
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+
+        let mut head = Vec::new();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.try_next().await? {
+            // Re-scan a little of the previous chunk in case a tag was split
+            let scan_from = head.len().saturating_sub(8);
+            head.extend_from_slice(&chunk);
+            let scanned = &head[scan_from..];
+            if contains_ignore_case(scanned, b"</title>")
+                || contains_ignore_case(scanned, b"</head>")
+                || head.len() >= TITLE_SCAN_LIMIT
+            {
+                break;
+            }
+        }
+
+        let html = charset::decode_html(&head, content_type.as_deref());
+        Ok(self.page_title(&html).filter(|title| !title.is_empty()))
+    }
+
+    /// Check that a URL resolves without fetching its body
+    ///
+    /// Sends a HEAD request. Servers that refuse HEAD (405 or 501) are
+    /// asked again with a GET for just the first byte.
+    pub async fn check_url(&self, url: &str) -> Result<UrlStatus> {
+        let url = Url::parse(url)?;
+        self.throttle(&url).await;
+        debug!("Checking URL: {}", url);
+        let mut response = self.client.head(url.as_str()).send().await?;
+        if matches!(response.status().as_u16(), 405 | 501) {
+            debug!("HEAD not allowed for {}, retrying with a ranged GET", url);
+            response = self
+                .client
+                .get(url.as_str())
+                .header(reqwest::header::RANGE, "bytes=0-0")
+                .send()
+                .await?;
+        }
+        Ok(UrlStatus {
+            status: response.status().as_u16(),
+            content_type: response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from),
+        })
+    }
+
+    /// Download a resource to `dir` over HTTP, returning the saved path
+    pub async fn download(&self, url: &str, dir: &Path) -> Result<PathBuf> {
+        let url = Url::parse(url)?;
+        self.throttle(&url).await;
+        download::download_to_dir(&self.client, &url, dir).await
+    }
+
+    /// Wait for the per-host rate limit before requesting `url`
+    async fn throttle(&self, url: &Url) {
+        if let Some(host) = url.host_str() {
+            self.rate_limiter.acquire(host).await;
+        }
     }
 
     /// Fetch using headless browser (with JavaScript)
@@ -128,28 +601,21 @@ impl Fetcher {
 
         let browser_handle = self.browser.as_ref().unwrap();
         let handle = browser_handle.lock().await;
+        let page = self.open_page(&handle, url).await?;
 
-        let page = handle
-            .browser
-            .new_page(url.as_str())
-            .await
-            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
-
-        // Wait for the page to load
-        page.wait_for_navigation()
-            .await
-            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        let result = navigation_timeout(self.timeout(), async {
+            // Wait for the page to load
+            self.wait_until_settled(&page).await?;
 
-        // Get the rendered HTML
-        let html = page
-            .content()
-            .await
-            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+            // Get the rendered HTML
+            self.page_html(&page, url).await
+        })
+        .await;
 
         // Close the page
-        let _ = page.close().await;
+        self.close_page(&handle, page).await;
 
-        Ok(html)
+        result
     }
 
     /// Fetch using headless browser with accessibility tree extraction
@@ -158,71 +624,298 @@ impl Fetcher {
 
         let browser_handle = self.browser.as_ref().unwrap();
         let handle = browser_handle.lock().await;
+        let page = self.open_page(&handle, url).await?;
 
-        let page = handle
-            .browser
-            .new_page(url.as_str())
-            .await
-            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        let result = navigation_timeout(self.timeout(), self.load_ax_page(&page, url)).await;
+
+        // Close the page
+        self.close_page(&handle, page).await;
+
+        result
+    }
 
+    /// Wait for a browser page to load and extract it via the AX tree
+    async fn load_ax_page(&self, page: &chromiumoxide::Page, url: &Url) -> Result<Page> {
         // Wait for the page to load
-        page.wait_for_navigation()
-            .await
-            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        self.wait_until_settled(page).await?;
 
         // Small delay for dynamic content to settle
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // Get the rendered HTML (for the Page struct)
-        let html = page
-            .content()
-            .await
-            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        let html = self.page_html(page, url).await?;
 
         // Extract title from HTML
-        let title = extract::extract_title(&html);
+        let title = self.page_title(&html);
 
         // Fetch and convert the accessibility tree
         let content = if self.config.extract_content {
             debug!("Fetching accessibility tree...");
-            match fetch_ax_tree(&page).await {
-                Ok(tree) => {
-                    let (markdown, links) = ax_tree_to_markdown(&tree);
-                    debug!("AX tree converted: {} chars, {} links", markdown.len(), links.len());
-                    Some(ExtractedContent {
-                        text: markdown,
-                        title: title.clone(),
-                        links,
-                    })
-                }
-                Err(e) => {
-                    warn!("Failed to fetch AX tree, falling back to readability: {}", e);
-                    // Fallback to readability extraction
-                    Some(extract::extract_content(&html, url)?)
-                }
+            match fetch_ax_tree(page).await {
+                Ok(tree) => Some(self.post_process(
+                    self.content_from_ax_tree(tree, &html, url, title.clone()).await?,
+                )),
+                Err(e) => Some(self.post_process(self.content_without_ax_tree(e, &html, url)?)),
             }
         } else {
             None
         };
 
-        // Close the page
-        let _ = page.close().await;
-
         Ok(Page {
             url: url.clone(),
             title,
             html,
             content,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        })
+    }
+
+    /// Rendered HTML of a browser page
+    ///
+    /// With `follow_iframes`, the bodies of same-origin iframes are inlined
+    /// in place of the iframe elements. Cross-origin frames can't be read
+    /// from the page and are left alone.
+    async fn page_html(&self, page: &chromiumoxide::Page, url: &Url) -> Result<String> {
+        let html = page
+            .content()
+            .await
+            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        if !self.config.follow_iframes {
+            return Ok(html);
+        }
+
+        let frames: HashMap<String, String> = match page.evaluate(IFRAME_BODIES_SCRIPT).await {
+            Ok(result) => result.into_value().unwrap_or_default(),
+            Err(e) => {
+                warn!("Failed to read iframe content: {}", e);
+                return Ok(html);
+            }
+        };
+        debug!("Inlining {} same-origin iframes", frames.len());
+        Ok(extract::inline_iframes(&html, url, &frames))
+    }
+
+    /// Fill in and submit a form, returning the page it navigates to
+    ///
+    /// Loads `url` in the browser, sets the value of each input whose `name`
+    /// matches a field, then clicks `submit_selector` (or submits the form
+    /// owning the first field) and extracts the resulting page. Requires
+    /// JavaScript rendering.
+    pub async fn submit_form(
+        &self,
+        url: &str,
+        fields: &[(String, String)],
+        submit_selector: Option<&str>,
+    ) -> Result<Page> {
+        let url = Url::parse(url)?;
+        let browser_handle = self.browser.as_ref().ok_or_else(|| {
+            FoxError::BrowserError("Form submission requires a browser".to_string())
+        })?;
+        debug!("Submitting form on: {}", url);
+
+        let handle = browser_handle.lock().await;
+        let page = self.open_page(&handle, &url).await?;
+
+        let result = navigation_timeout(
+            self.timeout(),
+            self.submit_on_page(&page, url, fields, submit_selector),
+        )
+        .await;
+
+        self.close_page(&handle, page).await;
+
+        result
+    }
+
+    /// Fill and submit a form on an already opened page
+    async fn submit_on_page(
+        &self,
+        page: &chromiumoxide::Page,
+        url: Url,
+        fields: &[(String, String)],
+        submit_selector: Option<&str>,
+    ) -> Result<Page> {
+        self.wait_until_settled(page).await?;
+
+        for (name, value) in fields {
+            page.evaluate(fill_field_script(name, value))
+                .await
+                .map_err(|e| FoxError::BrowserError(format!("Failed to fill '{}': {}", name, e)))?;
+        }
+
+        match submit_selector {
+            Some(selector) => {
+                page.find_element(selector)
+                    .await
+                    .map_err(|e| FoxError::BrowserError(e.to_string()))?
+                    .click()
+                    .await
+                    .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+            }
+            None => {
+                let first = fields.first().map(|(name, _)| name.as_str()).unwrap_or("");
+                page.evaluate(submit_form_script(first))
+                    .await
+                    .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+            }
+        }
+
+        page.wait_for_navigation()
+            .await
+            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+
+        let html = page
+            .content()
+            .await
+            .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        let result_url = page
+            .url()
+            .await
+            .ok()
+            .flatten()
+            .and_then(|u| Url::parse(&u).ok())
+            .unwrap_or(url);
+        let title = self.page_title(&html);
+
+        let content = if !self.config.extract_content {
+            None
+        } else if self.extraction_method == ExtractionMethod::Accessibility {
+            match fetch_ax_tree(page).await {
+                Ok(tree) => Some(self.post_process(
+                    self.content_from_ax_tree(tree, &html, &result_url, title.clone()).await?,
+                )),
+                Err(e) => {
+                    Some(self.post_process(self.content_without_ax_tree(e, &html, &result_url)?))
+                }
+            }
+        } else {
+            Some(self.post_process(self.extract_readability(&html, &result_url)?))
+        };
+
+        Ok(Page {
+            url: result_url,
+            title,
+            html,
+            content,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        })
+    }
+
+    /// Convert an AX tree to extracted content
+    ///
+    /// Pages that block the accessibility API or render into canvas/shadow DOM
+    /// can produce a near-empty tree. When the AX markdown is shorter than
+    /// `ax_min_content_chars` and readability finds more text in the HTML,
+    /// the readability result is used instead; with
+    /// [`ExtractionStrategy::PreferLonger`] it is used whenever it finds more
+    /// text, and [`ExtractionStrategy::SingleMethod`] never falls back.
+    /// Readability is also used outright when a site rule matches the URL.
+    async fn content_from_ax_tree(
+        &self,
+        tree: AXTree,
+        html: &str,
+        url: &Url,
+        title: Option<String>,
+    ) -> Result<ExtractedContent> {
+        // Site rules are CSS selectors, which only readability can apply
+        if !extract::site_selectors(&self.config.site_rules, url).is_empty() {
+            debug!("Site rule matches {}, using readability extraction", url);
+            return self.extract_readability(html, url);
+        }
+        // The same goes for selectors of elements to strip
+        if !self.config.strip_selectors.is_empty() {
+            debug!("Strip selectors configured, using readability extraction");
+            return self.extract_readability(html, url);
+        }
+
+        let options = AxConversionOptions {
+            link_style: self.config.link_style,
+            ..self.config.ax_options.clone()
+        };
+        let plain_options = self.config.plain_text.then(|| self.config.ax_options.clone());
+        let AxConversion {
+            markdown,
+            links,
+            plain_text,
+        } = convert_ax_tree(tree, options, plain_options).await?;
+        debug!("AX tree converted: {} chars, {} links", markdown.len(), links.len());
+
+        let ax_len = markdown.trim().len();
+        let try_readability = match self.config.extraction_strategy {
+            ExtractionStrategy::AxThenReadability => ax_len < self.config.ax_min_content_chars,
+            ExtractionStrategy::SingleMethod => false,
+            ExtractionStrategy::PreferLonger => true,
+        };
+        if try_readability {
+            if let Ok(fallback) = self.extract_readability(html, url) {
+                if fallback.text.trim().len() > ax_len {
+                    warn!(
+                        "AX tree yielded only {} chars, using readability's longer result",
+                        ax_len
+                    );
+                    return Ok(fallback);
+                }
+            }
+        }
+
+        let document = Html::parse_document(html);
+        let structured_data = extract::extract_jsonld(&document);
+        let lang = extract::extract_lang(&document);
+        let metadata = extract::jsonld_metadata(&structured_data);
+        let content_html = if self.config.content_html {
+            let selectors = extract::site_selectors(&self.config.site_rules, url);
+            extract::extract_content_html_with_rules(html, &selectors).ok()
+        } else {
+            None
+        };
+        Ok(ExtractedContent {
+            text: markdown,
+            title: metadata.headline.or(title),
+            links,
+            author: metadata.author,
+            published: metadata.date_published,
+            description: extract::extract_description(&document),
+            structured_data,
+            plain_text,
+            content_html,
+            lang,
         })
     }
 
+    /// Content for a page whose AX tree couldn't be read: readability, unless
+    /// the strategy rules out falling back
+    fn content_without_ax_tree(&self, error: FoxError, html: &str, url: &Url) -> Result<ExtractedContent> {
+        if self.config.extraction_strategy == ExtractionStrategy::SingleMethod {
+            return Err(error);
+        }
+        warn!("Failed to fetch AX tree, falling back to readability: {}", error);
+        self.extract_readability(html, url)
+    }
+
+    /// Readability extraction, plus a plain-text rendering if configured
+    fn extract_readability(&self, html: &str, url: &Url) -> Result<ExtractedContent> {
+        let html = &extract::strip_elements(html, &self.config.strip_selectors);
+        let selectors = extract::site_selectors(&self.config.site_rules, url);
+        let mut content =
+            extract::extract_content_with_rules(html, url, self.config.link_style, &selectors)?;
+        if self.config.plain_text {
+            content.plain_text = Some(extract::extract_plain_text_with_rules(html, &selectors)?);
+        }
+        if self.config.content_html {
+            content.content_html = Some(extract::extract_content_html_with_rules(html, &selectors)?);
+        }
+        Ok(content)
+    }
+
     /// Fetch a page without JavaScript rendering
     pub async fn fetch_no_js(&self, url: &str) -> Result<Page> {
         let url = Url::parse(url)?;
-        let html = self.fetch_with_http(&url).await?;
-        let title = extract::extract_title(&html);
+        let (html, redirect_chain) = self.fetch_http_with_redirects(&url).await?;
+        let title = self.page_title(&html);
         let content = if self.config.extract_content {
-            Some(extract::extract_content(&html, &url)?)
+            Some(self.post_process(self.extract_readability(&html, &url)?))
         } else {
             None
         };
@@ -232,6 +925,8 @@ impl Fetcher {
             title,
             html,
             content,
+            redirect_chain,
+            markdown_cache: Default::default(),
         })
     }
 
@@ -242,9 +937,9 @@ impl Fetcher {
             .transpose()?
             .unwrap_or_else(|| Url::parse("about:blank").unwrap());
 
-        let title = extract::extract_title(html);
+        let title = self.page_title(html);
         let content = if self.config.extract_content {
-            Some(extract::extract_content(html, &url)?)
+            Some(self.post_process(self.extract_readability(html, &url)?))
         } else {
             None
         };
@@ -254,35 +949,484 @@ impl Fetcher {
             title,
             html: html.to_string(),
             content,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
         })
     }
 }
 
 impl Fetcher {
-    /// Get a reference to the Chrome manager
-    pub fn chrome_manager(&self) -> &ChromeManager {
-        &self.chrome_manager
+    /// Configured limit for a single page load
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs)
     }
 
-    /// Check if JavaScript rendering is available
-    pub fn has_javascript(&self) -> bool {
-        self.browser.is_some()
-    }
+    /// Open a new browser tab on `url`, bounded by the configured timeout
+    ///
+    /// When resource blocking is configured the tab starts blank so
+    /// interception is in place before navigation begins.
+    async fn open_page(&self, handle: &BrowserHandle, url: &Url) -> Result<chromiumoxide::Page> {
+        let filter = RequestFilter {
+            resources: self.block_resources.clone(),
+            blocklist: self.config.blocklist.clone(),
+        };
+        let patterns = filter.patterns();
+        // The browser keeps its own user agent unless one was configured
+        let user_agent =
+            Some(&self.config.user_agent).filter(|ua| **ua != crate::default_user_agent());
+        let media = media_emulation(&self.config);
+        navigation_timeout(self.timeout(), async {
+            if patterns.is_empty()
+                && self.config.auth.is_none()
+                && user_agent.is_none()
+                && media.is_none()
+            {
+                return self.new_page(handle, url.as_str()).await;
+            }
 
-    /// Get the current extraction method
-    pub fn extraction_method(&self) -> ExtractionMethod {
-        self.extraction_method
+            let page = self.new_page(handle, "about:blank").await?;
+            let navigated = async {
+                if !patterns.is_empty() {
+                    block_requests(&page, patterns, filter).await?;
+                }
+                if let Some(auth) = &self.config.auth {
+                    // Sent with every request the page makes, like the HTTP path
+                    let headers = network::Headers::new(serde_json::json!({
+                        "Authorization": auth.header_value(),
+                    }));
+                    page.execute(network::SetExtraHttpHeadersParams::new(headers))
+                        .await
+                        .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+                }
+                if let Some(user_agent) = user_agent {
+                    page.execute(network::SetUserAgentOverrideParams::new(user_agent.as_str()))
+                        .await
+                        .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+                }
+                if let Some(media) = media {
+                    page.execute(media)
+                        .await
+                        .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+                }
+                page.goto(url.as_str())
+                    .await
+                    .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+                Ok(())
+            }
+            .await;
+            match navigated {
+                Ok(()) => Ok(page),
+                Err(e) => {
+                    self.close_page(handle, page).await;
+                    Err(e)
+                }
+            }
+        })
+        .await
     }
 
-    /// Check if using accessibility tree extraction
-    pub fn uses_accessibility_tree(&self) -> bool {
-        self.browser.is_some() && self.extraction_method == ExtractionMethod::Accessibility
+    /// Open a tab at `url`
+    ///
+    /// With cookies disabled the tab gets its own incognito browser context,
+    /// so nothing it stores outlives [`Fetcher::close_page`].
+    async fn new_page(&self, handle: &BrowserHandle, url: &str) -> Result<chromiumoxide::Page> {
+        let mut params = CreateTargetParams::new(url);
+        if !self.config.enable_cookies {
+            let context = handle
+                .browser
+                .execute(CreateBrowserContextParams::default())
+                .await
+                .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+            params.browser_context_id = Some(context.result.browser_context_id.clone());
+        }
+        handle
+            .browser
+            .new_page(params)
+            .await
+            .map_err(|e| FoxError::BrowserError(e.to_string()))
     }
-}
 
-impl Drop for Fetcher {
-    fn drop(&mut self) {
-        // Browser cleanup is handled by the async runtime
+    /// Close a tab opened by [`Fetcher::new_page`], discarding its incognito
+    /// context if it has one
+    async fn close_page(&self, handle: &BrowserHandle, page: chromiumoxide::Page) {
+        if !self.config.enable_cookies {
+            let info = page
+                .execute(GetTargetInfoParams {
+                    target_id: Some(page.target_id().clone()),
+                })
+                .await;
+            if let Ok(Some(context)) = info.map(|info| info.result.target_info.browser_context_id.clone()) {
+                // Disposing the context closes its pages too
+                let _ = handle.browser.execute(DisposeBrowserContextParams::new(context)).await;
+                return;
+            }
+        }
+        let _ = page.close().await;
+    }
+
+    /// Wait until a freshly opened page is ready according to the
+    /// configured [`WaitStrategy`]
+    async fn wait_until_settled(&self, page: &chromiumoxide::Page) -> Result<()> {
+        let strategy = self.config.wait_strategy;
+        if strategy.waits_for_load() {
+            page.wait_for_navigation()
+                .await
+                .map_err(|e| FoxError::BrowserError(e.to_string()))?;
+        } else {
+            wait_for_dom(page).await?;
+        }
+        if strategy.waits_for_network_idle() {
+            wait_for_network_idle(page, NETWORK_QUIET_PERIOD).await?;
+        }
+        Ok(())
+    }
+
+    /// Extract the page title, cleaned up if `clean_titles` is enabled
+    fn page_title(&self, html: &str) -> Option<String> {
+        if self.config.clean_titles {
+            extract::extract_clean_title(html)
+        } else {
+            extract::extract_title(html)
+        }
+    }
+
+    /// Apply configured cleanup to freshly extracted content
+    fn post_process(&self, mut content: ExtractedContent) -> ExtractedContent {
+        if content.lang.is_none() && self.config.detect_language {
+            content.lang = extract::detect_language(&content.text);
+        }
+        if self.config.sanitize_text {
+            extract::sanitize_content(&mut content);
+        }
+        if self.config.strip_tracking_params {
+            links::strip_tracking_from_content(&mut content);
+        }
+        if let Some(max_bytes) = self.config.max_content_bytes {
+            extract::truncate_content(&mut content, max_bytes);
+        }
+        content
+    }
+
+    /// Whether the browser started, and why not if it didn't
+    pub fn browser_status(&self) -> &BrowserStatus {
+        &self.browser_status
+    }
+
+    /// Replace the fetch configuration for subsequent fetches
+    ///
+    /// The HTTP client keeps the user agent, timeout, and redirect
+    /// blocklist it was built with, and enabling JavaScript only takes
+    /// effect if a browser was launched.
+    pub fn set_config(&mut self, config: FetchConfig) {
+        if config.requests_per_second_per_host != self.config.requests_per_second_per_host {
+            self.rate_limiter = HostRateLimiter::new(config.requests_per_second_per_host);
+        }
+        self.config = config;
+    }
+
+    /// Get a reference to the Chrome manager
+    pub fn chrome_manager(&self) -> &ChromeManager {
+        &self.chrome_manager
+    }
+
+    /// Check if JavaScript rendering is available
+    pub fn has_javascript(&self) -> bool {
+        self.browser.is_some()
+    }
+
+    /// Get the current extraction method
+    pub fn extraction_method(&self) -> ExtractionMethod {
+        self.extraction_method
+    }
+
+    /// Change the extraction method used for subsequent fetches
+    pub fn set_extraction_method(&mut self, method: ExtractionMethod) {
+        self.extraction_method = method;
+    }
+
+    /// Change how links are rendered in subsequent fetches
+    pub fn set_link_style(&mut self, style: LinkStyle) {
+        self.config.link_style = style;
+    }
+
+    /// Check if using accessibility tree extraction
+    pub fn uses_accessibility_tree(&self) -> bool {
+        self.browser.is_some() && self.extraction_method == ExtractionMethod::Accessibility
+    }
+}
+
+impl Drop for Fetcher {
+    fn drop(&mut self) {
+        // Browser cleanup is handled by the async runtime
+    }
+}
+
+/// JavaScript that maps each readable iframe's URL to its body HTML
+///
+/// `contentDocument` is null for cross-origin frames, so only same-origin
+/// frames are included.
+const IFRAME_BODIES_SCRIPT: &str = r#"(() => {
+    const frames = {};
+    for (const frame of document.querySelectorAll("iframe[src]")) {
+        try {
+            const doc = frame.contentDocument;
+            if (doc && doc.body) frames[frame.src] = doc.body.innerHTML;
+        } catch (e) {}
+    }
+    return frames;
+})()"#;
+
+/// JavaScript that sets the value of the form control named `name`
+fn fill_field_script(name: &str, value: &str) -> String {
+    let name = serde_json::Value::String(name.to_string());
+    let value = serde_json::Value::String(value.to_string());
+    format!(
+        r#"(() => {{
+    const el = document.getElementsByName({name})[0];
+    if (!el) throw new Error("No field named " + {name});
+    if (el.type === "checkbox" || el.type === "radio") {{
+        el.checked = {value} !== "" && {value} !== "false";
+    }} else {{
+        el.value = {value};
+    }}
+    el.dispatchEvent(new Event("input", {{ bubbles: true }}));
+    el.dispatchEvent(new Event("change", {{ bubbles: true }}));
+}})()"#
+    )
+}
+
+/// JavaScript that submits the form owning the control named `name`,
+/// or the first form on the page
+fn submit_form_script(name: &str) -> String {
+    let name = serde_json::Value::String(name.to_string());
+    format!(
+        r#"(() => {{
+    const el = document.getElementsByName({name})[0];
+    const form = (el && el.form) || document.forms[0];
+    if (!form) throw new Error("No form to submit");
+    if (form.requestSubmit) form.requestSubmit(); else form.submit();
+}})()"#
+    )
+}
+
+/// Whether two URLs refer to the same document, ignoring any fragment
+fn same_document(a: &Url, b: &Url) -> bool {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    a.set_fragment(None);
+    b.set_fragment(None);
+    a == b
+}
+
+/// Redirect policy that refuses to follow redirects to blocklisted hosts
+fn redirect_policy(hosts: Vec<String>, max_redirects: usize) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() > max_redirects {
+            attempt.error("too many redirects")
+        } else if blocklist::url_is_blocked(attempt.url().as_str(), &hosts) {
+            attempt.stop()
+        } else {
+            let _ = REDIRECT_CHAIN.try_with(|chain| chain.borrow_mut().push(attempt.url().clone()));
+            attempt.follow()
+        }
+    })
+}
+
+/// Markdown converted from an AX tree, with a plain-text rendering if asked for
+struct AxConversion {
+    markdown: String,
+    links: Vec<Link>,
+    plain_text: Option<String>,
+}
+
+/// Convert `tree` to markdown, and to plain text with `plain_options`, on a
+/// blocking thread
+///
+/// Trees with thousands of nodes take long enough to convert that doing it
+/// inline would hold up every other task on the same worker.
+async fn convert_ax_tree(
+    tree: AXTree,
+    options: AxConversionOptions,
+    plain_options: Option<AxConversionOptions>,
+) -> Result<AxConversion> {
+    debug!("Converting {} AX nodes...", tree.nodes.len());
+    report_progress(FetchProgress::Converting {
+        nodes: tree.nodes.len(),
+    });
+    tokio::task::spawn_blocking(move || {
+        let (markdown, links) = ax_tree_to_markdown_with_options(&tree, &options);
+        let plain_text = plain_options.map(|options| ax_tree_to_plain_with_options(&tree, &options));
+        AxConversion {
+            markdown,
+            links,
+            plain_text,
+        }
+    })
+    .await
+    .map_err(|e| FoxError::ExtractionError(format!("AX tree conversion failed: {}", e)))
+}
+
+/// Media emulation a browser page needs before navigating, if any
+fn media_emulation(config: &FetchConfig) -> Option<emulation::SetEmulatedMediaParams> {
+    config
+        .emulate_print
+        .then(|| emulation::SetEmulatedMediaParams::builder().media("print").build())
+}
+
+/// Decides which requests a browser page aborts
+#[derive(Debug, Clone, Default)]
+struct RequestFilter {
+    /// Resource types that are always aborted
+    resources: Vec<ResourceType>,
+    /// Hosts whose requests are aborted
+    blocklist: Vec<String>,
+}
+
+impl RequestFilter {
+    /// Interception patterns for the requests this filter needs to see
+    ///
+    /// A blocklist has to inspect every request; resource blocking alone
+    /// only pauses the blocked types.
+    fn patterns(&self) -> Vec<cdp_fetch::RequestPattern> {
+        if self.blocklist.is_empty() {
+            block_patterns(&self.resources)
+        } else {
+            vec![cdp_fetch::RequestPattern::builder()
+                .url_pattern("*")
+                .request_stage(cdp_fetch::RequestStage::Request)
+                .build()]
+        }
+    }
+
+    /// Whether a paused request should be failed rather than continued
+    fn should_abort(&self, url: &str, resource_type: &network::ResourceType) -> bool {
+        self.resources.iter().any(|kind| &kind.to_cdp() == resource_type)
+            || blocklist::url_is_blocked(url, &self.blocklist)
+    }
+}
+
+/// Intercept requests on `page` matching `patterns`, failing the ones
+/// `filter` rejects and continuing the rest
+///
+/// Paused requests are handled by a background task that ends when the
+/// page closes.
+async fn block_requests(
+    page: &chromiumoxide::Page,
+    patterns: Vec<cdp_fetch::RequestPattern>,
+    filter: RequestFilter,
+) -> Result<()> {
+    let browser_err = |e: chromiumoxide::error::CdpError| FoxError::BrowserError(e.to_string());
+    let mut paused = page
+        .event_listener::<cdp_fetch::EventRequestPaused>()
+        .await
+        .map_err(browser_err)?;
+    page.execute(cdp_fetch::EnableParams::builder().patterns(patterns).build())
+        .await
+        .map_err(browser_err)?;
+
+    let page = page.clone();
+    tokio::spawn(async move {
+        while let Some(event) = paused.next().await {
+            let request_id = event.request_id.clone();
+            let result = if filter.should_abort(&event.request.url, &event.resource_type) {
+                debug!("Blocked request: {}", event.request.url);
+                page.execute(cdp_fetch::FailRequestParams::new(
+                    request_id,
+                    network::ErrorReason::BlockedByClient,
+                ))
+                .await
+                .map(|_| ())
+            } else {
+                page.execute(cdp_fetch::ContinueRequestParams::new(request_id))
+                    .await
+                    .map(|_| ())
+            };
+            if let Err(e) = result {
+                debug!("Failed to resolve intercepted request: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Poll until the document has finished parsing
+async fn wait_for_dom(page: &chromiumoxide::Page) -> Result<()> {
+    loop {
+        let state: String = page
+            .evaluate("document.readyState")
+            .await
+            .map_err(|e| FoxError::BrowserError(e.to_string()))?
+            .into_value()
+            .unwrap_or_default();
+        if state == "interactive" || state == "complete" {
+            return Ok(());
+        }
+        tokio::time::sleep(READY_STATE_POLL).await;
+    }
+}
+
+/// Requests a page has started but not yet finished loading
+#[derive(Debug, Default)]
+struct InFlightRequests {
+    ids: HashSet<String>,
+}
+
+impl InFlightRequests {
+    fn started(&mut self, id: &str) {
+        self.ids.insert(id.to_string());
+    }
+
+    /// Requests that began before monitoring started are ignored
+    fn finished(&mut self, id: &str) {
+        self.ids.remove(id);
+    }
+
+    fn is_idle(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// Wait until no requests have been in flight for `quiet`
+///
+/// Only requests issued after this is called are tracked.
+async fn wait_for_network_idle(page: &chromiumoxide::Page, quiet: Duration) -> Result<()> {
+    let browser_err = |e: chromiumoxide::error::CdpError| FoxError::BrowserError(e.to_string());
+    page.execute(network::EnableParams::default()).await.map_err(browser_err)?;
+    let mut started = page
+        .event_listener::<network::EventRequestWillBeSent>()
+        .await
+        .map_err(browser_err)?;
+    let mut finished = page
+        .event_listener::<network::EventLoadingFinished>()
+        .await
+        .map_err(browser_err)?;
+    let mut failed = page
+        .event_listener::<network::EventLoadingFailed>()
+        .await
+        .map_err(browser_err)?;
+
+    let mut in_flight = InFlightRequests::default();
+    loop {
+        tokio::select! {
+            Some(event) = started.next() => in_flight.started(event.request_id.inner()),
+            Some(event) = finished.next() => in_flight.finished(event.request_id.inner()),
+            Some(event) = failed.next() => in_flight.finished(event.request_id.inner()),
+            _ = tokio::time::sleep(quiet), if in_flight.is_idle() => return Ok(()),
+            else => return Ok(()),
+        }
+    }
+}
+
+/// Abort `fut` if it runs longer than `limit`
+///
+/// Pages that never fire a load event would otherwise hang the caller.
+async fn navigation_timeout<T>(
+    limit: Duration,
+    fut: impl std::future::Future<Output = Result<T>>,
+) -> Result<T> {
+    match tokio::time::timeout(limit, fut).await {
+        Ok(result) => result,
+        Err(_) => Err(FoxError::BrowserError("navigation timed out".to_string())),
     }
 }
 
@@ -295,3 +1439,782 @@ pub async fn fetch_simple(url: &str) -> Result<Page> {
     let fetcher = Fetcher::with_config(config).await?;
     fetcher.fetch(url).await
 }
+
+/// Check whether `haystack` contains `needle`, ignoring ASCII case
+fn contains_ignore_case(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window.eq_ignore_ascii_case(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accessibility::AXNode;
+    use std::collections::HashMap;
+
+    fn ax_node(id: &str, role: &str, name: Option<&str>, children: &[&str]) -> AXNode {
+        AXNode {
+            node_id: id.to_string(),
+            role: role.to_string(),
+            name: name.map(String::from),
+            value: None,
+            description: None,
+            level: Some(1),
+            url: None,
+            focused: false,
+            ignored: false,
+            child_ids: children.iter().map(|c| c.to_string()).collect(),
+            properties: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_form_requires_browser() {
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let fields = vec![("q".to_string(), "rust".to_string())];
+        let result = fetcher.submit_form("https://example.com", &fields, None).await;
+        assert!(matches!(result, Err(FoxError::BrowserError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_progress_callback_sees_first_run_download() {
+        let data_dir =
+            std::env::temp_dir().join(format!("fox-fetcher-download-{}", std::process::id()));
+        let mut chrome_manager = ChromeManager::with_config(ChromeConfig {
+            mode: "bundled".to_string(),
+            data_dir: data_dir.clone(),
+            ..Default::default()
+        });
+        chrome_manager.set_manifest_url(crate::chrome::serve_fake_chrome("9.9.9").await);
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        chrome_manager.set_progress_callback(move |progress| {
+            sink.lock().unwrap().push(progress);
+        });
+
+        // The stub binary can't launch, so the fetcher falls back to HTTP
+        let fetcher = Fetcher::with_chrome_manager(FetchConfig::default(), chrome_manager)
+            .await
+            .unwrap();
+        assert!(fetcher.browser.is_none());
+
+        let events = events.lock().unwrap();
+        assert!(matches!(
+            events.first(),
+            Some(DownloadProgress::Starting { version }) if version == "9.9.9"
+        ));
+        assert!(events.iter().any(|e| matches!(e, DownloadProgress::Downloading { .. })));
+        assert!(matches!(events.last(), Some(DownloadProgress::Complete { .. })));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn test_failed_chrome_download_is_reported() {
+        let data_dir =
+            std::env::temp_dir().join(format!("fox-fetcher-offline-{}", std::process::id()));
+        let mut chrome_manager = ChromeManager::with_config(ChromeConfig {
+            mode: "bundled".to_string(),
+            data_dir: data_dir.clone(),
+            ..Default::default()
+        });
+        // Nothing listens on port 1, so the manifest fetch fails
+        chrome_manager.set_manifest_url("http://127.0.0.1:1/manifest.json".to_string());
+
+        let fetcher = Fetcher::with_chrome_manager(FetchConfig::default(), chrome_manager)
+            .await
+            .unwrap();
+        match fetcher.browser_status() {
+            BrowserStatus::Unavailable(reason) => {
+                assert!(reason.contains("Chrome manifest"), "{}", reason)
+            }
+            status => panic!("expected Unavailable, got {:?}", status),
+        }
+
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        assert_eq!(fetcher.browser_status(), &BrowserStatus::Disabled);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn test_wait_strategy_selects_wait_path() {
+        let load: WaitStrategy = "load".parse().unwrap();
+        assert!(load.waits_for_load() && !load.waits_for_network_idle());
+
+        let dom: WaitStrategy = "DOMContentLoaded".parse().unwrap();
+        assert_eq!(dom, WaitStrategy::DomContentLoaded);
+        assert!(!dom.waits_for_load() && !dom.waits_for_network_idle());
+
+        let idle: WaitStrategy = "networkidle".parse().unwrap();
+        assert!(idle.waits_for_load() && idle.waits_for_network_idle());
+
+        assert!("eventually".parse::<WaitStrategy>().is_err());
+        assert_eq!(FetchConfig::default().wait_strategy, WaitStrategy::Load);
+    }
+
+    #[test]
+    fn test_user_agent_presets() {
+        let googlebot: UserAgentPreset = "Googlebot".parse().unwrap();
+        assert!(googlebot.user_agent().contains("Googlebot/2.1"));
+        assert!(UserAgentPreset::Firefox.user_agent().contains("Firefox/"));
+        assert_eq!(UserAgentPreset::Fox.user_agent(), FetchConfig::default().user_agent);
+        assert!("netscape".parse::<UserAgentPreset>().is_err());
+    }
+
+    #[test]
+    fn test_emulate_print_sets_print_media() {
+        assert!(media_emulation(&FetchConfig::default()).is_none());
+
+        let config = FetchConfig {
+            emulate_print: true,
+            ..Default::default()
+        };
+        let params = media_emulation(&config).unwrap();
+        assert_eq!(params.media.as_deref(), Some("print"));
+        assert_eq!(
+            emulation::SetEmulatedMediaParams::IDENTIFIER,
+            "Emulation.setEmulatedMedia"
+        );
+    }
+
+    #[test]
+    fn test_request_filter_aborts_blocklisted_hosts() {
+        let filter = RequestFilter {
+            resources: vec![ResourceType::Font],
+            blocklist: vec!["ads.example.net".to_string()],
+        };
+        let script = network::ResourceType::Script;
+        assert!(filter.should_abort("https://ads.example.net/pixel.js", &script));
+        assert!(filter.should_abort("https://cdn.ads.example.net/a.js", &script));
+        assert!(!filter.should_abort("https://example.com/app.js", &script));
+        assert!(filter.should_abort("https://example.com/a.woff2", &network::ResourceType::Font));
+
+        // With a blocklist every request is intercepted, not just fonts
+        let patterns = filter.patterns();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].resource_type, None);
+        assert!(RequestFilter::default().patterns().is_empty());
+    }
+
+    #[test]
+    fn test_in_flight_requests() {
+        let mut in_flight = InFlightRequests::default();
+        assert!(in_flight.is_idle());
+        in_flight.started("1");
+        in_flight.started("2");
+        in_flight.finished("1");
+        // Finishing a request we never saw start is ignored
+        in_flight.finished("0");
+        assert!(!in_flight.is_idle());
+        in_flight.finished("2");
+        assert!(in_flight.is_idle());
+    }
+
+    #[tokio::test]
+    async fn test_navigation_timeout_aborts_hung_future() {
+        let start = std::time::Instant::now();
+        let result: Result<()> =
+            navigation_timeout(Duration::from_millis(50), std::future::pending()).await;
+        assert!(start.elapsed() < Duration::from_secs(5));
+        match result {
+            Err(FoxError::BrowserError(msg)) => assert_eq!(msg, "navigation timed out"),
+            other => panic!("expected timeout error, got {:?}", other.map(|_| ())),
+        }
+
+        let result = navigation_timeout(Duration::from_secs(5), async { Ok(7) }).await;
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_content_html_keeps_article() {
+        let html = format!(
+            "<html><body><nav>Menu</nav><article><h1>Story</h1><p>{}</p></article></body></html>",
+            "Article body text. ".repeat(10)
+        );
+
+        let config = FetchConfig {
+            javascript: false,
+            content_html: true,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let page = fetcher.render_html(&html, Some("https://example.com/")).unwrap();
+        let outline = page.outline_html().unwrap();
+        assert!(outline.starts_with("<article>"));
+        assert!(outline.contains("<h1>Story</h1>"));
+        assert!(!outline.contains("Menu"));
+
+        let fetcher = Fetcher::with_config(FetchConfig {
+            javascript: false,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let page = fetcher.render_html(&html, Some("https://example.com/")).unwrap();
+        assert!(page.outline_html().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_detect_language_flag() {
+        let body = "The quick brown fox jumps over the lazy dog while the farmer watches from the porch. "
+            .repeat(3);
+        let undeclared = format!("<html><body><article><p>{}</p></article></body></html>", body);
+        let declared = format!(r#"<html lang="de_ch"><body><article><p>{}</p></article></body></html>"#, body);
+
+        let lang = |detect_language: bool, html: String| async move {
+            let fetcher = Fetcher::with_config(FetchConfig {
+                javascript: false,
+                detect_language,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+            let page = fetcher.render_html(&html, Some("https://example.com/")).unwrap();
+            page.content.unwrap().lang
+        };
+        assert_eq!(lang(true, undeclared.clone()).await.as_deref(), Some("en"));
+        assert_eq!(lang(false, undeclared).await, None);
+        assert_eq!(lang(false, declared).await.as_deref(), Some("de-CH"));
+    }
+
+    /// A request received by [`spawn_test_server`]
+    struct TestRequest {
+        method: String,
+        path: String,
+        headers: Vec<(String, String)>,
+    }
+
+    impl TestRequest {
+        fn parse(raw: &str) -> Self {
+            let mut lines = raw.lines();
+            let mut start = lines.next().unwrap_or("").split_whitespace();
+            let method = start.next().unwrap_or("").to_string();
+            let path = start.next().unwrap_or("/").to_string();
+            let headers = lines
+                .take_while(|line| !line.is_empty())
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+                .collect();
+            Self { method, path, headers }
+        }
+
+        /// Value of the header `name`, matched case-insensitively
+        fn header(&self, name: &str) -> Option<&str> {
+            let name = name.to_lowercase();
+            self.headers.iter().find(|(n, _)| *n == name).map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// A response for [`spawn_test_server`] to send
+    struct TestResponse {
+        status: &'static str,
+        headers: Vec<(&'static str, String)>,
+        body: String,
+    }
+
+    impl TestResponse {
+        /// `200 OK` with an HTML body
+        fn html(body: impl Into<String>) -> Self {
+            Self::status("200 OK").header("Content-Type", "text/html").body(body)
+        }
+
+        /// An empty response with `status`, such as `"404 Not Found"`
+        fn status(status: &'static str) -> Self {
+            Self {
+                status,
+                headers: Vec::new(),
+                body: String::new(),
+            }
+        }
+
+        fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+            self.headers.push((name, value.into()));
+            self
+        }
+
+        fn body(mut self, body: impl Into<String>) -> Self {
+            self.body = body.into();
+            self
+        }
+
+        fn to_http(&self) -> String {
+            let mut response = format!("HTTP/1.1 {}\r\n", self.status);
+            for (name, value) in &self.headers {
+                response.push_str(&format!("{}: {}\r\n", name, value));
+            }
+            // An explicit Content-Length may promise more than is sent
+            if !self.headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-length")) {
+                response.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+            }
+            response.push_str("Connection: close\r\n\r\n");
+            response.push_str(&self.body);
+            response
+        }
+    }
+
+    /// Tiny HTTP server answering every request with `respond(request)`
+    ///
+    /// Connections stay open until the client hangs up. Returns the server
+    /// address and the list of requested paths.
+    async fn spawn_test_server<F>(respond: F) -> (std::net::SocketAddr, Arc<std::sync::Mutex<Vec<String>>>)
+    where
+        F: Fn(&TestRequest) -> TestResponse + Send + Sync + 'static,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+        let respond = Arc::new(respond);
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+                let seen = Arc::clone(&seen);
+                let respond = Arc::clone(&respond);
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let request = TestRequest::parse(&String::from_utf8_lossy(&buf[..n]));
+                    let response = respond(&request);
+                    seen.lock().unwrap().push(request.path);
+                    let _ = socket.write_all(response.to_http().as_bytes()).await;
+                    let _ = socket.read(&mut buf).await;
+                });
+            }
+        });
+        (addr, requests)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_title_stops_after_title() {
+        // Promise a huge body, send only the head, then stall: a full read
+        // would hang until the timeout below
+        let (addr, _) = spawn_test_server(|_| {
+            TestResponse::html("<html><head><TITLE>Fox &amp; Friends</TITLE><meta charset=\"utf-8\">")
+                .header("Content-Length", "10000000")
+        })
+        .await;
+
+        let fetcher = Fetcher::new().await.unwrap();
+        let title = tokio::time::timeout(
+            Duration::from_secs(5),
+            fetcher.fetch_title(&format!("http://{}/", addr)),
+        )
+        .await
+        .expect("fetch_title read past the title")
+        .unwrap();
+        assert_eq!(title.as_deref(), Some("Fox & Friends"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_title_missing() {
+        let (addr, _) = spawn_test_server(|_| {
+            TestResponse::html("<html><head></head><body><p>No title</p></body></html>")
+        })
+        .await;
+        let fetcher = Fetcher::new().await.unwrap();
+        let title = fetcher.fetch_title(&format!("http://{}/", addr)).await.unwrap();
+        assert_eq!(title, None);
+    }
+
+    #[tokio::test]
+    async fn test_custom_connection_pool() {
+        let (addr, requests) = spawn_test_server(|request| {
+            TestResponse::html(format!(
+                "<html><head><title>{}</title></head><body><p>Hi</p></body></html>",
+                request.path
+            ))
+        })
+        .await;
+
+        for (max_idle, idle_secs) in [(0, 1), (4, 5)] {
+            let config = FetchConfig {
+                javascript: false,
+                pool_max_idle_per_host: max_idle,
+                pool_idle_timeout_secs: idle_secs,
+                ..Default::default()
+            };
+            let fetcher = Fetcher::with_config(config).await.unwrap();
+            let urls = (0..3).map(|i| format!("http://{}/{}", addr, i));
+            let pages: Vec<_> = fetcher.fetch_stream(urls, 3).collect().await;
+            assert!(pages.iter().all(|(_, page)| page.is_ok()));
+        }
+        assert_eq!(requests.lock().unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_cookies_can_be_disabled() {
+        // Sets a cookie on every response and records the Cookie header sent
+        let cookies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&cookies);
+        let (addr, _) = spawn_test_server(move |request| {
+            seen.lock().unwrap().push(request.header("Cookie").map(String::from));
+            TestResponse::html("<html><head><title>Cookies</title></head><body><p>Hi</p></body></html>")
+                .header("Set-Cookie", "session=abc; Path=/")
+        })
+        .await;
+        let url = format!("http://{}/", addr);
+
+        for enable_cookies in [true, false] {
+            cookies.lock().unwrap().clear();
+            let config = FetchConfig {
+                javascript: false,
+                enable_cookies,
+                ..Default::default()
+            };
+            let fetcher = Fetcher::with_config(config).await.unwrap();
+            fetcher.fetch(&url).await.unwrap();
+            fetcher.fetch(&url).await.unwrap();
+
+            let expected = if enable_cookies {
+                vec![None, Some("session=abc".to_string())]
+            } else {
+                vec![None, None]
+            };
+            assert_eq!(*cookies.lock().unwrap(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_basic_auth_header() {
+        let auth = Auth::Basic {
+            user: "Aladdin".to_string(),
+            pass: "open sesame".to_string(),
+        };
+        assert_eq!(auth.header_value(), "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert_eq!(Auth::Bearer("t0k3n".to_string()).header_value(), "Bearer t0k3n");
+        assert!(!format!("{:?}", auth).contains("sesame"));
+
+        // Records the Authorization header of each request
+        let headers = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&headers);
+        let (addr, _) = spawn_test_server(move |request| {
+            seen.lock().unwrap().push(request.header("Authorization").map(String::from));
+            TestResponse::html("<html><head><title>Private</title></head><body><p>Hi</p></body></html>")
+        })
+        .await;
+
+        let config = FetchConfig {
+            javascript: false,
+            auth: Some(auth),
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        fetcher.fetch(&format!("http://{}/", addr)).await.unwrap();
+        assert_eq!(
+            *headers.lock().unwrap(),
+            vec![Some("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_chain() {
+        // `/a` redirects to `/b`, which redirects to `/c`
+        let (addr, _) = spawn_test_server(|request| match request.path.as_str() {
+            "/a" => TestResponse::status("302 Found").header("Location", "/b"),
+            "/b" => TestResponse::status("301 Moved Permanently").header("Location", "/c"),
+            _ => TestResponse::html("<html><head><title>C</title></head><body><p>Landed</p></body></html>"),
+        })
+        .await;
+        let base = format!("http://{}", addr);
+
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let page = fetcher.fetch(&format!("{}/a", base)).await.unwrap();
+        let chain: Vec<String> = page.redirect_chain.iter().map(|u| u.to_string()).collect();
+        assert_eq!(chain, vec![format!("{}/b", base), format!("{}/c", base)]);
+
+        let page = fetcher.fetch(&format!("{}/c", base)).await.unwrap();
+        assert!(page.redirect_chain.is_empty());
+
+        let config = FetchConfig {
+            javascript: false,
+            max_redirects: 1,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        match fetcher.fetch(&format!("{}/a", base)).await {
+            Err(FoxError::TooManyRedirects { limit, .. }) => assert_eq!(limit, 1),
+            other => panic!("expected TooManyRedirects, got {:?}", other.map(|p| p.url)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_url() {
+        // `/missing` is a 404; `/get-only` rejects HEAD but answers a GET
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+        let (addr, _) = spawn_test_server(move |request| {
+            let ranged = request.header("Range") == Some("bytes=0-0");
+            let status = match (request.method.as_str(), request.path.as_str()) {
+                ("HEAD", "/missing") => "404 Not Found",
+                ("HEAD", "/get-only") => "405 Method Not Allowed",
+                ("GET", "/get-only") if ranged => "206 Partial Content",
+                _ => "500 Internal Server Error",
+            };
+            seen.lock().unwrap().push(format!("{} {}", request.method, request.path));
+            TestResponse::status(status).header("Content-Type", "text/html")
+        })
+        .await;
+
+        let fetcher = Fetcher::new().await.unwrap();
+        let missing = fetcher.check_url(&format!("http://{}/missing", addr)).await.unwrap();
+        assert_eq!(missing.status, 404);
+        assert_eq!(missing.content_type.as_deref(), Some("text/html"));
+
+        let get_only = fetcher.check_url(&format!("http://{}/get-only", addr)).await.unwrap();
+        assert_eq!(get_only.status, 206);
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["HEAD /missing", "HEAD /get-only", "GET /get-only"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follow_canonical_refetches_once() {
+        let (addr, requests) = spawn_test_server(|request| {
+            // Both pages point at /article, so following must stop there
+            TestResponse::html(format!(
+                r#"<html><head><title>{}</title><link rel="canonical" href="/article#top"></head><body><p>Page {}</p></body></html>"#,
+                request.path, request.path
+            ))
+        })
+        .await;
+
+        let config = FetchConfig {
+            javascript: false,
+            follow_canonical: true,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let page = fetcher.fetch(&format!("http://{}/amp", addr)).await.unwrap();
+
+        assert_eq!(page.url.path(), "/article");
+        assert_eq!(page.title.as_deref(), Some("/article"));
+        assert_eq!(*requests.lock().unwrap(), vec!["/amp", "/article"]);
+        assert_eq!(
+            page.canonical_url().map(|u| u.to_string()),
+            Some(format!("http://{}/article#top", addr))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stream_yields_every_url() {
+        let (addr, _) = spawn_test_server(|request| {
+            TestResponse::html(format!(
+                "<html><head><title>{}</title></head><body><p>Page {}</p></body></html>",
+                request.path, request.path
+            ))
+        })
+        .await;
+
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let urls = vec![
+            format!("http://{}/one", addr),
+            format!("http://{}/two", addr),
+            "not a url".to_string(),
+        ];
+
+        let mut results: Vec<(String, Result<Page>)> =
+            fetcher.fetch_stream(urls.clone(), 2).collect().await;
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(results.len(), 3);
+        let (url, page) = &results[0];
+        assert_eq!(url, &urls[0]);
+        assert_eq!(page.as_ref().unwrap().title.as_deref(), Some("/one"));
+        let (url, page) = &results[1];
+        assert_eq!(url, &urls[1]);
+        assert_eq!(page.as_ref().unwrap().title.as_deref(), Some("/two"));
+        assert!(results[2].1.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_keeps_input_order() {
+        let (addr, requests) = spawn_test_server(|request| match request.path.as_str() {
+            "/good" => TestResponse::html("<html><head><title>Good</title></head><body><p>Page</p></body></html>"),
+            _ => TestResponse::status("404 Not Found")
+                .header("Content-Type", "text/html")
+                .body("<html><head><title>Not Found</title></head><body><p>No such page</p></body></html>"),
+        })
+        .await;
+
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+        let missing = format!("http://{}/missing", addr);
+        let good = format!("http://{}/good", addr);
+
+        let results = fetcher.fetch_many(&[&missing, "not a url", &good], 2).await;
+        assert_eq!(results.len(), 3);
+
+        // A 404 still comes back as the page the server sent
+        assert_eq!(results[0].0, missing);
+        assert_eq!(results[0].1.as_ref().unwrap().title.as_deref(), Some("Not Found"));
+        // A URL that can't be fetched fails on its own
+        assert_eq!(results[1].0, "not a url");
+        assert!(results[1].1.is_err());
+        assert_eq!(results[2].0, good);
+        assert_eq!(results[2].1.as_ref().unwrap().title.as_deref(), Some("Good"));
+
+        let mut paths = requests.lock().unwrap().clone();
+        paths.sort();
+        assert_eq!(paths, vec!["/good", "/missing"]);
+    }
+
+    #[cfg(feature = "live-browser-tests")]
+    #[tokio::test]
+    async fn test_submit_get_form_live() {
+        // `/` serves a GET form, anything else echoes its path
+        let (addr, _) = spawn_test_server(|request| {
+            TestResponse::html(if request.path == "/" {
+                r#"<html><body><form action="/echo" method="get"><input name="q"><button type="submit">Go</button></form></body></html>"#.to_string()
+            } else {
+                format!("<html><head><title>Echo</title></head><body><article><p>Query was {}</p></article></body></html>", request.path)
+            })
+        })
+        .await;
+
+        let fetcher = Fetcher::new().await.unwrap();
+        let fields = vec![("q".to_string(), "hello fox".to_string())];
+        let page = fetcher
+            .submit_form(&format!("http://{}/", addr), &fields, None)
+            .await
+            .unwrap();
+
+        assert_eq!(page.url.path(), "/echo");
+        assert!(page.to_markdown().contains("q=hello+fox"));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_large_ax_tree_converts_on_blocking_thread() {
+        let paragraphs = 5000;
+        let ids: Vec<String> = (0..paragraphs).map(|i| format!("p{}", i)).collect();
+        let id_refs: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        let mut tree = AXTree::new();
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), ax_node("root", "RootWebArea", None, &id_refs));
+        for (i, id) in ids.iter().enumerate() {
+            let text = format!("t{}", i);
+            tree.nodes.insert(id.clone(), ax_node(id, "paragraph", None, &[&text]));
+            let name = format!("Paragraph number {}", i);
+            tree.nodes.insert(text.clone(), ax_node(&text, "StaticText", Some(&name), &[]));
+        }
+
+        // On a single-threaded runtime another task only gets to run before
+        // the conversion finishes if the conversion happens off this thread
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = ran.clone();
+        tokio::spawn(async move { flag.store(true, std::sync::atomic::Ordering::SeqCst) });
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let report: ProgressReporter = {
+            let reported = reported.clone();
+            Arc::new(move |progress| reported.lock().unwrap().push(progress))
+        };
+        let options = AxConversionOptions::default();
+        let conversion = FETCH_PROGRESS
+            .scope(report, convert_ax_tree(tree, options.clone(), Some(options)))
+            .await
+            .unwrap();
+        assert!(ran.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![FetchProgress::Converting {
+                nodes: 2 * paragraphs + 1
+            }]
+        );
+
+        assert!(conversion.markdown.contains("Paragraph number 0"));
+        assert!(conversion.markdown.contains(&format!("Paragraph number {}", paragraphs - 1)));
+        assert!(conversion.plain_text.unwrap().contains("Paragraph number 4999"));
+    }
+
+    #[tokio::test]
+    async fn test_prefer_longer_keeps_longer_extraction() {
+        let ax_text = "Accessibility text that is long enough to pass the minimum. ".repeat(5);
+        let tree = || {
+            let mut tree = AXTree::new();
+            tree.root_id = Some("root".to_string());
+            tree.nodes.insert("root".to_string(), ax_node("root", "RootWebArea", None, &["p"]));
+            tree.nodes.insert("p".to_string(), ax_node("p", "paragraph", None, &["t"]));
+            tree.nodes.insert("t".to_string(), ax_node("t", "StaticText", Some(&ax_text), &[]));
+            tree
+        };
+
+        let body = "Readability finds this much longer article text. ".repeat(20);
+        let html = format!("<html><body><article><p>{}</p></article></body></html>", body);
+        let url = Url::parse("https://example.com").unwrap();
+
+        let extract = |strategy: ExtractionStrategy| {
+            let (tree, html, url) = (tree(), html.clone(), url.clone());
+            async move {
+                let config = FetchConfig {
+                    javascript: false,
+                    extraction_strategy: strategy,
+                    ..Default::default()
+                };
+                let fetcher = Fetcher::with_config(config).await.unwrap();
+                fetcher.content_from_ax_tree(tree, &html, &url, None).await.unwrap()
+            }
+        };
+
+        // The AX text clears the minimum, so the default keeps it
+        let content = extract(ExtractionStrategy::AxThenReadability).await;
+        assert!(content.text.starts_with("Accessibility text"));
+
+        let content = extract(ExtractionStrategy::PreferLonger).await;
+        assert!(content.text.contains("much longer article text"));
+
+        assert_eq!("prefer-longer".parse(), Ok(ExtractionStrategy::PreferLonger));
+        assert_eq!("single".parse(), Ok(ExtractionStrategy::SingleMethod));
+        assert!("fastest".parse::<ExtractionStrategy>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tiny_ax_tree_falls_back_to_readability() {
+        let config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(config).await.unwrap();
+
+        let mut tree = AXTree::new();
+        tree.root_id = Some("root".to_string());
+        tree.nodes.insert("root".to_string(), ax_node("root", "RootWebArea", None, &["h1"]));
+        tree.nodes.insert("h1".to_string(), ax_node("h1", "heading", Some("Hi"), &[]));
+
+        let body = "This paragraph has plenty of readable text in it. ".repeat(10);
+        let html = format!("<html><body><article><p>{}</p></article></body></html>", body);
+        let url = Url::parse("https://example.com").unwrap();
+
+        let content = fetcher.content_from_ax_tree(tree, &html, &url, None).await.unwrap();
+        assert!(content.text.contains("plenty of readable text"));
+        assert!(!content.text.starts_with("# Hi"));
+    }
+}
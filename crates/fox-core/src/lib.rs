@@ -7,16 +7,35 @@
 //! - HTML to Markdown conversion
 
 pub mod accessibility;
+pub mod blocklist;
+pub mod charset;
 pub mod chrome;
+pub mod download;
 pub mod extract;
 pub mod fetch;
+pub mod links;
 pub mod markdown;
+pub mod ratelimit;
 
+use std::sync::OnceLock;
 use thiserror::Error;
 
 // Re-export key types
-pub use accessibility::{ax_tree_to_markdown, fetch_ax_tree, AXNode, AXTree};
-pub use chrome::{ChromeConfig, ChromeManager, ChromeSource, DownloadProgress, ExtractionMethod};
+pub use accessibility::{
+    ax_tree_to_html, ax_tree_to_html_with_options, ax_tree_to_markdown,
+    ax_tree_to_markdown_with_options, ax_tree_to_plain, ax_tree_to_plain_with_options,
+    fetch_ax_tree, AXNode, AXTree, AxConversionOptions,
+};
+pub use chrome::{
+    ChromeConfig, ChromeInfo, ChromeManager, ChromeSource, DownloadProgress, ExtractionMethod,
+    ResourceType,
+};
+pub use extract::SiteRule;
+pub use fetch::{
+    Auth, BrowserStatus, ExtractionStrategy, FetchProgress, UrlStatus, UserAgentPreset,
+    WaitStrategy,
+};
+pub use markdown::LinkStyle;
 
 #[derive(Error, Debug)]
 pub enum FoxError {
@@ -29,6 +48,9 @@ pub enum FoxError {
     #[error("Browser error: {0}")]
     BrowserError(String),
 
+    #[error("Too many redirects fetching {url} (limit is {limit})")]
+    TooManyRedirects { url: String, limit: usize },
+
     #[error("Content extraction failed: {0}")]
     ExtractionError(String),
 
@@ -52,6 +74,33 @@ pub struct Page {
     pub html: String,
     /// Extracted main content (if extraction was performed)
     pub content: Option<ExtractedContent>,
+    /// URLs redirected through over HTTP, in order, ending with the one the
+    /// content came from; empty when there was no redirect
+    pub redirect_chain: Vec<url::Url>,
+    /// Markdown converted from `html`, kept so it is only converted once
+    pub markdown_cache: MarkdownCache,
+}
+
+/// Markdown conversions of a page's HTML, computed on first use
+///
+/// The cache is not invalidated, so start a new one with
+/// `MarkdownCache::default()` when changing a page's HTML.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownCache {
+    reader: OnceLock<String>,
+    full: OnceLock<String>,
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Markdown conversions run by `Page` on this thread
+    static MARKDOWN_CONVERSIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Count a markdown conversion, for tests checking the cache
+fn count_conversion() {
+    #[cfg(test)]
+    MARKDOWN_CONVERSIONS.with(|count| count.set(count.get() + 1));
 }
 
 /// Extracted content from a web page
@@ -63,6 +112,24 @@ pub struct ExtractedContent {
     pub title: Option<String>,
     /// Links found in the content
     pub links: Vec<Link>,
+    /// Author name(s), when the page declares them in structured data
+    pub author: Option<String>,
+    /// Publication date, when the page declares it in structured data
+    pub published: Option<String>,
+    /// Summary from the page's `<meta name="description">` or Open Graph
+    /// description
+    pub description: Option<String>,
+    /// Parsed JSON-LD blocks embedded in the page
+    pub structured_data: Vec<serde_json::Value>,
+    /// Plain-text rendering, when requested via `FetchConfig::plain_text`
+    pub plain_text: Option<String>,
+    /// Raw HTML of the main-content region, when requested via
+    /// `FetchConfig::content_html`
+    pub content_html: Option<String>,
+    /// Page language as a BCP 47 tag (`de-CH`, `en`): the `<html lang>`
+    /// value, or else detected from the text if `FetchConfig::detect_language`
+    /// is on
+    pub lang: Option<String>,
 }
 
 /// A link found in the page
@@ -72,47 +139,256 @@ pub struct Link {
     pub text: String,
     /// The URL
     pub url: String,
-    /// Position in the markdown content (character offset)
+    /// Hover/description text (HTML `title` attribute or AX description)
+    pub title: Option<String>,
+    /// Byte offset of the link in the markdown content
     pub position: usize,
 }
 
+impl Link {
+    /// Check whether the link leaves the site of `base`
+    ///
+    /// Relative URLs resolve against `base`. The link stays on-site when its
+    /// host matches the base host or is a subdomain of it, ignoring a leading
+    /// `www.` on the base. Links without a host, like `mailto:`, are never
+    /// external.
+    pub fn is_external(&self, base: &url::Url) -> bool {
+        let host = match base.join(&self.url).ok().and_then(|u| u.host_str().map(str::to_lowercase)) {
+            Some(host) => host,
+            None => return false,
+        };
+        let site = match base.host_str() {
+            Some(site) => site.to_lowercase(),
+            None => return true,
+        };
+        let site = site.strip_prefix("www.").unwrap_or(&site);
+        host != site && !host.ends_with(&format!(".{}", site))
+    }
+}
+
 impl Page {
     /// Convert the page content to markdown
     pub fn to_markdown(&self) -> String {
         if let Some(ref content) = self.content {
             content.text.clone()
         } else {
-            markdown::html_to_markdown(&self.html)
+            self.markdown_cache
+                .reader
+                .get_or_init(|| {
+                    count_conversion();
+                    markdown::html_to_markdown(&self.html)
+                })
+                .clone()
         }
     }
 
+    /// Convert the page content to a standalone HTML document
+    ///
+    /// Renders the markdown back to sanitized HTML under the page title.
+    pub fn to_html(&self) -> String {
+        markdown::markdown_to_html(&self.to_markdown(), self.title.as_deref().unwrap_or(""))
+    }
+
+    /// The `<link rel="canonical">` URL the page declares, if any
+    pub fn canonical_url(&self) -> Option<url::Url> {
+        extract::extract_canonical_url(&self.html, &self.url)
+    }
+
+    /// Convert the whole page to markdown, ignoring extracted content
+    ///
+    /// Includes navigation, sidebars, and footers that reader extraction
+    /// drops. Relative links are resolved against the page URL.
+    pub fn to_full_markdown(&self) -> String {
+        self.markdown_cache
+            .full
+            .get_or_init(|| {
+                count_conversion();
+                let mut links = Vec::new();
+                markdown::html_to_markdown_full_page(
+                    &self.html,
+                    &self.url,
+                    &mut links,
+                    LinkStyle::Inline,
+                )
+            })
+            .clone()
+    }
+
     /// Convert the page content to plain text
+    ///
+    /// Uses the direct plain-text conversion when the fetcher produced one,
+    /// falling back to stripping the markdown otherwise.
     pub fn to_plain_text(&self) -> String {
-        let md = self.to_markdown();
-        markdown::markdown_to_plain(&md)
+        match self.content {
+            Some(ref content) => match content.plain_text {
+                Some(ref plain) => plain.clone(),
+                None => markdown::markdown_to_plain(&content.text),
+            },
+            None => markdown::html_to_plain(&self.html),
+        }
+    }
+
+    /// Raw HTML of the extracted main-content region, if it was kept
+    pub fn outline_html(&self) -> Option<&str> {
+        self.content.as_ref()?.content_html.as_deref()
+    }
+
+    /// The page as JSON: URL, title, markdown content, links, and metadata
+    pub fn to_json(&self) -> serde_json::Value {
+        let content = self.content.as_ref();
+        serde_json::json!({
+            "url": self.url.to_string(),
+            "title": self.title,
+            "content": self.to_markdown(),
+            "links": content.map(|c| c.links.iter().map(|l| {
+                serde_json::json!({
+                    "text": l.text,
+                    "url": l.url,
+                    "title": l.title
+                })
+            }).collect::<Vec<_>>()).unwrap_or_default(),
+            "author": content.and_then(|c| c.author.clone()),
+            "published": content.and_then(|c| c.published.clone()),
+            "description": content.and_then(|c| c.description.clone()),
+            "lang": content.and_then(|c| c.lang.clone()),
+            "structured_data": content.map(|c| c.structured_data.clone()).unwrap_or_default()
+        })
     }
 }
 
+/// Fox's own user agent string
+pub fn default_user_agent() -> String {
+    format!("Fox/{} (Terminal Browser)", env!("CARGO_PKG_VERSION"))
+}
+
 /// Configuration for fetching pages
 #[derive(Debug, Clone)]
 pub struct FetchConfig {
     /// Whether to use JavaScript rendering
     pub javascript: bool,
-    /// User agent string
+    /// User agent string; browser pages keep the browser's own user agent
+    /// unless this is changed from [`default_user_agent`]
     pub user_agent: String,
     /// Request timeout in seconds
     pub timeout_secs: u64,
     /// Whether to extract content using readability
     pub extract_content: bool,
+    /// Whether to strip tracking parameters (utm_*, fbclid, ...) from links
+    pub strip_tracking_params: bool,
+    /// Minimum AX markdown length before falling back to readability
+    pub ax_min_content_chars: usize,
+    /// When accessibility extraction falls back to readability
+    pub extraction_strategy: ExtractionStrategy,
+    /// Role overrides for accessibility tree conversion
+    pub ax_options: AxConversionOptions,
+    /// Also produce a plain-text rendering alongside the markdown
+    pub plain_text: bool,
+    /// How links are rendered in the markdown output
+    pub link_style: LinkStyle,
+    /// Collapse whitespace, NFC-normalize, and trim site suffixes from titles
+    pub clean_titles: bool,
+    /// Truncate extracted text beyond this many bytes
+    pub max_content_bytes: Option<usize>,
+    /// Keep the raw HTML of the main-content region
+    pub content_html: bool,
+    /// Guess the language from the text when the page doesn't declare one
+    pub detect_language: bool,
+    /// Site-specific content selectors tried before the generic heuristics
+    pub site_rules: Vec<SiteRule>,
+    /// Refetch the page's canonical URL when it differs from the requested one
+    pub follow_canonical: bool,
+    /// When a browser-rendered page counts as loaded
+    pub wait_strategy: WaitStrategy,
+    /// Ad/tracker hosts whose requests are aborted (see [`blocklist`])
+    pub blocklist: Vec<String>,
+    /// Idle HTTP connections kept open per host for reuse (0 = no reuse)
+    pub pool_max_idle_per_host: usize,
+    /// Seconds an idle HTTP connection is kept before being closed
+    pub pool_idle_timeout_secs: u64,
+    /// Keep cookies between requests; when off, browser pages also open in
+    /// a throwaway incognito context
+    pub enable_cookies: bool,
+    /// Credentials sent with every request
+    pub auth: Option<Auth>,
+    /// Redirects followed before a fetch fails
+    pub max_redirects: usize,
+    /// Strip zero-width characters, BOMs, and soft hyphens from the content
+    pub sanitize_text: bool,
+    /// In browser mode, inline the content of same-origin iframes where
+    /// they appear; cross-origin iframes keep their placeholder
+    pub follow_iframes: bool,
+    /// CSS selectors for elements removed before extraction, such as
+    /// cookie banners; pages are then extracted with readability
+    pub strip_selectors: Vec<String>,
+    /// Most requests per second sent to any one host; requests to the same
+    /// host wait their turn while other hosts are fetched in parallel
+    pub requests_per_second_per_host: Option<f64>,
+    /// In browser mode, render pages with print stylesheets, which often
+    /// hide navigation and ads
+    pub emulate_print: bool,
 }
 
 impl Default for FetchConfig {
     fn default() -> Self {
         Self {
             javascript: true,
-            user_agent: format!("Fox/{} (Terminal Browser)", env!("CARGO_PKG_VERSION")),
+            user_agent: default_user_agent(),
             timeout_secs: 30,
             extract_content: true,
+            strip_tracking_params: false,
+            ax_min_content_chars: 200,
+            extraction_strategy: ExtractionStrategy::default(),
+            ax_options: AxConversionOptions::default(),
+            plain_text: false,
+            link_style: LinkStyle::default(),
+            clean_titles: false,
+            max_content_bytes: None,
+            content_html: false,
+            detect_language: true,
+            site_rules: Vec::new(),
+            follow_canonical: false,
+            wait_strategy: WaitStrategy::default(),
+            blocklist: Vec::new(),
+            pool_max_idle_per_host: 16,
+            pool_idle_timeout_secs: 90,
+            enable_cookies: true,
+            auth: None,
+            max_redirects: 10,
+            sanitize_text: true,
+            follow_iframes: false,
+            strip_selectors: Vec::new(),
+            requests_per_second_per_host: None,
+            emulate_print: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_markdown_converted_once() {
+        let page = Page {
+            url: url::Url::parse("https://example.com").unwrap(),
+            title: None,
+            html: "<html><body><nav>Menu</nav><h1>Title</h1><p>Body text</p></body></html>"
+                .to_string(),
+            content: None,
+            redirect_chain: Vec::new(),
+            markdown_cache: MarkdownCache::default(),
+        };
+        let conversions = || MARKDOWN_CONVERSIONS.with(|count| count.get());
+
+        let first = page.to_markdown();
+        assert!(first.contains("# Title"));
+        assert_eq!(page.to_markdown(), first);
+        assert_eq!(page.to_markdown(), first);
+        assert_eq!(conversions(), 1);
+
+        let full = page.to_full_markdown();
+        assert!(full.contains("Menu"));
+        assert_eq!(page.to_full_markdown(), full);
+        assert_eq!(conversions(), 2);
+    }
+}
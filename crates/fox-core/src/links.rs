@@ -0,0 +1,189 @@
+//! Link post-processing
+//!
+//! Cleanup applied to extracted links after conversion, such as removing
+//! tracking parameters from query strings.
+
+use crate::ExtractedContent;
+use url::Url;
+
+/// Query parameter prefixes that are always tracking noise
+const TRACKING_PREFIXES: &[&str] = &["utm_"];
+
+/// Individual query parameters known to be used only for tracking
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gclsrc", "msclkid", "yclid", "mc_cid", "mc_eid", "igshid",
+    "_hsenc", "_hsmi",
+];
+
+/// Check if a query parameter name is a known tracking parameter
+pub fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_lowercase();
+    TRACKING_PREFIXES.iter().any(|p| name.starts_with(p)) || TRACKING_PARAMS.contains(&name.as_str())
+}
+
+/// Remove known tracking parameters from a URL's query string
+///
+/// Unknown parameters are left alone. URLs that fail to parse or carry no
+/// tracking parameters are returned unchanged.
+pub fn strip_tracking_params(url: &str) -> String {
+    let mut parsed = match Url::parse(url) {
+        Ok(u) => u,
+        Err(_) => return url.to_string(),
+    };
+
+    if parsed.query().is_none() {
+        return url.to_string();
+    }
+
+    let total = parsed.query_pairs().count();
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(k, _)| !is_tracking_param(k))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    if kept.len() == total {
+        return url.to_string();
+    }
+
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    parsed.to_string()
+}
+
+/// Strip tracking parameters from every link in extracted content
+///
+/// Link URLs are rewritten in the markdown text as well, and link positions
+/// are shifted to account for the shorter URLs.
+pub fn strip_tracking_from_content(content: &mut ExtractedContent) {
+    let original = std::mem::take(&mut content.text);
+    let mut text = String::with_capacity(original.len());
+    let mut cursor = 0;
+    let mut delta: isize = 0;
+
+    for link in content.links.iter_mut() {
+        let cleaned = strip_tracking_params(&link.url);
+
+        if link.position < cursor {
+            link.url = cleaned;
+            continue;
+        }
+        link.position = (link.position as isize + delta) as usize;
+
+        if cleaned == link.url {
+            continue;
+        }
+
+        // Rewrite the `](url` part of this link's markup
+        let needle = format!("]({}", link.url);
+        let search_from = (link.position as isize - delta) as usize;
+        if let Some(offset) = original[search_from..].find(&needle) {
+            let url_start = search_from + offset + 2;
+            text.push_str(&original[cursor..url_start]);
+            text.push_str(&cleaned);
+            cursor = url_start + link.url.len();
+            delta += cleaned.len() as isize - link.url.len() as isize;
+        }
+        link.url = cleaned;
+    }
+
+    text.push_str(&original[cursor..]);
+    content.text = text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Link;
+
+    fn link(url: &str) -> Link {
+        Link {
+            text: String::new(),
+            url: url.to_string(),
+            title: None,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn test_is_external_same_host() {
+        let base = Url::parse("https://www.example.com/articles/1").unwrap();
+        assert!(!link("https://www.example.com/about").is_external(&base));
+        assert!(!link("/about").is_external(&base));
+        assert!(!link("#section").is_external(&base));
+        assert!(!link("https://EXAMPLE.com/").is_external(&base));
+        assert!(!link("mailto:me@other.org").is_external(&base));
+    }
+
+    #[test]
+    fn test_is_external_subdomain() {
+        let base = Url::parse("https://example.com/").unwrap();
+        assert!(!link("https://docs.example.com/guide").is_external(&base));
+        assert!(!link("https://a.b.example.com/").is_external(&base));
+
+        let sub = Url::parse("https://docs.example.com/").unwrap();
+        assert!(link("https://blog.example.com/").is_external(&sub));
+    }
+
+    #[test]
+    fn test_is_external_cross_host() {
+        let base = Url::parse("https://example.com/").unwrap();
+        assert!(link("https://other.org/").is_external(&base));
+        assert!(link("https://notexample.com/").is_external(&base));
+        assert!(link("//cdn.other.org/x.js").is_external(&base));
+    }
+
+    #[test]
+    fn test_strip_tracking_params_keeps_legitimate_params() {
+        let url = "https://example.com/article?id=42&utm_source=newsletter&utm_medium=email";
+        assert_eq!(strip_tracking_params(url), "https://example.com/article?id=42");
+    }
+
+    #[test]
+    fn test_strip_tracking_params_untouched() {
+        let url = "https://example.com/search?q=rust&page=2";
+        assert_eq!(strip_tracking_params(url), url);
+        assert_eq!(strip_tracking_params("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_strip_tracking_from_content() {
+        let first = "https://a.com/?fbclid=abc";
+        let second = "https://b.com/";
+        let text = format!("[A]({}) and [B]({})", first, second);
+        let mut content = ExtractedContent {
+            links: vec![
+                Link {
+                    text: "A".to_string(),
+                    url: first.to_string(),
+                    title: None,
+                    position: 0,
+                },
+                Link {
+                    text: "B".to_string(),
+                    url: second.to_string(),
+                    title: None,
+                    position: text.find("[B]").unwrap(),
+                },
+            ],
+            text,
+            title: None,
+            author: None,
+            published: None,
+            description: None,
+            structured_data: Vec::new(),
+            plain_text: None,
+            content_html: None,
+            lang: None,
+        };
+
+        strip_tracking_from_content(&mut content);
+
+        assert_eq!(content.text, "[A](https://a.com/) and [B](https://b.com/)");
+        assert_eq!(content.links[0].url, "https://a.com/");
+        assert!(content.text[content.links[1].position..].starts_with("[B]"));
+    }
+}
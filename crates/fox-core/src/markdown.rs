@@ -1,29 +1,92 @@
 //! HTML to Markdown conversion
 
 use crate::Link;
+use pulldown_cmark::{html, CowStr, Event, Parser, Tag};
 use regex::Regex;
-use scraper::{ElementRef, Html, Node, Selector};
+use scraper::{ElementRef, Html, Node};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use url::Url;
 
+/// How links are rendered in markdown output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LinkStyle {
+    /// `[text](url)` markup
+    #[default]
+    Inline,
+    /// Link text only; URLs are still collected in the link list
+    Hidden,
+}
+
+impl std::str::FromStr for LinkStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inline" => Ok(LinkStyle::Inline),
+            "hidden" | "none" => Ok(LinkStyle::Hidden),
+            _ => Err(format!("Unknown link style: {}. Use 'inline' or 'hidden'", s)),
+        }
+    }
+}
+
 /// Convert HTML to Markdown
 pub fn html_to_markdown(html: &str) -> String {
     let base_url = Url::parse("about:blank").unwrap();
     let mut links = Vec::new();
-    html_to_markdown_with_base(html, &base_url, &mut links)
+    html_to_markdown_with_base(html, &base_url, &mut links, LinkStyle::Inline)
 }
 
 /// Convert HTML to Markdown with a base URL for resolving links
-pub fn html_to_markdown_with_base(html: &str, base_url: &Url, links: &mut Vec<Link>) -> String {
+pub fn html_to_markdown_with_base(
+    html: &str,
+    base_url: &Url,
+    links: &mut Vec<Link>,
+    link_style: LinkStyle,
+) -> String {
     let document = Html::parse_fragment(html);
     let mut output = String::new();
     let root = document.root_element();
 
-    convert_element(root, base_url, &mut output, links, &mut Context::default());
+    let mut ctx = Context {
+        link_style,
+        ..Default::default()
+    };
+    let first_link = links.len();
+    convert_element(root, base_url, &mut output, links, &mut ctx);
+
+    // Leading whitespace is trimmed below, so shift link positions to match
+    let leading = output.len() - output.trim_start().len();
+    for link in &mut links[first_link..] {
+        link.position = link.position.saturating_sub(leading);
+    }
 
     // Clean up the output
     clean_markdown(&output)
 }
 
+/// Convert a whole HTML document to Markdown
+///
+/// Unlike `html_to_markdown_with_base`, navigation, headers, and footers
+/// are kept so the result shows the complete page.
+pub fn html_to_markdown_full_page(
+    html: &str,
+    base_url: &Url,
+    links: &mut Vec<Link>,
+    link_style: LinkStyle,
+) -> String {
+    let document = Html::parse_document(html);
+    let mut output = String::new();
+
+    let mut ctx = Context {
+        link_style,
+        keep_page_chrome: true,
+        ..Default::default()
+    };
+    convert_element(document.root_element(), base_url, &mut output, links, &mut ctx);
+
+    clean_markdown(&output)
+}
+
 /// Convert Markdown to plain text
 pub fn markdown_to_plain(markdown: &str) -> String {
     let mut text = markdown.to_string();
@@ -52,6 +115,292 @@ pub fn markdown_to_plain(markdown: &str) -> String {
     text.trim().to_string()
 }
 
+/// Convert Markdown to a standalone HTML document
+///
+/// The markdown is rendered inside a minimal `<html>` page titled `title`.
+/// Embedded raw HTML is escaped rather than passed through, and links or
+/// images with `javascript:`/`data:`/`vbscript:` targets lose their URL.
+pub fn markdown_to_html(markdown: &str, title: &str) -> String {
+    let events = Parser::new(markdown).map(|event| match event {
+        Event::Html(raw) => Event::Text(raw),
+        Event::Start(Tag::Link(kind, dest, title)) => {
+            Event::Start(Tag::Link(kind, safe_url(dest), title))
+        }
+        Event::Start(Tag::Image(kind, dest, title)) => {
+            Event::Start(Tag::Image(kind, safe_url(dest), title))
+        }
+        other => other,
+    });
+
+    let mut body = String::new();
+    html::push_html(&mut body, events);
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+/// Blank out URLs whose scheme can run script
+fn safe_url(dest: CowStr<'_>) -> CowStr<'_> {
+    let scheme = dest.trim_start().to_ascii_lowercase();
+    if ["javascript:", "data:", "vbscript:"]
+        .iter()
+        .any(|s| scheme.starts_with(s))
+    {
+        CowStr::Borrowed("")
+    } else {
+        dest
+    }
+}
+
+/// Escape text for use in HTML content
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Convert HTML directly to plain text, without any markdown markers
+///
+/// Headings, paragraphs, and other blocks are separated by blank lines; list
+/// items and table rows each get their own line, with table cells separated
+/// by tabs. Links keep only their visible text and images are dropped.
+pub fn html_to_plain(html: &str) -> String {
+    let document = Html::parse_fragment(html);
+    let mut output = String::new();
+    convert_plain_element(document.root_element(), &mut output, &mut false);
+
+    let trimmed: Vec<&str> = output.lines().map(|line| line.trim_end()).collect();
+    clean_markdown(&trimmed.join("\n"))
+}
+
+/// A heading in a document outline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// Heading level (1-6)
+    pub level: u8,
+    /// Heading text without the `#` markers
+    pub text: String,
+    /// Zero-based line number of the heading in the markdown
+    pub line: usize,
+}
+
+/// Build a table of contents from the ATX headings in markdown
+///
+/// Lines inside fenced code blocks are ignored.
+pub fn extract_toc(md: &str) -> Vec<TocEntry> {
+    let mut toc = Vec::new();
+    let mut in_code = false;
+
+    for (line_num, line) in md.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim();
+            if !text.is_empty() {
+                toc.push(TocEntry {
+                    level: level as u8,
+                    text: text.to_string(),
+                    line: line_num,
+                });
+            }
+        }
+    }
+
+    toc
+}
+
+/// Turn heading text into a URL fragment the way GitHub-style anchors do
+///
+/// Letters are lowercased, spaces become `-`, and punctuation other than
+/// `-` and `_` is dropped, so "Getting Started!" becomes `getting-started`.
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c.to_lowercase().collect::<String>()),
+            c if c.is_whitespace() => Some("-".to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Longest slug `slugify_title` returns, in characters
+const MAX_TITLE_SLUG: usize = 80;
+
+/// Turn a page title into a file name stem
+///
+/// Apostrophes are dropped and every other run of punctuation or whitespace
+/// becomes a single `-`, so "What's New: Rust 2.0!" becomes
+/// `whats-new-rust-2-0`. Long titles are cut to 80 characters, and a title
+/// with no letters or digits gives an empty string.
+pub fn slugify_title(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            if slug.chars().count() >= MAX_TITLE_SLUG {
+                break;
+            }
+            slug.extend(c.to_lowercase());
+        } else if c != '\'' && c != '\u{2019}' && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Hard-wrap markdown paragraphs at the given column width
+///
+/// Fenced code blocks, tables, and headings are passed through untouched,
+/// and links are never split across lines. List items and blockquotes keep
+/// their marker on the first line and are indented on continuation lines.
+pub fn reflow(md: &str, width: usize) -> String {
+    if width == 0 {
+        return md.to_string();
+    }
+
+    let mut output = String::with_capacity(md.len());
+    let mut in_fence = false;
+
+    for line in md.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let passthrough = in_fence
+            || trimmed.is_empty()
+            || trimmed.starts_with('|')
+            || trimmed.starts_with('#')
+            || line.chars().count() <= width;
+
+        if passthrough {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let (first_prefix, rest_prefix, body) = split_reflow_prefix(line);
+        let prefix_width = first_prefix.chars().count();
+        let available = width.saturating_sub(prefix_width).max(1);
+
+        let mut current = String::new();
+        let mut current_width = 0;
+        let mut first_line = true;
+
+        for word in reflow_words(body) {
+            let word_width = word.chars().count();
+            if !current.is_empty() && current_width + 1 + word_width > available {
+                output.push_str(if first_line { &first_prefix } else { &rest_prefix });
+                output.push_str(&current);
+                output.push('\n');
+                current.clear();
+                current_width = 0;
+                first_line = false;
+            }
+            if !current.is_empty() {
+                current.push(' ');
+                current_width += 1;
+            }
+            current.push_str(&word);
+            current_width += word_width;
+        }
+
+        if !current.is_empty() {
+            output.push_str(if first_line { &first_prefix } else { &rest_prefix });
+            output.push_str(&current);
+            output.push('\n');
+        }
+    }
+
+    if !md.ends_with('\n') {
+        output.pop();
+    }
+    output
+}
+
+/// Split a line into its first-line prefix, continuation prefix, and body
+fn split_reflow_prefix(line: &str) -> (String, String, &str) {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    // Blockquotes repeat their marker on every line
+    if let Some(rest) = trimmed.strip_prefix("> ") {
+        let prefix = format!("{}> ", indent);
+        return (prefix.clone(), prefix, rest);
+    }
+
+    // Bullet and numbered list items hang-indent under their text
+    let marker_len = if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+        2
+    } else {
+        let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits > 0 && trimmed[digits..].starts_with(". ") {
+            digits + 2
+        } else {
+            0
+        }
+    };
+
+    let first = format!("{}{}", indent, &trimmed[..marker_len]);
+    let rest = " ".repeat(first.chars().count());
+    (first, rest, &trimmed[marker_len..])
+}
+
+/// Split text into words, keeping `[text](url)` links as a single unit
+fn reflow_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut bracket_depth = 0usize;
+    let mut paren_depth = 0usize;
+    let mut pending_url = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' if paren_depth == 0 => bracket_depth += 1,
+            ']' if paren_depth == 0 && bracket_depth > 0 => {
+                bracket_depth -= 1;
+                pending_url = chars.peek() == Some(&'(');
+            }
+            '(' if pending_url => {
+                pending_url = false;
+                paren_depth = 1;
+            }
+            '(' if paren_depth > 0 => paren_depth += 1,
+            ')' if paren_depth > 0 => paren_depth -= 1,
+            c if c.is_whitespace() && bracket_depth == 0 && paren_depth == 0 => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
 #[derive(Default)]
 struct Context {
     in_pre: bool,
@@ -60,6 +409,11 @@ struct Context {
     list_depth: usize,
     list_counters: Vec<usize>,
     current_position: usize,
+    link_style: LinkStyle,
+    /// Render nav, header, and footer elements instead of skipping them
+    keep_page_chrome: bool,
+    /// Converting the contents of a table cell, where tables can't nest
+    in_table_cell: bool,
 }
 
 fn convert_element(
@@ -105,7 +459,8 @@ fn convert_tag(
 
     match tag {
         // Skip non-content elements
-        "script" | "style" | "noscript" | "nav" | "footer" | "header" => return,
+        "script" | "style" | "noscript" | "head" => {}
+        "nav" | "footer" | "header" if !ctx.keep_page_chrome => {}
 
         // Headings
         "h1" => {
@@ -218,21 +573,35 @@ fn convert_tag(
             let text = text.trim();
             if let Some(href) = element.value().attr("href") {
                 let resolved = resolve_url(href, base_url);
+                let title = element
+                    .value()
+                    .attr("title")
+                    .map(|t| normalize_whitespace(t).trim().to_string())
+                    .filter(|t| !t.is_empty() && t != text);
                 let position = output.len();
 
-                output.push('[');
-                if text.is_empty() {
-                    output.push_str(&resolved);
-                } else {
-                    output.push_str(text);
+                match ctx.link_style {
+                    LinkStyle::Inline => {
+                        output.push('[');
+                        if text.is_empty() {
+                            output.push_str(&resolved);
+                        } else {
+                            output.push_str(text);
+                        }
+                        output.push_str("](");
+                        output.push_str(&resolved);
+                        if let Some(ref title) = title {
+                            output.push_str(&link_title_suffix(title));
+                        }
+                        output.push(')');
+                    }
+                    LinkStyle::Hidden => output.push_str(text),
                 }
-                output.push_str("](");
-                output.push_str(&resolved);
-                output.push(')');
 
                 links.push(Link {
                     text: text.to_string(),
                     url: resolved,
+                    title,
                     position,
                 });
             } else {
@@ -244,7 +613,10 @@ fn convert_tag(
         "img" => {
             let alt = element.value().attr("alt").unwrap_or("image");
             if let Some(src) = element.value().attr("src") {
-                let resolved = resolve_url(src, base_url);
+                let resolved = match compact_data_uri(src) {
+                    Some(compact) => compact.to_string(),
+                    None => resolve_url(src, base_url),
+                };
                 output.push_str(&format!("![{}]({})", alt, resolved));
             } else {
                 output.push_str(&format!("[IMG: {}]", alt));
@@ -304,7 +676,55 @@ fn convert_tag(
             ensure_newlines(output, 1);
         }
 
+        // Figures: content (usually an image) followed by an italic caption
+        "figure" => {
+            ensure_newlines(output, 2);
+            let mut caption = None;
+            for child in element.children() {
+                match child.value() {
+                    Node::Element(_) => {
+                        if let Some(child_elem) = ElementRef::wrap(child) {
+                            if child_elem.value().name() == "figcaption" {
+                                let text: String = child_elem.text().collect();
+                                caption = Some(normalize_whitespace(&text).trim().to_string());
+                            } else {
+                                convert_tag(child_elem, base_url, output, links, ctx);
+                            }
+                        }
+                    }
+                    Node::Text(text) => {
+                        let normalized = normalize_whitespace(text.text.as_ref());
+                        if !normalized.trim().is_empty() {
+                            output.push_str(&normalized);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(caption) = caption.filter(|c| !c.is_empty()) {
+                ensure_newlines(output, 1);
+                output.push('*');
+                output.push_str(&caption);
+                output.push('*');
+            }
+            ensure_newlines(output, 2);
+        }
+        "figcaption" => {
+            ensure_newlines(output, 1);
+            output.push('*');
+            convert_element(element, base_url, output, links, ctx);
+            output.push('*');
+            ensure_newlines(output, 2);
+        }
+
         // Tables
+        // Markdown tables can't nest, so leave a placeholder in the cell
+        "table" if ctx.in_table_cell => {
+            if !output.is_empty() && !output.ends_with(' ') {
+                output.push(' ');
+            }
+            output.push_str("[table] ");
+        }
         "table" => {
             ensure_newlines(output, 2);
             convert_table(element, base_url, output, links, ctx);
@@ -398,18 +818,14 @@ fn convert_table(
     links: &mut Vec<Link>,
     ctx: &mut Context,
 ) {
-    let row_selector = Selector::parse("tr").unwrap();
-    let th_selector = Selector::parse("th").unwrap();
-    let td_selector = Selector::parse("td").unwrap();
-
     let mut rows: Vec<Vec<String>> = Vec::new();
     let mut has_header = false;
 
-    for row in table.select(&row_selector) {
+    for row in table_rows(table) {
         let mut cells: Vec<String> = Vec::new();
 
         // Try headers first
-        let headers: Vec<_> = row.select(&th_selector).collect();
+        let headers: Vec<_> = child_elements(row, "th").collect();
         if !headers.is_empty() {
             has_header = true;
             for th in headers {
@@ -418,10 +834,12 @@ fn convert_table(
             }
         } else {
             // Then data cells
-            for td in row.select(&td_selector) {
+            for td in child_elements(row, "td") {
                 let mut cell_output = String::new();
+                ctx.in_table_cell = true;
                 convert_element(td, base_url, &mut cell_output, links, ctx);
-                cells.push(cell_output.trim().replace('\n', " ").to_string());
+                ctx.in_table_cell = false;
+                cells.push(join_cell_lines(&cell_output));
             }
         }
 
@@ -441,7 +859,7 @@ fn convert_table(
     for row in &rows {
         for (i, cell) in row.iter().enumerate() {
             if i < col_widths.len() {
-                col_widths[i] = col_widths[i].max(cell.len());
+                col_widths[i] = col_widths[i].max(cell.width());
             }
         }
     }
@@ -451,7 +869,7 @@ fn convert_table(
         output.push('|');
         for (i, width) in col_widths.iter().enumerate() {
             let cell = row.get(i).map(|s| s.as_str()).unwrap_or("");
-            output.push_str(&format!(" {:<width$} |", cell, width = width));
+            output.push_str(&format!(" {} |", pad_to_width(cell, *width)));
         }
         output.push('\n');
 
@@ -466,6 +884,151 @@ fn convert_table(
     }
 }
 
+/// Join the lines of a converted cell with `<br>`
+///
+/// Markdown table rows can't span lines, but `<br>` keeps intentional
+/// breaks inside a cell.
+fn join_cell_lines(cell: &str) -> String {
+    cell.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// Rows that belong to `table` itself, skipping rows of nested tables
+fn table_rows(table: ElementRef) -> Vec<ElementRef> {
+    let mut rows = Vec::new();
+    for child in table.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "tr" => rows.push(child),
+            "thead" | "tbody" | "tfoot" => rows.extend(child_elements(child, "tr")),
+            _ => {}
+        }
+    }
+    rows
+}
+
+/// Direct child elements of `parent` named `name`
+fn child_elements<'a>(
+    parent: ElementRef<'a>,
+    name: &'a str,
+) -> impl Iterator<Item = ElementRef<'a>> {
+    parent
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(move |e| e.value().name() == name)
+}
+
+/// Pad `text` with spaces to `width` terminal columns
+///
+/// Wide characters such as CJK count as two columns, so padding by
+/// character or byte count would misalign table columns.
+pub(crate) fn pad_to_width(text: &str, width: usize) -> String {
+    let mut padded = text.to_string();
+    padded.push_str(&" ".repeat(width.saturating_sub(text.width())));
+    padded
+}
+
+/// Cut `text` to at most `width` terminal columns, ending in `...` if cut
+pub(crate) fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(3);
+    let mut used = 0;
+    let mut truncated = String::new();
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        truncated.push(c);
+    }
+    truncated.push_str("...");
+    truncated
+}
+
+fn convert_plain_element(element: ElementRef, output: &mut String, in_pre: &mut bool) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => {
+                let content = text.text.as_ref();
+                if *in_pre {
+                    output.push_str(content);
+                } else {
+                    let normalized = normalize_whitespace(content);
+                    if output.is_empty() || output.ends_with('\n') || output.ends_with('\t') {
+                        output.push_str(normalized.trim_start());
+                    } else {
+                        output.push_str(&normalized);
+                    }
+                }
+            }
+            Node::Element(_) => {
+                if let Some(elem) = ElementRef::wrap(child) {
+                    convert_plain_tag(elem, output, in_pre);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn convert_plain_tag(element: ElementRef, output: &mut String, in_pre: &mut bool) {
+    match element.value().name() {
+        // Skip non-content elements
+        "script" | "style" | "noscript" | "nav" | "footer" | "header" | "template" => {}
+
+        // Media and form controls have no readable text of their own
+        "img" | "video" | "audio" | "iframe" | "input" | "select" | "textarea" => {}
+
+        // Blocks separated by a blank line
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "blockquote" | "figure"
+        | "figcaption" | "ul" | "ol" | "table" | "form" | "dl" => {
+            ensure_newlines(output, 2);
+            convert_plain_element(element, output, in_pre);
+            ensure_newlines(output, 2);
+        }
+        "pre" => {
+            ensure_newlines(output, 2);
+            *in_pre = true;
+            convert_plain_element(element, output, in_pre);
+            *in_pre = false;
+            ensure_newlines(output, 2);
+        }
+        "hr" => ensure_newlines(output, 2),
+
+        // Blocks on their own line
+        "div" | "section" | "article" | "main" | "li" | "tr" | "dt" | "dd" => {
+            ensure_newlines(output, 1);
+            convert_plain_element(element, output, in_pre);
+            ensure_newlines(output, 1);
+        }
+        "br" => output.push('\n'),
+
+        // Table cells are tab-separated within their row
+        "td" | "th" => {
+            output.truncate(output.trim_end_matches(' ').len());
+            if !output.is_empty() && !output.ends_with('\n') {
+                output.push('\t');
+            }
+            let mut cell = String::new();
+            convert_plain_element(element, &mut cell, in_pre);
+            output.push_str(normalize_whitespace(&cell).trim());
+        }
+
+        // Everything else (inline formatting, links, spans) contributes its text
+        _ => convert_plain_element(element, output, in_pre),
+    }
+}
+
+/// Format a link title as the ` "title"` suffix inside markdown link parens
+pub(crate) fn link_title_suffix(title: &str) -> String {
+    format!(" \"{}\"", title.replace('"', "\\\""))
+}
+
 fn normalize_whitespace(text: &str) -> String {
     let ws_re = Regex::new(r"\s+").unwrap();
     ws_re.replace_all(text, " ").to_string()
@@ -488,6 +1051,33 @@ fn resolve_url(href: &str, base_url: &Url) -> String {
     }
 }
 
+/// Shorten a `data:` URI to its scheme and media type, e.g. `data:image/png`
+///
+/// Inline images carry their whole base64 payload in the URI, which would
+/// swamp the markdown. Returns `None` for any other URL.
+pub(crate) fn compact_data_uri(src: &str) -> Option<&str> {
+    let src = src.trim();
+    if !src.get(..5)?.eq_ignore_ascii_case("data:") {
+        return None;
+    }
+    let end = src.find([';', ',']).unwrap_or(src.len());
+    Some(&src[..end])
+}
+
+/// Remove invisible characters that pages embed in their text
+///
+/// Zero-width spaces and joiners, byte-order marks, and soft hyphens
+/// render as nothing but break search, yanks, and diffs. Regular
+/// whitespace is left alone.
+pub fn sanitize(text: &str) -> String {
+    text.chars().filter(|&c| !is_invisible(c)).collect()
+}
+
+/// Whether `c` is removed by [`sanitize`]
+pub(crate) fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{200B}'..='\u{200D}' | '\u{FEFF}' | '\u{00AD}')
+}
+
 fn clean_markdown(md: &str) -> String {
     // Remove excessive newlines
     let re = Regex::new(r"\n{3,}").unwrap();
@@ -505,6 +1095,45 @@ fn clean_markdown(md: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_table_pads_wide_characters() {
+        let html = "<table><tr><th>名前</th><th>Note</th></tr><tr><td>Zoë</td><td>日本語のテキスト</td></tr><tr><td>Bob</td><td>ok</td></tr></table>";
+        let md = html_to_markdown(html);
+        let lines: Vec<&str> = md.lines().filter(|l| l.starts_with('|')).collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "| 名前 | Note             |");
+        assert_eq!(lines[1], "| ---- | ---------------- |");
+        for line in &lines {
+            assert_eq!(line.width(), lines[1].width(), "misaligned: {}", line);
+        }
+        assert_eq!(truncate_to_width("日本語のテキスト", 9), "日本語...");
+    }
+
+    #[test]
+    fn test_nested_table_in_cell() {
+        let html = "<table><tr><th>Name</th><th>Details</th></tr><tr><td>Alice</td><td>See<table><tr><td>a</td><td>b</td></tr></table></td></tr></table>";
+        let md = html_to_markdown(html);
+        let lines: Vec<&str> = md.lines().filter(|l| l.starts_with('|')).collect();
+        assert_eq!(lines.len(), 3, "{}", md);
+        assert_eq!(lines[2], "| Alice | See [table] |");
+        for line in &lines {
+            assert_eq!(line.matches('|').count(), 3, "broken row: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_table_cell_keeps_line_breaks() {
+        let html = "<table><tr><th>Address</th></tr><tr><td>1 Main St<br>Springfield</td></tr></table>";
+        let md = html_to_markdown(html);
+        assert!(md.contains("| 1 Main St<br>Springfield |"), "{}", md);
+    }
+
+    #[test]
+    fn test_sanitize_removes_zero_width() {
+        let text = "\u{FEFF}Zero\u{200B}width\u{200C} and soft\u{00AD}hyphen\u{200D}\ttab\n";
+        assert_eq!(sanitize(text), "Zerowidth and softhyphen\ttab\n");
+    }
+
     #[test]
     fn test_heading_conversion() {
         let html = "<h1>Title</h1>";
@@ -526,6 +1155,41 @@ mod tests {
         assert!(md.contains("[Example](https://example.com)"));
     }
 
+    #[test]
+    fn test_hidden_link_conversion() {
+        let html = r#"<p>See <a href="https://example.com">Example</a> now</p>"#;
+        let base_url = Url::parse("https://example.com").unwrap();
+        let mut links = Vec::new();
+        let md = html_to_markdown_with_base(html, &base_url, &mut links, LinkStyle::Hidden);
+        assert_eq!(md, "See Example now\n");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com");
+        assert!(md[links[0].position..].starts_with("Example"));
+    }
+
+    #[test]
+    fn test_link_title_conversion() {
+        let html = r#"<a href="https://example.com" title="An example site">Example</a>"#;
+        let md = html_to_markdown(html);
+        assert!(md.contains(r#"[Example](https://example.com "An example site")"#));
+    }
+
+    #[test]
+    fn test_figure_conversion() {
+        let html = r#"<p>Intro</p><figure><img src="https://example.com/cat.jpg" alt="A cat"><figcaption>My cat</figcaption></figure><p>Outro</p>"#;
+        let md = html_to_markdown(html);
+        assert!(md.contains("Intro\n\n![A cat](https://example.com/cat.jpg)\n*My cat*\n\nOutro"));
+    }
+
+    #[test]
+    fn test_data_uri_image_collapses() {
+        let payload = "iVBORw0KGgo".repeat(200);
+        let html = format!(r#"<p><img src="data:image/png;base64,{}" alt="Logo"></p>"#, payload);
+        let md = html_to_markdown(&html);
+        assert_eq!(md, "![Logo](data:image/png)\n");
+        assert_eq!(compact_data_uri("https://example.com/a.png"), None);
+    }
+
     #[test]
     fn test_list_conversion() {
         let html = "<ul><li>One</li><li>Two</li></ul>";
@@ -548,4 +1212,77 @@ mod tests {
         let plain = markdown_to_plain(md);
         assert_eq!(plain, "Title\n\nbold link");
     }
+
+    #[test]
+    fn test_markdown_to_html() {
+        let md = "# Title\n\nA paragraph with [a link](https://example.com).\n\n<script>alert(1)</script>\n\n[bad](javascript:alert(1))";
+        let html = markdown_to_html(md, "Fish & Chips");
+        assert!(html.contains("<title>Fish &amp; Chips</title>"));
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<p>A paragraph with <a href=\"https://example.com\">a link</a>.</p>"));
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("javascript:"));
+    }
+
+    #[test]
+    fn test_html_to_plain() {
+        let html = r#"<h1>Title</h1><p>Some <strong>bold</strong> and a <a href="https://example.com">link</a>.</p><ul><li>One</li><li>Two</li></ul><table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table>"#;
+        let plain = html_to_plain(html);
+        assert_eq!(plain, "Title\n\nSome bold and a link.\n\nOne\nTwo\n\nA\tB\n1\t2\n");
+    }
+
+    #[test]
+    fn test_slugify_title() {
+        assert_eq!(slugify_title("What's New: Rust 2.0!"), "whats-new-rust-2-0");
+        assert_eq!(slugify_title("  Hello,   World -- (Part 1)  "), "hello-world-part-1");
+        assert_eq!(slugify_title("example.com"), "example-com");
+        assert_eq!(slugify_title("Ünïcode Straße"), "ünïcode-straße");
+        assert_eq!(slugify_title("?!"), "");
+        let long = slugify_title(&"word ".repeat(100));
+        assert!(long.chars().count() <= MAX_TITLE_SLUG);
+        assert!(long.starts_with("word-word") && long.ends_with("word"));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Getting Started"), "getting-started");
+        assert_eq!(slugify("  What's new in v2.0?  "), "whats-new-in-v20");
+        assert_eq!(slugify("snake_case and-dashes"), "snake_case-and-dashes");
+    }
+
+    #[test]
+    fn test_extract_toc() {
+        let md = "# Guide\n\nIntro text\n\n## Setup\n\n```\n# not a heading\n```\n\n### Install ###\n\n#hashtag\n";
+        let toc = extract_toc(md);
+        assert_eq!(
+            toc,
+            vec![
+                TocEntry { level: 1, text: "Guide".to_string(), line: 0 },
+                TocEntry { level: 2, text: "Setup".to_string(), line: 4 },
+                TocEntry { level: 3, text: "Install".to_string(), line: 10 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reflow_wraps_paragraphs() {
+        let md = "one two three four five six seven eight nine ten\n";
+        let wrapped = reflow(md, 20);
+        assert_eq!(wrapped, "one two three four\nfive six seven eight\nnine ten\n");
+        assert!(wrapped.lines().all(|l| l.chars().count() <= 20));
+    }
+
+    #[test]
+    fn test_reflow_leaves_code_blocks_intact() {
+        let code = "let x = some_function_call(argument_one, argument_two, argument_three);";
+        let md = format!("```\n{}\n```\n", code);
+        assert_eq!(reflow(&md, 20), md);
+    }
+
+    #[test]
+    fn test_reflow_keeps_links_whole() {
+        let md = "see [the example site](https://example.com/a/long/path) for details";
+        let wrapped = reflow(md, 20);
+        assert!(wrapped.contains("[the example site](https://example.com/a/long/path)"));
+    }
 }
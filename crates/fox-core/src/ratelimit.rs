@@ -0,0 +1,118 @@
+//! Per-host request rate limiting
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Spaces out requests to the same host
+///
+/// Each host gets its own schedule, so requests to one host wait their turn
+/// while requests to other hosts go ahead in parallel. Without a rate every
+/// request proceeds immediately.
+#[derive(Debug, Default)]
+pub struct HostRateLimiter {
+    interval: Option<Duration>,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    /// Allow at most `requests_per_second` requests to any one host
+    ///
+    /// `None`, or a rate [`request_interval`] rejects, disables limiting.
+    pub fn new(requests_per_second: Option<f64>) -> Self {
+        let interval = requests_per_second.and_then(request_interval);
+        Self {
+            interval,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until a request to `host` is allowed
+    pub async fn acquire(&self, host: &str) {
+        let interval = match self.interval {
+            Some(interval) => interval,
+            None => return,
+        };
+
+        // Reserve the next free slot for this host, then sleep outside the lock
+        let host = host.to_lowercase();
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slot
+                .get(&host)
+                .copied()
+                .filter(|&next| next > now)
+                .unwrap_or(now);
+            // An interval too long to schedule leaves the host unlimited
+            next_slot.insert(host, slot.checked_add(interval).unwrap_or(slot));
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Time between requests to one host at `requests_per_second`
+///
+/// `None` for zero, negative, or non-finite rates, and for rates so small the
+/// interval does not fit in a [`Duration`].
+pub fn request_interval(requests_per_second: f64) -> Option<Duration> {
+    if !(requests_per_second.is_finite() && requests_per_second > 0.0) {
+        return None;
+    }
+    Duration::try_from_secs_f64(1.0 / requests_per_second).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_same_host_requests_are_spaced() {
+        let limiter = HostRateLimiter::new(Some(20.0));
+        let interval = Duration::from_millis(50);
+
+        let start = Instant::now();
+        limiter.acquire("example.com").await;
+        assert!(start.elapsed() < interval);
+        limiter.acquire("Example.com").await;
+        assert!(start.elapsed() >= interval);
+
+        // Another host is not held up by example.com's schedule
+        let before = Instant::now();
+        limiter.acquire("other.org").await;
+        assert!(before.elapsed() < interval);
+    }
+
+    #[tokio::test]
+    async fn test_no_rate_is_unlimited() {
+        for rate in [None, Some(0.0), Some(f64::INFINITY), Some(1e-300)] {
+            let limiter = HostRateLimiter::new(rate);
+            let start = Instant::now();
+            for _ in 0..5 {
+                limiter.acquire("example.com").await;
+            }
+            assert!(start.elapsed() < Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn test_request_interval() {
+        assert_eq!(request_interval(4.0), Some(Duration::from_millis(250)));
+        assert_eq!(request_interval(0.0), None);
+        assert_eq!(request_interval(f64::NAN), None);
+        // 1 / 1e-20 seconds overflows Duration
+        assert_eq!(request_interval(1e-20), None);
+        assert_eq!(request_interval(1e-300), None);
+    }
+
+    #[tokio::test]
+    async fn test_huge_interval_does_not_panic() {
+        // Representable as a Duration, but too far out to add to an Instant
+        let limiter = HostRateLimiter::new(Some(1.0 / (u64::MAX as f64 * 0.9)));
+        assert!(limiter.interval.is_some());
+        limiter.acquire("example.com").await;
+        limiter.acquire("example.com").await;
+    }
+}
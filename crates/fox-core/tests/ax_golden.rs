@@ -0,0 +1,52 @@
+//! Golden-file tests for accessibility tree to markdown conversion
+//!
+//! Each `tests/fixtures/ax/<name>.json` is a saved `AXTree` whose expected
+//! markdown lives next to it in `<name>.md`. Run with `FOX_UPDATE_GOLDEN=1`
+//! to rewrite the `.md` files from the current converter output.
+
+use fox_core::{ax_tree_to_markdown, AXTree};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ax")
+}
+
+#[test]
+fn test_ax_markdown_golden_files() {
+    let update = std::env::var_os("FOX_UPDATE_GOLDEN").is_some();
+    let mut trees: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    trees.sort();
+    assert!(!trees.is_empty(), "no fixtures in {:?}", fixtures_dir());
+
+    let mut failures = Vec::new();
+    for tree_path in &trees {
+        let tree = AXTree::from_json(&fs::read_to_string(tree_path).unwrap())
+            .unwrap_or_else(|e| panic!("{:?}: {}", tree_path, e));
+        let (markdown, _links) = ax_tree_to_markdown(&tree);
+
+        let golden_path = tree_path.with_extension("md");
+        if update {
+            fs::write(&golden_path, &markdown).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&golden_path)
+            .unwrap_or_else(|_| panic!("missing {:?}; run with FOX_UPDATE_GOLDEN=1", golden_path));
+        if markdown != expected {
+            failures.push(format!(
+                "{:?}\n--- expected\n{}\n--- actual\n{}",
+                golden_path, expected, markdown
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "markdown differs from golden files (FOX_UPDATE_GOLDEN=1 to accept):\n\n{}",
+        failures.join("\n\n")
+    );
+}
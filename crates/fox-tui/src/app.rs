@@ -1,17 +1,29 @@
 //! Application state management
 
+use crate::clipboard::{self, ClipboardProvider};
 use crate::config::Config;
-use crate::history::History;
-use crate::tabs::TabManager;
-use crate::vim::{Command, VimMode, VimState};
+use crate::history::{ExportFormat, History};
+use crate::jumplist::{Jump, JumpList};
+use crate::prefetch::Prefetcher;
+use crate::tabs::{TabManager, ViewMode};
+use crate::vim::{Command, VimMode, VimState, YankFormat, COMMAND_NAMES};
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use fox_core::fetch::Fetcher;
-use fox_core::{FetchConfig, Link};
+use fox_core::{
+    links, markdown, BrowserStatus, ExtractionMethod, FetchConfig, FetchProgress, Link, Page,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::debug;
 
+/// Lines scrolled per mouse-wheel notch
+const MOUSE_SCROLL_LINES: isize = 3;
+
 /// Main application state
 pub struct App {
     /// Tab manager
@@ -26,18 +38,33 @@ pub struct App {
     pub status: Option<String>,
     /// Whether loading is in progress
     pub loading: bool,
+    /// Latest stage reported by the page load in progress
+    pub load_progress: Option<FetchProgress>,
+    /// Page load running in the background, if any
+    navigation: Option<(PendingNavigation, JoinHandle<fox_core::Result<Page>>)>,
+    /// Incremented for each page load; a finished load whose generation
+    /// no longer matches has been superseded or canceled
+    navigation_generation: u64,
+    /// Progress reported by the page load in progress
+    navigation_progress: Option<UnboundedReceiver<FetchProgress>>,
     /// Configuration
     pub config: Config,
     /// HTTP/Browser fetcher
-    fetcher: Arc<Mutex<Fetcher>>,
+    fetcher: Arc<RwLock<Fetcher>>,
+    /// Background fetches of visible links
+    prefetcher: Prefetcher,
+    /// Page URL and scroll offset the last prefetch was started for
+    prefetch_view: Option<(Option<String>, usize)>,
     /// Pending key for multi-key commands
     pending_key: Option<char>,
     /// Link hints for hint mode (multi-letter hints like qutebrowser)
     pub link_hints: Vec<(String, Link)>,
     /// Current hint input buffer for multi-letter hints
     pub hint_input: String,
-    /// Search results positions
-    pub search_results: Vec<usize>,
+    /// What happens when a hint is selected
+    pub hint_action: HintAction,
+    /// Search results as (tab index, byte position) pairs
+    pub search_results: Vec<(usize, usize)>,
     /// Current search result index
     pub search_index: usize,
     /// Last search query
@@ -46,9 +73,50 @@ pub struct App {
     pub url_suggestions: Vec<UrlSuggestion>,
     /// Currently selected suggestion index
     pub suggestion_index: usize,
+    /// Clipboard used for yank and paste
+    pub clipboard: Box<dyn ClipboardProvider>,
+    /// Scroll offsets saved with `m<letter>`
+    pub marks: HashMap<char, usize>,
+    /// Positions left by navigations and scroll jumps, for `Ctrl-o`/`Ctrl-i`
+    pub jumps: JumpList,
+    /// Opens a URL outside the terminal, for `:gui`
+    pub opener: Opener,
+}
+
+/// What to do with a page once its background load finishes
+#[derive(Debug, Clone)]
+struct PendingNavigation {
+    /// Value of `App::navigation_generation` when the load started
+    generation: u64,
+    url: String,
+    add_to_history: bool,
+    /// Scroll offset to restore once loaded, for jumplist moves
+    scroll_offset: Option<usize>,
+}
+
+/// Opens `url` with the given program, or the system default browser
+pub type Opener = Box<dyn FnMut(&str, Option<&str>) -> std::io::Result<()> + Send>;
+
+/// Open `url` in `program`, or the system default browser when unset
+fn open_in_browser(url: &str, program: Option<&str>) -> std::io::Result<()> {
+    match program {
+        Some(program) => open::with_detached(url, program),
+        None => open::that_detached(url),
+    }
+}
+
+/// Action performed on the link picked in hint mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HintAction {
+    /// Navigate to the link
+    #[default]
+    Follow,
+    /// Download the link target to the downloads directory
+    Download,
 }
 
-/// A URL suggestion from history
+/// A command-mode completion: a URL from history, or a command name with
+/// its description as the title
 #[derive(Clone, Debug)]
 pub struct UrlSuggestion {
     pub url: String,
@@ -61,32 +129,113 @@ impl App {
     pub async fn new() -> Result<Self> {
         let config = Config::load()?;
 
-        let fetch_config = FetchConfig {
-            javascript: config.javascript,
-            ..Default::default()
-        };
-
         let chrome_config = config.to_chrome_config();
-        let fetcher = Fetcher::with_config_and_chrome(fetch_config, chrome_config).await?;
+        let fetcher = Fetcher::with_config_and_chrome(fetch_config(&config), chrome_config).await?;
+
+        Ok(Self::from_parts(config, History::load()?, fetcher))
+    }
+
+    /// Assemble an application from already-constructed parts
+    pub fn from_parts(config: Config, mut history: History, fetcher: Fetcher) -> Self {
+        history.set_ignore_hosts(config.history.ignore_hosts.clone());
+        history.set_retention(config.history.max_entries, config.history.max_age_days);
+        let mut tabs = TabManager::new();
+        tabs.set_max_width(config.display.max_width);
+        tabs.set_view_mode(view_mode(&config));
+        let clipboard = clipboard::provider(config.general.clipboard);
+        let status = match fetcher.browser_status() {
+            BrowserStatus::Unavailable(reason) => {
+                format!("JavaScript unavailable, using HTTP only: {}", reason)
+            }
+            _ => "Welcome to Fox! Press : to enter commands, or :o <url> to navigate".to_string(),
+        };
 
-        Ok(Self {
-            tabs: TabManager::new(),
-            history: History::load()?,
+        Self {
+            tabs,
+            history,
             vim: VimState::new(),
             input: String::new(),
-            status: Some("Welcome to Fox! Press : to enter commands, or :o <url> to navigate".to_string()),
+            status: Some(status),
             loading: false,
+            navigation: None,
+            navigation_generation: 0,
+            navigation_progress: None,
+            load_progress: None,
             config,
-            fetcher: Arc::new(Mutex::new(fetcher)),
+            fetcher: Arc::new(RwLock::new(fetcher)),
+            prefetcher: Prefetcher::new(),
+            prefetch_view: None,
             pending_key: None,
             link_hints: Vec::new(),
             hint_input: String::new(),
+            hint_action: HintAction::Follow,
             search_results: Vec::new(),
             search_index: 0,
             last_search: String::new(),
             url_suggestions: Vec::new(),
             suggestion_index: 0,
-        })
+            clipboard,
+            marks: HashMap::new(),
+            jumps: JumpList::new(),
+            opener: Box::new(open_in_browser),
+        }
+    }
+
+    /// Reload the configuration file and apply it to the running app
+    pub async fn reload_config(&mut self) -> Result<()> {
+        let config = Config::load()?;
+        self.apply_config(config).await;
+        Ok(())
+    }
+
+    /// Apply a new configuration, warning about settings that need a restart
+    pub async fn apply_config(&mut self, config: Config) {
+        let mut restart = Vec::new();
+        if config.browser.mode != self.config.browser.mode {
+            restart.push("browser");
+        }
+        if config.browser.chrome_path != self.config.browser.chrome_path {
+            restart.push("chrome_path");
+        }
+        if config.browser.user_data_dir != self.config.browser.user_data_dir {
+            restart.push("user_data_dir");
+        }
+
+        let method = config
+            .browser
+            .extraction_method
+            .parse()
+            .unwrap_or(ExtractionMethod::Accessibility);
+        {
+            let mut fetcher = self.fetcher.write().await;
+            if config.javascript && !fetcher.has_javascript() {
+                restart.push("javascript");
+            }
+            fetcher.set_config(fetch_config(&config));
+            fetcher.set_extraction_method(method);
+        }
+
+        self.tabs.set_max_width(config.display.max_width);
+        self.tabs.set_view_mode(view_mode(&config));
+        self.history.set_ignore_hosts(config.history.ignore_hosts.clone());
+        self.history
+            .set_retention(config.history.max_entries, config.history.max_age_days);
+        if config.general.clipboard != self.config.general.clipboard {
+            self.clipboard = clipboard::provider(config.general.clipboard);
+        }
+        self.config = config;
+
+        self.status = Some(if restart.is_empty() {
+            "Config reloaded".to_string()
+        } else {
+            format!("Config reloaded; restart to apply: {}", restart.join(", "))
+        });
+    }
+
+    /// Switch the extraction method used for subsequent navigations
+    pub async fn set_extraction_method(&mut self, method: ExtractionMethod) {
+        self.fetcher.write().await.set_extraction_method(method);
+        self.status = Some(format!("Extraction method: {}", method));
     }
 
     /// Navigate to a URL in the current tab
@@ -101,6 +250,31 @@ impl App {
 
     /// Internal navigation with optional history tracking
     async fn navigate_internal(&mut self, url: &str, add_to_history: bool) -> Result<()> {
+        // Contact links can't be fetched; hand them to the clipboard instead
+        if self.handle_contact_link(url) {
+            return Ok(());
+        }
+
+        // Fragment links into the current page scroll instead of re-fetching
+        if self.scroll_to_fragment(url) {
+            return Ok(());
+        }
+
+        self.start_navigation(url, add_to_history, None);
+        Ok(())
+    }
+
+    /// Start loading `url` into the current tab in the background
+    ///
+    /// The page is applied by [`App::tick`] once fetched. A load already in
+    /// progress is canceled, so a slow page can't replace a newer one.
+    fn start_navigation(&mut self, url: &str, add_to_history: bool, scroll_offset: Option<usize>) {
+        if let Some((_, task)) = self.navigation.take() {
+            task.abort();
+        }
+        self.navigation_progress = None;
+        self.load_progress = None;
+        self.navigation_generation += 1;
         self.loading = true;
         self.status = Some(format!("Loading {}...", url));
 
@@ -111,24 +285,205 @@ impl App {
             url.to_string()
         };
 
-        let fetcher = self.fetcher.lock().await;
-        match fetcher.fetch(&url).await {
+        let pending = PendingNavigation {
+            generation: self.navigation_generation,
+            url: url.clone(),
+            add_to_history,
+            scroll_offset,
+        };
+        if let Some(page) = self.prefetcher.take(&url) {
+            self.apply_navigation(pending, Ok(page));
+            return;
+        }
+        let fetcher = Arc::clone(&self.fetcher);
+        let (progress, updates) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            let report = move |update| {
+                let _ = progress.send(update);
+            };
+            fetcher.read().await.fetch_with_progress(&url, report).await
+        });
+        self.navigation = Some((pending, task));
+        self.navigation_progress = Some(updates);
+    }
+
+    /// Show the result of a finished page load, unless a newer load or a
+    /// cancel has superseded it
+    fn apply_navigation(&mut self, pending: PendingNavigation, result: fox_core::Result<Page>) {
+        if pending.generation != self.navigation_generation {
+            debug!("Discarding superseded load of {}", pending.url);
+            return;
+        }
+        self.loading = false;
+
+        match result {
             Ok(page) => {
-                if add_to_history {
-                    self.history.add(&url, page.title.as_deref());
+                if pending.add_to_history {
+                    let history_url = if self.config.strip_tracking_params {
+                        links::strip_tracking_params(&pending.url)
+                    } else {
+                        pending.url.clone()
+                    };
+                    self.history.add(&history_url, page.title.as_deref());
+                    self.record_jump();
+                }
+                let tab = self.tabs.current_mut();
+                tab.load_page(page);
+                if let Some(offset) = pending.scroll_offset {
+                    tab.set_scroll_offset(offset);
                 }
-                self.tabs.current_mut().load_page(page);
                 self.status = None;
             }
             Err(e) => {
                 self.status = Some(format!("Error: {}", e));
             }
         }
+    }
 
-        self.loading = false;
+    /// Wait for the page load in progress, if any, and show it
+    pub async fn finish_navigation(&mut self) {
+        self.navigation_progress = None;
+        self.load_progress = None;
+        if let Some((pending, task)) = self.navigation.take() {
+            match task.await {
+                Ok(result) => self.apply_navigation(pending, result),
+                Err(e) => debug!("Load of {} did not finish: {}", pending.url, e),
+            }
+        }
+    }
+
+    /// Open each URL in its own tab, loading them one at a time, and
+    /// return to the first tab
+    pub async fn open_tabs(&mut self, urls: &[String]) -> Result<()> {
+        for (i, url) in urls.iter().enumerate() {
+            if i > 0 {
+                self.tabs.new_tab();
+            }
+            self.navigate(url).await?;
+            self.finish_navigation().await;
+        }
+        self.tabs.go_to_tab(0);
         Ok(())
     }
 
+    /// Abandon the page load in progress, if any
+    pub fn cancel_navigation(&mut self) {
+        self.navigation_progress = None;
+        self.load_progress = None;
+        if let Some((pending, task)) = self.navigation.take() {
+            task.abort();
+            self.navigation_generation += 1;
+            self.loading = false;
+            self.status = Some(format!("Canceled loading {}", pending.url));
+        }
+    }
+
+    /// Handle links to a `#fragment` of the current page
+    ///
+    /// Scrolls to the heading whose slug matches the fragment. Returns true
+    /// if the URL pointed into the current page, whether or not a matching
+    /// heading was found.
+    fn scroll_to_fragment(&mut self, url: &str) -> bool {
+        let tab = self.tabs.current_mut();
+        let fragment = match tab.page_url() {
+            Some(base) => match base.join(url) {
+                Ok(target) if target.fragment().is_some() => {
+                    let mut target_page = target.clone();
+                    target_page.set_fragment(None);
+                    let mut current_page = base.clone();
+                    current_page.set_fragment(None);
+                    if target_page != current_page {
+                        return false;
+                    }
+                    target.fragment().unwrap_or("").to_string()
+                }
+                _ => return false,
+            },
+            None => return false,
+        };
+
+        let slug = markdown::slugify(&fragment);
+        let heading = tab
+            .content()
+            .map(markdown::extract_toc)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|entry| markdown::slugify(&entry.text) == slug);
+        match heading {
+            Some(entry) => {
+                self.record_jump();
+                self.tabs.current_mut().scroll_to_markdown_line(entry.line);
+                self.status = None;
+            }
+            None => self.status = Some(format!("No heading for #{}", fragment)),
+        }
+        true
+    }
+
+    /// Remember the current position in the jumplist before jumping away
+    fn record_jump(&mut self) {
+        if let Some(jump) = self.current_jump() {
+            self.jumps.record(jump);
+        }
+    }
+
+    /// The current tab's URL and scroll offset
+    fn current_jump(&self) -> Option<Jump> {
+        let tab = self.tabs.current();
+        tab.url().map(|url| Jump {
+            url,
+            scroll_offset: tab.scroll_offset(),
+        })
+    }
+
+    /// Go back (`Ctrl-o`) or forward (`Ctrl-i`) through the jumplist
+    fn walk_jumplist(&mut self, back: bool) {
+        let jump = if back {
+            match self.current_jump() {
+                Some(current) => self.jumps.back(current),
+                None => None,
+            }
+        } else {
+            self.jumps.forward()
+        };
+        let jump = match jump {
+            Some(jump) => jump,
+            None => return,
+        };
+
+        if self.tabs.current().url().as_deref() == Some(jump.url.as_str()) {
+            self.tabs.current_mut().set_scroll_offset(jump.scroll_offset);
+        } else {
+            self.start_navigation(&jump.url, false, Some(jump.scroll_offset));
+        }
+    }
+
+    /// Handle `mailto:` and `tel:` links by copying the address
+    ///
+    /// Returns true if the URL was a contact link and has been handled.
+    fn handle_contact_link(&mut self, url: &str) -> bool {
+        let lower = url.to_lowercase();
+        let (label, address) = if lower.starts_with("mailto:") {
+            ("Email", &url["mailto:".len()..])
+        } else if lower.starts_with("tel:") {
+            ("Phone", &url["tel:".len()..])
+        } else {
+            return false;
+        };
+
+        // Drop mailto parameters like ?subject=...
+        let address = address.split('?').next().unwrap_or(address).to_string();
+
+        let copied = self.clipboard.set(&address);
+
+        self.status = Some(if copied {
+            format!("{}: {} (copied to clipboard)", label, address)
+        } else {
+            format!("{}: {}", label, address)
+        });
+        true
+    }
+
     /// Navigate to a URL in a new tab
     pub async fn navigate_new_tab(&mut self, url: &str) -> Result<()> {
         self.tabs.new_tab();
@@ -146,19 +501,55 @@ impl App {
         }
     }
 
+    /// Handle a mouse event, scrolling the current tab on wheel movement
+    ///
+    /// Ignored while typing a command or search, and in hint mode where the
+    /// hints are tied to the links currently on screen.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if matches!(
+            self.vim.mode,
+            VimMode::Command | VimMode::Search | VimMode::Hint
+        ) {
+            return;
+        }
+        let tab = self.tabs.current_mut();
+        match mouse_scroll_delta(mouse.kind) {
+            Some(delta) if delta > 0 => tab.scroll_down(delta.unsigned_abs()),
+            Some(delta) => tab.scroll_up(delta.unsigned_abs()),
+            None => {}
+        }
+    }
+
     async fn handle_normal_key(&mut self, key: KeyEvent) -> Result<bool> {
         let tab = self.tabs.current_mut();
 
         // Handle multi-key commands
         if let Some(pending) = self.pending_key.take() {
             match (pending, key.code) {
-                ('g', KeyCode::Char('g')) => tab.scroll_to_top(),
+                ('g', KeyCode::Char('g')) => {
+                    self.record_jump();
+                    self.tabs.current_mut().scroll_to_top();
+                }
                 ('g', KeyCode::Char('t')) => self.tabs.next_tab(),
                 ('g', KeyCode::Char('T')) => self.tabs.prev_tab(),
                 ('g', KeyCode::Char(n)) if n.is_ascii_digit() => {
                     let idx = n.to_digit(10).unwrap_or(1) as usize;
                     self.tabs.go_to_tab(idx.saturating_sub(1));
                 }
+                (';', KeyCode::Char('d')) => self.enter_hint_mode(HintAction::Download),
+                ('g', KeyCode::Char('i')) => self.focus_first_input(),
+                ('g', KeyCode::Char('o')) => self.open_external(),
+                ('m', KeyCode::Char(mark)) if mark.is_ascii_alphabetic() => {
+                    self.marks.insert(mark, tab.scroll_offset());
+                    self.status = Some(format!("Mark set: {}", mark));
+                }
+                ('`', KeyCode::Char(mark)) => match self.marks.get(&mark).copied() {
+                    Some(offset) => {
+                        self.record_jump();
+                        self.tabs.current_mut().set_scroll_offset(offset);
+                    }
+                    None => self.status = Some(format!("Mark not set: {}", mark)),
+                },
                 _ => {}
             }
             return Ok(false);
@@ -166,30 +557,50 @@ impl App {
 
         match key.code {
             // Scrolling
-            KeyCode::Char('j') | KeyCode::Down => tab.scroll_down(1),
-            KeyCode::Char('k') | KeyCode::Up => tab.scroll_up(1),
+            KeyCode::Char('j') | KeyCode::Down => tab.scroll_down(self.config.display.scroll_lines.max(1)),
+            KeyCode::Char('k') | KeyCode::Up => tab.scroll_up(self.config.display.scroll_lines.max(1)),
             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                tab.scroll_down(tab.viewport_height / 2)
+                tab.scroll_by((tab.viewport_height / 2) as isize, self.config.display.smooth_scroll)
             }
             KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                tab.scroll_up(tab.viewport_height / 2)
+                tab.scroll_by(-((tab.viewport_height / 2) as isize), self.config.display.smooth_scroll)
             }
             KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                tab.scroll_down(tab.viewport_height)
+                tab.scroll_by(tab.viewport_height as isize, self.config.display.smooth_scroll)
             }
             KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                tab.scroll_up(tab.viewport_height)
+                tab.scroll_by(-(tab.viewport_height as isize), self.config.display.smooth_scroll)
+            }
+            KeyCode::Char('G') => {
+                self.record_jump();
+                self.tabs.current_mut().scroll_to_bottom();
             }
-            KeyCode::Char('G') => tab.scroll_to_bottom(),
             KeyCode::Char('g') => {
                 self.pending_key = Some('g');
             }
 
             // Link navigation
-            KeyCode::Char('h') => tab.prev_link(),
-            KeyCode::Char('l') => tab.next_link(),
+            KeyCode::Char('h') => {
+                tab.prev_link();
+                tab.reveal_selected_link(self.config.display.scroll_margin);
+            }
+            KeyCode::Char('l') => {
+                tab.next_link();
+                tab.reveal_selected_link(self.config.display.scroll_margin);
+            }
             KeyCode::Char('f') => {
-                self.enter_hint_mode();
+                self.enter_hint_mode(HintAction::Follow);
+            }
+            KeyCode::Char(';') => {
+                self.pending_key = Some(';');
+            }
+
+            // Marks
+            KeyCode::Char('m') => {
+                self.pending_key = Some('m');
+            }
+            KeyCode::Char('`') => {
+                self.pending_key = Some('`');
             }
             KeyCode::Enter => {
                 if let Some(link) = tab.selected_link() {
@@ -198,6 +609,17 @@ impl App {
                 }
             }
 
+            // Jumplist; terminals send Ctrl-i as Tab
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.walk_jumplist(true);
+            }
+            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.walk_jumplist(false);
+            }
+            KeyCode::Tab => {
+                self.walk_jumplist(false);
+            }
+
             // History
             KeyCode::Char('H') => {
                 if let Some(url) = self.history.back() {
@@ -219,6 +641,10 @@ impl App {
                 }
             }
 
+            KeyCode::Esc if self.loading => {
+                self.cancel_navigation();
+            }
+
             // Mode switching
             KeyCode::Char(':') => {
                 self.vim.mode = VimMode::Command;
@@ -246,6 +672,9 @@ impl App {
                     self.yank_to_clipboard(&url);
                 }
             }
+            KeyCode::Char('Y') => {
+                self.copy_page_content(YankFormat::Markdown);
+            }
             KeyCode::Char('p') => {
                 if let Some(url) = self.paste_from_clipboard() {
                     self.navigate(&url).await?;
@@ -291,6 +720,11 @@ impl App {
                         self.input = format!("{} {}", prefix, suggestion.url);
                         self.url_suggestions.clear();
                         self.suggestion_index = 0;
+                    } else if !self.input.contains(' ') {
+                        // Completing the command name itself
+                        self.input = suggestion.url.clone();
+                        self.url_suggestions.clear();
+                        self.suggestion_index = 0;
                     }
                 }
             }
@@ -312,6 +746,12 @@ impl App {
                 self.input.pop();
                 self.update_url_suggestions();
             }
+            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(text) = self.paste_from_clipboard() {
+                    self.input.push_str(&text);
+                    self.update_url_suggestions();
+                }
+            }
             KeyCode::Char(c) => {
                 self.input.push(c);
                 self.update_url_suggestions();
@@ -338,6 +778,12 @@ impl App {
         self.url_suggestions.clear();
         self.suggestion_index = 0;
 
+        // Until the command name is finished, suggest command names
+        if !self.input.is_empty() && !self.input.contains(' ') {
+            self.update_command_suggestions();
+            return;
+        }
+
         // Only show suggestions for open/tabopen commands
         let input = self.input.trim();
         let query = if input.starts_with("o ") {
@@ -394,6 +840,22 @@ impl App {
         }
     }
 
+    /// Suggest command names that fuzzy match the input
+    fn update_command_suggestions(&mut self) {
+        let mut matches: Vec<UrlSuggestion> = COMMAND_NAMES
+            .iter()
+            .filter_map(|(name, description)| {
+                fuzzy_match(name, &self.input).map(|score| UrlSuggestion {
+                    url: name.to_string(),
+                    title: Some(description.to_string()),
+                    score,
+                })
+            })
+            .collect();
+        matches.sort_by_key(|s| std::cmp::Reverse(s.score));
+        self.url_suggestions = matches;
+    }
+
     async fn handle_search_key(&mut self, key: KeyEvent) -> Result<bool> {
         match key.code {
             KeyCode::Esc => {
@@ -427,9 +889,13 @@ impl App {
             KeyCode::BackTab => {
                 self.tabs.current_mut().prev_form_field();
             }
-            _ => {
-                // In a full implementation, this would handle form input
+            KeyCode::Backspace => {
+                self.tabs.current_mut().form_input_mut().pop();
+            }
+            KeyCode::Char(c) => {
+                self.tabs.current_mut().form_input_mut().push(c);
             }
+            _ => {}
         }
         Ok(false)
     }
@@ -445,15 +911,31 @@ impl App {
                 self.hint_input.pop();
             }
             KeyCode::Char(c) => {
+                // Uppercase hints are only a display style; typing either case works
+                let c = if self.config.display.hint_uppercase {
+                    c.to_ascii_lowercase()
+                } else {
+                    c
+                };
                 self.hint_input.push(c);
 
                 // Check for exact match
                 if let Some((_, link)) = self.link_hints.iter().find(|(h, _)| h == &self.hint_input) {
                     let url = link.url.clone();
+                    if self.config.display.hint_all_links {
+                        // The link may be off screen; bring it into view first
+                        let tab = self.tabs.current_mut();
+                        if let Some(line) = tab.content().map(|content| link_line(content, link)) {
+                            tab.scroll_to_markdown_line(line);
+                        }
+                    }
                     self.vim.mode = VimMode::Normal;
                     self.link_hints.clear();
                     self.hint_input.clear();
-                    self.navigate(&url).await?;
+                    match self.hint_action {
+                        HintAction::Follow => self.navigate(&url).await?,
+                        HintAction::Download => self.download(&url).await,
+                    }
                 } else {
                     // Check if any hints start with current input (still valid prefix)
                     let has_match = self.link_hints.iter().any(|(h, _)| h.starts_with(&self.hint_input));
@@ -473,7 +955,17 @@ impl App {
         debug!("Executing command: {:?}", cmd);
 
         match cmd {
-            Command::Quit => return Ok(true),
+            Command::Quit => {
+                if self.tabs.len() <= 1 {
+                    return Ok(true);
+                }
+                self.tabs.close_current();
+            }
+            Command::QuitAll | Command::ForceQuit => return Ok(true),
+            Command::WriteQuit(path) => {
+                self.save_page(path.as_deref())?;
+                return Ok(true);
+            }
             Command::Open(url) => {
                 self.navigate(&url).await?;
             }
@@ -481,7 +973,13 @@ impl App {
                 self.navigate_new_tab(&url).await?;
             }
             Command::Write(path) => {
-                self.save_page(&path)?;
+                self.save_page(path.as_deref())?;
+            }
+            Command::Download(url) => {
+                self.download(&url).await;
+            }
+            Command::Toc(index) => {
+                self.show_toc(index);
             }
             Command::Tabs => {
                 self.status = Some(self.tabs.list_tabs());
@@ -489,8 +987,62 @@ impl App {
             Command::History => {
                 self.status = Some(self.history.list());
             }
+            Command::Jumps => {
+                self.status = Some(self.jumps.list());
+            }
+            Command::HistoryExport(path) => {
+                let path = std::path::Path::new(&path);
+                self.status = Some(match self.history.export(ExportFormat::from_path(path), path) {
+                    Ok(count) => format!("Exported {} URLs to {}", count, path.display()),
+                    Err(e) => format!("Error: {}", e),
+                });
+            }
+            Command::Private => {
+                let private = !self.history.is_private();
+                self.history.set_private(private);
+                self.status = Some(if private {
+                    "Private mode on: history is not recorded".to_string()
+                } else {
+                    "Private mode off".to_string()
+                });
+            }
             Command::Set(key, value) => {
-                self.config.set(&key, &value)?;
+                if let Err(e) = self.config.set(&key, &value) {
+                    self.status = Some(format!("Error: {}", e));
+                    return Ok(false);
+                }
+                if matches!(key.as_str(), "extraction_method" | "extraction") {
+                    let method = self
+                        .config
+                        .browser
+                        .extraction_method
+                        .parse()
+                        .unwrap_or(ExtractionMethod::Accessibility);
+                    self.set_extraction_method(method).await;
+                }
+                if matches!(key.as_str(), "default_mode" | "mode") {
+                    self.tabs.set_view_mode(view_mode(&self.config));
+                }
+                // Pick up fetch settings such as show_links and follow_canonical
+                self.fetcher.write().await.set_config(fetch_config(&self.config));
+            }
+            Command::PasteOpen => match self.paste_from_clipboard() {
+                Some(url) => self.navigate(&url).await?,
+                None => self.status = Some("Clipboard is empty".to_string()),
+            },
+            Command::Yank(format) => {
+                self.copy_page_content(format);
+            }
+            Command::Gui => {
+                self.open_external();
+            }
+            Command::SearchAll(query) => {
+                self.search_all(&query);
+            }
+            Command::Source => {
+                if let Err(e) = self.reload_config().await {
+                    self.status = Some(format!("Error: {}", e));
+                }
             }
             Command::Unknown(cmd) => {
                 self.status = Some(format!("Unknown command: {}", cmd));
@@ -501,65 +1053,80 @@ impl App {
         Ok(false)
     }
 
-    fn enter_hint_mode(&mut self) {
-        let tab = self.tabs.current();
-        if let Some(links) = tab.links() {
-            if let Some(content) = tab.content() {
-                let scroll_offset = tab.scroll_offset();
-                let viewport_height = tab.viewport_height;
-                let visible_start = scroll_offset;
-                let visible_end = scroll_offset + viewport_height;
-
-                // Calculate line number for each link and filter to visible ones
-                let mut visible_links: Vec<(usize, &Link)> = links
-                    .iter()
-                    .filter_map(|link| {
-                        // Find which line contains this link
-                        let line_num = content[..link.position.min(content.len())]
-                            .lines()
-                            .count()
-                            .saturating_sub(1);
-                        // Only include if in visible range
-                        if line_num >= visible_start && line_num < visible_end {
-                            Some((line_num, link))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-
-                // Sort by line number (top to bottom)
-                visible_links.sort_by_key(|(line, _)| *line);
+    fn enter_hint_mode(&mut self, action: HintAction) {
+        self.hint_action = action;
+        if self.tabs.current().links().is_some() {
+            let links = self.hint_links();
 
-                // Generate multi-letter hints for all visible links
-                let hints = generate_hints(visible_links.len());
-                self.link_hints = visible_links
-                    .into_iter()
-                    .zip(hints.into_iter())
-                    .map(|((_, link), hint)| (hint, link.clone()))
-                    .collect();
+            // Generate multi-letter hints for all hinted links
+            let hints = generate_hints(links.len(), &self.config.hint_chars());
+            self.link_hints = links.into_iter().zip(hints).map(|(link, hint)| (hint, link)).collect();
 
-                self.hint_input.clear();
-            }
+            self.hint_input.clear();
             self.vim.mode = VimMode::Hint;
         }
     }
 
+    /// Jump to the first form field on the page and enter insert mode
+    fn focus_first_input(&mut self) {
+        if self.tabs.current_mut().focus_first_form_field() {
+            self.vim.mode = VimMode::Insert;
+        } else {
+            self.status = Some("No input fields on this page".to_string());
+        }
+    }
+
+    /// Links to label in hint mode: every link on the page when
+    /// `hint_all_links` is set, otherwise only the ones on screen
+    fn hint_links(&self) -> Vec<Link> {
+        if self.config.display.hint_all_links {
+            self.tabs.current().links().map(<[Link]>::to_vec).unwrap_or_default()
+        } else {
+            self.visible_links()
+        }
+    }
+
+    /// Links on screen in the current tab, top to bottom
+    fn visible_links(&self) -> Vec<Link> {
+        let tab = self.tabs.current();
+        let (links, content) = match (tab.links(), tab.content()) {
+            (Some(links), Some(content)) => (links, content),
+            _ => return Vec::new(),
+        };
+
+        let visible_start = tab.scroll_offset();
+        let visible_end = visible_start + tab.viewport_height;
+
+        // Calculate line number for each link and filter to visible ones
+        let mut visible_links: Vec<(usize, &Link)> = links
+            .iter()
+            .filter_map(|link| {
+                let line_num = link_line(content, link);
+                // Only include if in visible range
+                if line_num >= visible_start && line_num < visible_end {
+                    Some((line_num, link))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Sort by line number (top to bottom)
+        visible_links.sort_by_key(|(line, _)| *line);
+        visible_links.into_iter().map(|(_, link)| link.clone()).collect()
+    }
+
     fn search(&mut self, query: &str) {
         self.last_search = query.to_string();
         self.search_results.clear();
         self.search_index = 0;
 
-        let tab = self.tabs.current();
-        if let Some(content) = tab.content() {
-            let query_lower = query.to_lowercase();
-            let content_lower = content.to_lowercase();
-
-            let mut pos = 0;
-            while let Some(idx) = content_lower[pos..].find(&query_lower) {
-                self.search_results.push(pos + idx);
-                pos += idx + 1;
-            }
+        let current = self.tabs.current_index();
+        if let Some(content) = self.tabs.current().content() {
+            self.search_results = find_matches(content, query)
+                .into_iter()
+                .map(|pos| (current, pos))
+                .collect();
 
             if !self.search_results.is_empty() {
                 self.status = Some(format!(
@@ -573,6 +1140,33 @@ impl App {
         }
     }
 
+    /// Search every open tab, so `n`/`N` cycle through matches across tabs
+    fn search_all(&mut self, query: &str) {
+        self.last_search = query.to_string();
+        self.search_results.clear();
+        self.search_index = 0;
+
+        let mut counts = Vec::new();
+        for (index, tab) in self.tabs.tabs().iter().enumerate() {
+            let matches = find_matches(tab.content().unwrap_or(""), query);
+            if !matches.is_empty() {
+                counts.push(format!("tab {}: {}", index + 1, matches.len()));
+            }
+            self.search_results.extend(matches.into_iter().map(|pos| (index, pos)));
+        }
+
+        if self.search_results.is_empty() {
+            self.status = Some(format!("Pattern not found in any tab: {}", query));
+            return;
+        }
+        self.status = Some(format!(
+            "Found {} matches ({})",
+            self.search_results.len(),
+            counts.join(", ")
+        ));
+        self.jump_to_search_result();
+    }
+
     fn next_search_result(&mut self) {
         if !self.search_results.is_empty() {
             self.search_index = (self.search_index + 1) % self.search_results.len();
@@ -592,46 +1186,190 @@ impl App {
     }
 
     fn jump_to_search_result(&mut self) {
-        if let Some(&pos) = self.search_results.get(self.search_index) {
+        if let Some(&(tab_index, pos)) = self.search_results.get(self.search_index) {
+            self.record_jump();
+            self.tabs.go_to_tab(tab_index);
             // Approximate line number from character position
             let tab = self.tabs.current_mut();
-            if let Some(content) = tab.content() {
-                let line = content[..pos].lines().count();
-                tab.scroll_to_line(line);
+            if let Some(before) = tab.content().and_then(|content| content.get(..pos)) {
+                let line = before.lines().count();
+                tab.scroll_to_markdown_line(line);
             }
         }
     }
 
-    fn save_page(&self, path: &str) -> Result<()> {
-        let tab = self.tabs.current();
-        if let Some(content) = tab.content() {
-            std::fs::write(path, content)?;
+    /// List the page's headings, or jump to the `index`th one (1-based)
+    fn show_toc(&mut self, index: Option<usize>) {
+        let tab = self.tabs.current_mut();
+        let toc = match tab.content() {
+            Some(content) => markdown::extract_toc(content),
+            None => Vec::new(),
+        };
+        if toc.is_empty() {
+            self.status = Some("No headings on this page".to_string());
+            return;
         }
+
+        match index {
+            Some(n) => match n.checked_sub(1).and_then(|i| toc.get(i)) {
+                Some(entry) => {
+                    self.record_jump();
+                    self.tabs.current_mut().scroll_to_markdown_line(entry.line);
+                    self.status = Some(entry.text.clone());
+                }
+                None => self.status = Some(format!("No heading {} (1-{})", n, toc.len())),
+            },
+            None => {
+                self.status = Some(
+                    toc.iter()
+                        .enumerate()
+                        .map(|(i, entry)| {
+                            let indent = "  ".repeat(entry.level.saturating_sub(1) as usize);
+                            format!("{}{}: {}", indent, i + 1, entry.text)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" | "),
+                );
+            }
+        }
+    }
+
+    /// Download a URL into the configured downloads directory
+    pub async fn download(&mut self, url: &str) {
+        let url = match self.tabs.current().url() {
+            Some(base) => url::Url::parse(&base)
+                .and_then(|b| b.join(url))
+                .map(|u| u.to_string())
+                .unwrap_or_else(|_| url.to_string()),
+            None => url.to_string(),
+        };
+        let dir = self.config.downloads_dir();
+
+        self.status = Some(format!("Downloading {}...", url));
+        let result = self.fetcher.read().await.download(&url, &dir).await;
+        self.status = Some(match result {
+            Ok(path) => format!("Downloaded to {}", path.display()),
+            Err(e) => format!("Error: {}", e),
+        });
+    }
+
+    /// Save the current page to `path` in the format its extension names,
+    /// or as markdown to a file in the working directory named after the
+    /// page when no path is given
+    fn save_page(&mut self, path: Option<&str>) -> Result<()> {
+        let tab = self.tabs.current();
+        let (page, markdown) = match (tab.page(), tab.markdown()) {
+            (Some(page), Some(markdown)) => (page, markdown),
+            _ => return Ok(()),
+        };
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => unused_path(Path::new(""), &self.save_name()),
+        };
+        let output = match SaveFormat::from_path(&path) {
+            SaveFormat::Markdown => markdown.to_string(),
+            SaveFormat::Plain => markdown::markdown_to_plain(markdown),
+            SaveFormat::Html => page.html.clone(),
+            SaveFormat::Json => serde_json::to_string_pretty(&page.to_json())?,
+        };
+        std::fs::write(&path, output)?;
+        self.status = Some(format!("Saved to {}", path.display()));
         Ok(())
     }
 
+    /// File name stem for saving the current page: its slugified title,
+    /// falling back to the host
+    fn save_name(&self) -> String {
+        let tab = self.tabs.current();
+        let host = tab.page_url().and_then(|url| url.host_str());
+        [tab.title(), host]
+            .into_iter()
+            .flatten()
+            .map(markdown::slugify_title)
+            .find(|slug| !slug.is_empty())
+            .unwrap_or_else(|| "page".to_string())
+    }
+
     fn yank_to_clipboard(&mut self, text: &str) {
-        match arboard::Clipboard::new() {
-            Ok(mut clipboard) => {
-                if clipboard.set_text(text).is_ok() {
-                    self.status = Some(format!("Yanked: {}", text));
-                }
+        self.status = Some(if self.clipboard.set(text) {
+            format!("Yanked: {}", text)
+        } else {
+            "Clipboard not available".to_string()
+        });
+    }
+
+    /// Copy the current tab's whole page to the clipboard
+    pub fn copy_page_content(&mut self, format: YankFormat) {
+        let markdown = match self.tabs.current().markdown() {
+            Some(markdown) => markdown.to_string(),
+            None => {
+                self.status = Some("No page to yank".to_string());
+                return;
             }
-            Err(_) => {
-                self.status = Some("Clipboard not available".to_string());
+        };
+        let text = match format {
+            YankFormat::Markdown => markdown,
+            YankFormat::Plain => markdown::markdown_to_plain(&markdown),
+        };
+
+        self.status = Some(if self.clipboard.set(&text) {
+            format!("Yanked {} characters", text.chars().count())
+        } else {
+            "Clipboard not available".to_string()
+        });
+    }
+
+    /// Open the current tab's URL in the external browser
+    pub fn open_external(&mut self) {
+        let url = match self.tabs.current().url() {
+            Some(url) => url,
+            None => {
+                self.status = Some("No page to open".to_string());
+                return;
             }
-        }
+        };
+        let program = self.config.general.external_browser.as_deref();
+        self.status = Some(match (self.opener)(&url, program) {
+            Ok(()) => format!("Opened in browser: {}", url),
+            Err(e) => format!("Error: failed to open {}: {}", url, e),
+        });
     }
 
+    /// Read the clipboard as a single trimmed line, if it has any text
     fn paste_from_clipboard(&mut self) -> Option<String> {
-        arboard::Clipboard::new()
-            .ok()
-            .and_then(|mut c| c.get_text().ok())
+        let text = clipboard::paste_text(&self.clipboard.get()?);
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
     }
 
     /// Process any pending async operations
     pub async fn tick(&mut self) -> Result<()> {
-        // Future: handle pending fetches, websockets, etc.
+        self.tabs.current_mut().step_scroll();
+
+        // Show how far a background page load has got, then the page once
+        // it finishes
+        if let Some(updates) = self.navigation_progress.as_mut() {
+            while let Ok(update) = updates.try_recv() {
+                self.load_progress = Some(update);
+            }
+        }
+        if self.navigation.as_ref().is_some_and(|(_, task)| task.is_finished()) {
+            self.finish_navigation().await;
+        }
+
+        // Prefetch links on screen; stale prefetches are canceled on scroll
+        if self.config.prefetch && !self.loading {
+            let tab = self.tabs.current();
+            let view = (tab.url(), tab.scroll_offset());
+            if self.prefetch_view.as_ref() != Some(&view) {
+                let visible = self.visible_links();
+                self.prefetcher.update(&visible, &self.fetcher);
+                self.prefetch_view = Some(view);
+            }
+        }
         Ok(())
     }
 
@@ -641,52 +1379,149 @@ impl App {
     }
 }
 
+/// Lines to scroll for a mouse event (positive is down), if it is a wheel event
+fn mouse_scroll_delta(kind: MouseEventKind) -> Option<isize> {
+    match kind {
+        MouseEventKind::ScrollDown => Some(MOUSE_SCROLL_LINES),
+        MouseEventKind::ScrollUp => Some(-MOUSE_SCROLL_LINES),
+        _ => None,
+    }
+}
+
+/// File formats `:w` can save a page in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveFormat {
+    /// The page's markdown as shown
+    Markdown,
+    /// The markdown with its syntax removed
+    Plain,
+    /// The page's HTML as fetched
+    Html,
+    /// The page as JSON, as `fox fetch --format json` prints it
+    Json,
+}
+
+impl SaveFormat {
+    /// Format for a file name: plain text for `.txt`, HTML for `.html` or
+    /// `.htm`, JSON for `.json`, and otherwise markdown
+    fn from_path(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("txt") => SaveFormat::Plain,
+            Some("html") | Some("htm") => SaveFormat::Html,
+            Some("json") => SaveFormat::Json,
+            _ => SaveFormat::Markdown,
+        }
+    }
+}
+
+/// `<stem>.md` in `dir`, or `<stem>-2.md`, `<stem>-3.md`, ... if that
+/// file already exists
+fn unused_path(dir: &Path, stem: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.md", stem));
+    let mut counter = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.md", stem, counter));
+        counter += 1;
+    }
+    path
+}
+
+/// View mode selected by `general.default_mode`
+fn view_mode(config: &Config) -> ViewMode {
+    config.general.default_mode.parse().unwrap_or_default()
+}
+
+/// Build the fetcher configuration from user settings
+fn fetch_config(config: &Config) -> FetchConfig {
+    FetchConfig {
+        javascript: config.javascript,
+        strip_tracking_params: config.strip_tracking_params,
+        link_style: config.display.show_links.parse().unwrap_or_default(),
+        clean_titles: config.clean_titles,
+        sanitize_text: config.sanitize_text,
+        site_rules: config.site_rules(),
+        follow_canonical: config.follow_canonical,
+        follow_iframes: config.follow_iframes,
+        emulate_print: config.emulate_print,
+        blocklist: config.blocklist(),
+        strip_selectors: config.strip_selectors.clone(),
+        requests_per_second_per_host: config.requests_per_second_per_host,
+        ..Default::default()
+    }
+}
+
+/// Line of `content` that contains `link`
+///
+/// `Link::position` is a byte offset, and may not fall on a character
+/// boundary once the text has been post-processed, so newlines are counted
+/// on the raw bytes rather than by slicing the string.
+pub(crate) fn link_line(content: &str, link: &Link) -> usize {
+    let end = link.position.min(content.len());
+    content.as_bytes()[..end].iter().filter(|&&b| b == b'\n').count()
+}
+
+/// Byte offsets of case-insensitive matches of `query` in `content`
+fn find_matches(content: &str, query: &str) -> Vec<usize> {
+    let query_lower = query.to_lowercase();
+    if query_lower.is_empty() {
+        return Vec::new();
+    }
+
+    // Lowercasing can change a character's byte length, so remember which
+    // byte of `content` each byte of the lowered copy came from
+    let mut content_lower = String::with_capacity(content.len());
+    let mut origin = Vec::with_capacity(content.len());
+    for (offset, c) in content.char_indices() {
+        content_lower.extend(c.to_lowercase());
+        origin.resize(content_lower.len(), offset);
+    }
+
+    let mut matches = Vec::new();
+    let mut pos = 0;
+    while let Some(idx) = content_lower[pos..].find(&query_lower) {
+        let start = pos + idx;
+        matches.push(origin[start]);
+        pos = start + content_lower[start..].chars().next().map_or(1, char::len_utf8);
+    }
+    matches.dedup();
+    matches
+}
+
 /// Generate multi-letter hints for a given count of links
-/// Uses home-row keys for easier typing, similar to qutebrowser
-fn generate_hints(count: usize) -> Vec<String> {
-    if count == 0 {
+///
+/// Hints are drawn from `chars` (home-row keys by default, similar to
+/// qutebrowser) and all share the shortest length that covers `count`, so
+/// no hint is a prefix of another.
+fn generate_hints(count: usize, chars: &[char]) -> Vec<String> {
+    if count == 0 || chars.len() < 2 {
         return vec![];
     }
 
-    // Use home row keys first (easier to type), then other keys
-    const HINT_CHARS: &[char] = &[
-        'a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l', // Home row first
-        'q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', // Top row
-        'z', 'x', 'c', 'v', 'b', 'n', 'm', // Bottom row
-    ];
-
-    let base = HINT_CHARS.len();
+    let base = chars.len();
 
     // Calculate minimum hint length needed
-    let hint_len = if count <= base {
-        1
-    } else if count <= base * base {
-        2
-    } else {
-        3 // Should be enough for most pages (27^3 = ~19000 links)
-    };
-
-    let mut hints = Vec::with_capacity(count);
-
-    for i in 0..count {
-        let hint = match hint_len {
-            1 => HINT_CHARS[i].to_string(),
-            2 => {
-                let first = HINT_CHARS[i / base];
-                let second = HINT_CHARS[i % base];
-                format!("{}{}", first, second)
-            }
-            _ => {
-                let first = HINT_CHARS[i / (base * base)];
-                let second = HINT_CHARS[(i / base) % base];
-                let third = HINT_CHARS[i % base];
-                format!("{}{}{}", first, second, third)
-            }
-        };
-        hints.push(hint);
+    let mut hint_len = 1;
+    let mut capacity = base;
+    while capacity < count {
+        hint_len += 1;
+        capacity = capacity.saturating_mul(base);
     }
 
-    hints
+    (0..count)
+        .map(|i| {
+            let mut hint = vec![' '; hint_len];
+            let mut n = i;
+            for slot in hint.iter_mut().rev() {
+                *slot = chars[n % base];
+                n /= base;
+            }
+            hint.into_iter().collect()
+        })
+        .collect()
 }
 
 /// Simple fuzzy match function that returns a score if pattern matches text
@@ -745,3 +1580,622 @@ fn fuzzy_match(text: &str, pattern: &str) -> Option<i32> {
 
     Some(score)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(url: &str, text: &str, links: Vec<Link>) -> fox_core::Page {
+        fox_core::Page {
+            url: url::Url::parse(url).unwrap(),
+            title: None,
+            html: String::new(),
+            content: Some(fox_core::ExtractedContent {
+                text: text.to_string(),
+                title: None,
+                links,
+                author: None,
+                published: None,
+                description: None,
+                structured_data: Vec::new(),
+                plain_text: None,
+                content_html: None,
+                lang: None,
+            }),
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        }
+    }
+
+    async fn test_app() -> App {
+        let fetch_config = FetchConfig {
+            javascript: false,
+            ..Default::default()
+        };
+        let fetcher = Fetcher::with_config(fetch_config).await.unwrap();
+        App::from_parts(Config::default(), History::new(), fetcher)
+    }
+
+    #[test]
+    fn test_generate_hints_custom_alphabet() {
+        let chars: Vec<char> = "aoeu".chars().collect();
+        let hints = generate_hints(4, &chars);
+        assert_eq!(hints, vec!["a", "o", "e", "u"]);
+
+        // Too few characters for one letter each: every hint gets longer
+        let hints = generate_hints(10, &chars);
+        assert_eq!(hints.len(), 10);
+        assert!(hints.iter().all(|h| h.len() == 2 && h.chars().all(|c| chars.contains(&c))));
+        let distinct: std::collections::HashSet<_> = hints.iter().collect();
+        assert_eq!(distinct.len(), 10);
+
+        let hints = generate_hints(9, &['x', 'y']);
+        assert!(hints.iter().all(|h| h.len() == 4));
+        assert_eq!(hints[0], "xxxx");
+        assert_eq!(hints[8], "yxxx");
+    }
+
+    #[tokio::test]
+    async fn test_set_extraction_method_updates_fetcher() {
+        let mut app = test_app().await;
+        assert_eq!(
+            app.fetcher.read().await.extraction_method(),
+            ExtractionMethod::Accessibility
+        );
+
+        app.set_extraction_method(ExtractionMethod::Readability).await;
+        assert_eq!(
+            app.fetcher.read().await.extraction_method(),
+            ExtractionMethod::Readability
+        );
+    }
+
+    /// Clipboard test double whose contents the test can inspect
+    #[derive(Clone, Default)]
+    struct FakeClipboard(std::sync::Arc<std::sync::Mutex<Option<String>>>);
+
+    impl FakeClipboard {
+        fn with_text(text: &str) -> Self {
+            let clipboard = Self::default();
+            *clipboard.0.lock().unwrap() = Some(text.to_string());
+            clipboard
+        }
+
+        fn contents(&self) -> Option<String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl ClipboardProvider for FakeClipboard {
+        fn get(&mut self) -> Option<String> {
+            self.contents()
+        }
+
+        fn set(&mut self, text: &str) -> bool {
+            *self.0.lock().unwrap() = Some(text.to_string());
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_yank_and_paste_use_clipboard_provider() {
+        let mut app = test_app().await;
+        let clipboard = FakeClipboard::default();
+        app.clipboard = Box::new(clipboard.clone());
+
+        app.yank_to_clipboard("https://example.com/");
+        assert_eq!(clipboard.contents().as_deref(), Some("https://example.com/"));
+        assert_eq!(app.status.as_deref(), Some("Yanked: https://example.com/"));
+        assert_eq!(app.paste_from_clipboard().as_deref(), Some("https://example.com/"));
+
+        app.clipboard = Box::new(clipboard::NoClipboard);
+        app.yank_to_clipboard("https://example.com/");
+        assert_eq!(app.status.as_deref(), Some("Clipboard not available"));
+        assert_eq!(app.paste_from_clipboard(), None);
+    }
+
+    #[tokio::test]
+    async fn test_save_name_from_title_or_host() {
+        let mut app = test_app().await;
+        assert_eq!(app.save_name(), "page");
+
+        let titled = |title: Option<&str>| fox_core::Page {
+            url: url::Url::parse("https://docs.example.com/book").unwrap(),
+            title: title.map(String::from),
+            html: String::new(),
+            content: None,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        };
+        app.tabs.current_mut().load_page(titled(Some("Rust: The Book (2nd ed.) | Docs")));
+        assert_eq!(app.save_name(), "rust-the-book-2nd-ed-docs");
+        app.tabs.current_mut().load_page(titled(Some("***")));
+        assert_eq!(app.save_name(), "docs-example-com");
+    }
+
+    #[tokio::test]
+    async fn test_write_format_from_extension() {
+        let mut app = test_app().await;
+        let html = "<html><body><h1>Notes</h1><p>Some <b>bold</b> text.</p></body></html>";
+        let mut notes = page("https://example.com/notes", "# Notes\n\nSome **bold** text.\n", Vec::new());
+        notes.title = Some("Notes".to_string());
+        notes.html = html.to_string();
+        notes.content.as_mut().unwrap().author = Some("Ada".to_string());
+        app.tabs.current_mut().load_page(notes);
+
+        let dir = std::env::temp_dir().join(format!("fox-write-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let save = |name: &str| dir.join(name);
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+
+        for name in ["page.md", "page.txt", "page.HTML", "page.json"] {
+            let command = format!("w {}", save(name).display());
+            app.execute_command(&command).await.unwrap();
+            assert_eq!(app.status, Some(format!("Saved to {}", save(name).display())));
+        }
+        assert_eq!(read("page.md"), "# Notes\n\nSome **bold** text.\n");
+        assert!(read("page.txt").contains("Some bold text."));
+        assert!(!read("page.txt").contains(['#', '*']));
+        assert_eq!(read("page.HTML"), html);
+
+        let json: serde_json::Value = serde_json::from_str(&read("page.json")).unwrap();
+        assert_eq!(json["url"], "https://example.com/notes");
+        assert_eq!(json["title"], "Notes");
+        assert_eq!(json["author"], "Ada");
+        assert_eq!(json["content"], "# Notes\n\nSome **bold** text.\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unused_path_appends_counter() {
+        let dir = std::env::temp_dir().join(format!("fox-save-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(unused_path(&dir, "notes"), dir.join("notes.md"));
+
+        std::fs::write(dir.join("notes.md"), "").unwrap();
+        std::fs::write(dir.join("notes-2.md"), "").unwrap();
+        assert_eq!(unused_path(&dir, "notes"), dir.join("notes-3.md"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_yank_command_copies_page() {
+        let mut app = test_app().await;
+        let clipboard = FakeClipboard::default();
+        app.clipboard = Box::new(clipboard.clone());
+
+        app.execute_command("yank").await.unwrap();
+        assert_eq!(app.status.as_deref(), Some("No page to yank"));
+        assert_eq!(clipboard.contents(), None);
+
+        let text = "# Título\n\nSee [docs](https://example.com/docs).\n".to_string();
+        app.tabs.current_mut().load_page(page("https://example.com", &text, Vec::new()));
+
+        app.execute_command("yank").await.unwrap();
+        assert_eq!(clipboard.contents().as_deref(), Some(text.as_str()));
+        assert_eq!(
+            app.status,
+            Some(format!("Yanked {} characters", text.chars().count()))
+        );
+
+        app.execute_command("yank plain").await.unwrap();
+        let plain = clipboard.contents().unwrap();
+        assert!(plain.contains("Título"));
+        assert!(plain.contains("See docs."));
+        assert!(!plain.contains("https://"));
+    }
+
+    #[tokio::test]
+    async fn test_gui_opens_current_url() {
+        let mut app = test_app().await;
+        let opened = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let calls = opened.clone();
+        app.opener = Box::new(move |url, program| {
+            calls
+                .lock()
+                .unwrap()
+                .push((url.to_string(), program.map(str::to_string)));
+            Ok(())
+        });
+
+        app.execute_command("gui").await.unwrap();
+        assert_eq!(app.status.as_deref(), Some("No page to open"));
+        assert!(opened.lock().unwrap().is_empty());
+
+        app.tabs.current_mut().load_page(page("https://example.com/article", "", Vec::new()));
+        app.execute_command("gui").await.unwrap();
+        app.config.general.external_browser = Some("firefox".to_string());
+        app.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE)).await.unwrap();
+        app.handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE)).await.unwrap();
+
+        assert_eq!(
+            *opened.lock().unwrap(),
+            vec![
+                ("https://example.com/article".to_string(), None),
+                ("https://example.com/article".to_string(), Some("firefox".to_string())),
+            ]
+        );
+        assert_eq!(
+            app.status.as_deref(),
+            Some("Opened in browser: https://example.com/article")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_all_cycles_across_tabs() {
+        let mut app = test_app().await;
+        app.tabs.current_mut().load_page(page("https://example.com", "fox one\nfox two\n", Vec::new()));
+        app.tabs.new_tab();
+        app.tabs.current_mut().load_page(page("https://example.com", "no match\n", Vec::new()));
+        app.tabs.new_tab();
+        app.tabs.current_mut().load_page(page("https://example.com", "the Fox three\n", Vec::new()));
+
+        app.execute_command("search-all fox").await.unwrap();
+        assert_eq!(app.search_results, vec![(0, 0), (0, 8), (2, 4)]);
+        assert_eq!(app.status.as_deref(), Some("Found 3 matches (tab 1: 2, tab 3: 1)"));
+        assert_eq!(app.tabs.current_index(), 0);
+
+        let mut visited = Vec::new();
+        for _ in 0..3 {
+            app.next_search_result();
+            visited.push(app.tabs.current_index());
+        }
+        assert_eq!(visited, vec![0, 2, 0]);
+
+        app.prev_search_result();
+        assert_eq!(app.tabs.current_index(), 2);
+
+        // Multi-byte matches land on character boundaries of the original text
+        app.tabs.current_mut().load_page(page("https://example.com", "café CAFÉ\n", Vec::new()));
+        app.execute_command("search-all é").await.unwrap();
+        assert_eq!(app.search_results, vec![(2, 3), (2, 9)]);
+    }
+
+    #[test]
+    fn test_find_matches_non_ascii() {
+        assert_eq!(find_matches("café café", "é"), vec![3, 9]);
+        assert_eq!(find_matches("ééé", "éé"), vec![0, 2]);
+        // "İ" lowercases to three bytes, so offsets after it shift
+        assert_eq!(find_matches("İx ix", "x"), vec![2, 5]);
+        assert!(find_matches("fox", "").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_v_pastes_into_command_input() {
+        let mut app = test_app().await;
+        app.clipboard = Box::new(FakeClipboard::with_text("  https://example.com/page\n"));
+        app.vim.mode = VimMode::Command;
+        app.input = "o ".to_string();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL))
+            .await
+            .unwrap();
+        assert_eq!(app.input, "o https://example.com/page");
+
+        app.clipboard = Box::new(FakeClipboard::default());
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL))
+            .await
+            .unwrap();
+        assert_eq!(app.input, "o https://example.com/page");
+    }
+
+    #[tokio::test]
+    async fn test_command_name_completion() {
+        let mut app = test_app().await;
+        app.vim.mode = VimMode::Command;
+
+        for c in "hi".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .await
+                .unwrap();
+        }
+        let names: Vec<_> = app.url_suggestions.iter().map(|s| s.url.as_str()).collect();
+        assert_eq!(names.first(), Some(&"history"));
+        assert!(names.contains(&"history-export"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(app.input, "history");
+        assert!(app.url_suggestions.is_empty());
+
+        // Once an argument starts, command names are no longer suggested
+        app.input = "set ".to_string();
+        app.handle_key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert!(app.url_suggestions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_superseded_navigation_is_discarded() {
+        let mut app = test_app().await;
+        app.navigate("http://127.0.0.1:9/slow").await.unwrap();
+        let stale = app.navigation.as_ref().unwrap().0.clone();
+        app.navigate("http://127.0.0.1:9/new").await.unwrap();
+        assert!(app.loading);
+
+        // The first load finishing late must not replace the newer one
+        app.apply_navigation(stale, Ok(page("http://127.0.0.1:9/slow", "", Vec::new())));
+        assert_eq!(app.tabs.current().url(), None);
+        assert!(app.loading);
+
+        let current = app.navigation.as_ref().unwrap().0.clone();
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert!(!app.loading);
+        assert!(app.navigation.is_none());
+        assert_eq!(app.status.as_deref(), Some("Canceled loading http://127.0.0.1:9/new"));
+
+        // Nor does a canceled load
+        app.apply_navigation(current, Ok(page("http://127.0.0.1:9/new", "", Vec::new())));
+        assert_eq!(app.tabs.current().url(), None);
+    }
+
+    #[tokio::test]
+    async fn test_tick_shows_conversion_progress() {
+        let mut app = test_app().await;
+
+        // Stand in for a page load that has reached the conversion stage
+        let (progress, updates) = mpsc::unbounded_channel();
+        app.navigation_progress = Some(updates);
+        progress.send(FetchProgress::Converting { nodes: 12000 }).unwrap();
+        app.tick().await.unwrap();
+        assert_eq!(app.load_progress, Some(FetchProgress::Converting { nodes: 12000 }));
+
+        // A new load drops the old load's updates
+        app.navigate("http://127.0.0.1:9/next").await.unwrap();
+        assert!(progress.send(FetchProgress::Converting { nodes: 1 }).is_err());
+        app.cancel_navigation();
+        assert!(app.navigation_progress.is_none());
+        assert_eq!(app.load_progress, None);
+    }
+
+    #[tokio::test]
+    async fn test_quit_closes_tab_before_quitting() {
+        let mut app = test_app().await;
+        app.tabs.new_tab();
+        assert!(!app.execute_command("q").await.unwrap());
+        assert_eq!(app.tabs.len(), 1);
+        assert!(app.execute_command("q").await.unwrap());
+
+        app.tabs.new_tab();
+        assert!(app.execute_command("qa").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_hints_after_multibyte_text() {
+        let mut app = test_app().await;
+        let text = "Café crème 🎉\n\nÜber naïve 🦊🦊\n\n[link](https://example.com/a)\n".to_string();
+        let position = text.find("[link]").unwrap();
+        let link = |position| Link {
+            text: "link".to_string(),
+            url: "https://example.com/a".to_string(),
+            title: None,
+            position,
+        };
+        assert_eq!(link_line(&text, &link(position)), 4);
+        // An offset inside the fox emoji must not panic
+        let inside_emoji = text.find('🦊').unwrap() + 1;
+        assert_eq!(link_line(&text, &link(inside_emoji)), 2);
+
+        let links = vec![link(position), link(inside_emoji)];
+        app.tabs.current_mut().load_page(page("https://example.com", &text, links));
+        app.enter_hint_mode(HintAction::Follow);
+        assert_eq!(app.link_hints.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hint_all_links() {
+        let mut app = test_app().await;
+        let text = "Paragraph\n\n".repeat(50) + "[far](https://example.com/far)\n";
+        let link = |url: &str, position| Link {
+            text: url.to_string(),
+            url: url.to_string(),
+            title: None,
+            position,
+        };
+        let links = vec![link("https://example.com/near", 0), link("https://example.com/far", text.len() - 5)];
+        app.tabs.current_mut().load_page(page("https://example.com", &text, links));
+
+        app.enter_hint_mode(HintAction::Follow);
+        assert_eq!(app.link_hints.len(), 1);
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)).await.unwrap();
+
+        app.config.display.hint_all_links = true;
+        app.enter_hint_mode(HintAction::Follow);
+        assert_eq!(app.link_hints.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_toc_jump_on_wrapped_page() {
+        let mut app = test_app().await;
+        let paragraph = "word ".repeat(30) + "\n\n";
+        let text = paragraph.repeat(20) + "## Usage\n\n" + &"More\n\n".repeat(50);
+        app.tabs.current_mut().load_page(page("https://example.com/docs", &text, Vec::new()));
+
+        // The heading is markdown line 40, but each paragraph wraps onto two
+        // lines, so it lands mid-screen only if the jump allows for that
+        app.execute_command("toc 1").await.unwrap();
+        let tab = app.tabs.current();
+        assert_eq!(tab.scroll_offset(), 60 - tab.viewport_height / 2);
+        assert_eq!(tab.visible_lines()[tab.viewport_height / 2], "Usage");
+        assert_eq!(app.status.as_deref(), Some("Usage"));
+    }
+
+    #[tokio::test]
+    async fn test_fragment_link_scrolls_to_heading() {
+        let mut app = test_app().await;
+        // Paragraphs wrap onto two lines each, pushing the heading from
+        // markdown line 100 down to rendered line 150
+        let paragraph = "word ".repeat(30) + "\n\n";
+        let text = paragraph.repeat(50) + "## Getting Started\n\n" + &"More\n\n".repeat(50);
+        app.tabs.current_mut().load_page(page("https://example.com/docs", &text, Vec::new()));
+        let middle = app.tabs.current().viewport_height / 2;
+        let expected = 150 - middle;
+
+        app.navigate("#getting-started").await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), expected);
+        assert_eq!(app.tabs.current().visible_lines()[middle], "Getting Started");
+        assert_eq!(app.status, None);
+
+        app.tabs.current_mut().scroll_to_top();
+        app.navigate("https://example.com/docs#getting-started").await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), expected);
+
+        app.navigate("#missing").await.unwrap();
+        assert_eq!(app.status.as_deref(), Some("No heading for #missing"));
+    }
+
+    #[tokio::test]
+    async fn test_scroll_down_honors_scroll_lines() {
+        let mut app = test_app().await;
+        app.tabs.current_mut().load_page(page("https://example.com", &"Paragraph\n\n".repeat(50), Vec::new()));
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        app.handle_key(press('j')).await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), 1);
+
+        app.config.display.scroll_lines = 3;
+        app.handle_key(press('j')).await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), 4);
+        app.handle_key(press('k')).await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_marks_restore_scroll_offset() {
+        let mut app = test_app().await;
+        app.tabs.current_mut().load_page(page("https://example.com", &"Paragraph\n\n".repeat(50), Vec::new()));
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        app.tabs.current_mut().scroll_down(12);
+        app.handle_key(press('m')).await.unwrap();
+        app.handle_key(press('a')).await.unwrap();
+        assert_eq!(app.marks.get(&'a'), Some(&12));
+
+        app.tabs.current_mut().scroll_to_top();
+        app.handle_key(press('`')).await.unwrap();
+        app.handle_key(press('a')).await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), 12);
+
+        app.handle_key(press('`')).await.unwrap();
+        app.handle_key(press('z')).await.unwrap();
+        assert_eq!(app.status.as_deref(), Some("Mark not set: z"));
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_o_returns_to_position_before_jump() {
+        let mut app = test_app().await;
+        app.tabs.current_mut().load_page(page("https://example.com", &"Paragraph\n\n".repeat(50), Vec::new()));
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        // Plain scrolling is not a jump
+        app.tabs.current_mut().scroll_down(12);
+        app.handle_key(press('G')).await.unwrap();
+        let bottom = app.tabs.current().scroll_offset();
+        assert!(bottom > 12);
+
+        app.handle_key(ctrl('o')).await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), 12);
+        app.handle_key(ctrl('o')).await.unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), 12);
+
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(app.tabs.current().scroll_offset(), bottom);
+
+        app.execute_command("jumps").await.unwrap();
+        assert_eq!(
+            app.status.as_deref(),
+            Some(format!("https://example.com/:12 | >https://example.com/:{}", bottom).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mouse_wheel_scrolls() {
+        use crossterm::event::MouseButton;
+
+        assert_eq!(mouse_scroll_delta(MouseEventKind::ScrollDown), Some(3));
+        assert_eq!(mouse_scroll_delta(MouseEventKind::ScrollUp), Some(-3));
+        assert_eq!(mouse_scroll_delta(MouseEventKind::Down(MouseButton::Left)), None);
+
+        let mut app = test_app().await;
+        let text = "Paragraph\n\n".repeat(50);
+        app.tabs.current_mut().load_page(page("https://example.com", &text, Vec::new()));
+        let wheel = |kind| MouseEvent {
+            kind,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        app.handle_mouse(wheel(MouseEventKind::ScrollDown));
+        app.handle_mouse(wheel(MouseEventKind::ScrollDown));
+        assert_eq!(app.tabs.current().scroll_offset(), 6);
+        app.handle_mouse(wheel(MouseEventKind::ScrollUp));
+        assert_eq!(app.tabs.current().scroll_offset(), 3);
+
+        app.vim.mode = VimMode::Command;
+        app.handle_mouse(wheel(MouseEventKind::ScrollDown));
+        assert_eq!(app.tabs.current().scroll_offset(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_updates_max_width() {
+        let mut app = test_app().await;
+        app.tabs.current_mut().set_viewport_size(120, 40);
+        app.tabs.current_mut().load_page(page("https://example.com", &"word ".repeat(100), Vec::new()));
+        let widest = |app: &App| {
+            app.tabs
+                .current()
+                .visible_lines()
+                .iter()
+                .map(|l| l.chars().count())
+                .max()
+                .unwrap_or(0)
+        };
+        assert!(widest(&app) > 40);
+
+        let mut config = Config::default();
+        config.general.javascript = false;
+        config.display.max_width = 40;
+        app.apply_config(config).await;
+
+        assert_eq!(app.config.display.max_width, 40);
+        assert!(widest(&app) <= 40);
+        assert_eq!(app.status.as_deref(), Some("Config reloaded"));
+    }
+
+    #[tokio::test]
+    async fn test_gi_focuses_first_input() {
+        let mut app = test_app().await;
+        let text = format!("{}[INPUT: Search]\n\nMore text\n", "Paragraph\n\n".repeat(30));
+        app.tabs.current_mut().load_page(page("https://example.com", &text, Vec::new()));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE)).await.unwrap();
+        app.handle_key(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE)).await.unwrap();
+        assert_eq!(app.vim.mode, VimMode::Insert);
+
+        let tab = app.tabs.current();
+        assert_eq!(tab.form_field_lines(), vec![60]);
+        assert!(tab.scroll_offset() > 0);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE)).await.unwrap();
+        assert_eq!(app.tabs.current().form_input(), "r");
+    }
+
+    #[tokio::test]
+    async fn test_navigate_mailto_skips_fetcher() {
+        let mut app = test_app().await;
+        app.navigate("mailto:someone@example.com?subject=Hi").await.unwrap();
+
+        let status = app.status.clone().unwrap_or_default();
+        assert!(status.starts_with("Email: someone@example.com"));
+        assert!(app.tabs.current().url().is_none());
+        assert!(!app.loading);
+    }
+}
@@ -0,0 +1,193 @@
+//! Bookmark storage and import
+
+use anyhow::Result;
+use directories::ProjectDirs;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single bookmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub url: String,
+    pub title: Option<String>,
+    /// When the bookmark was added, in seconds since the Unix epoch
+    pub added: u64,
+}
+
+/// Saved bookmarks, one per URL
+#[derive(Debug, Default)]
+pub struct Bookmarks {
+    entries: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    /// Create an empty bookmark list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load bookmarks from disk
+    pub fn load() -> Result<Self> {
+        let mut bookmarks = Self::new();
+
+        if let Some(path) = Self::bookmarks_path() {
+            if path.exists() {
+                let content = fs::read_to_string(&path)?;
+                bookmarks.entries = serde_json::from_str(&content).unwrap_or_default();
+            }
+        }
+
+        Ok(bookmarks)
+    }
+
+    /// Save bookmarks to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = Self::bookmarks_path() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let content = serde_json::to_string_pretty(&self.entries)?;
+            fs::write(path, content)?;
+        }
+        Ok(())
+    }
+
+    fn bookmarks_path() -> Option<PathBuf> {
+        ProjectDirs::from("com", "fox", "fox")
+            .map(|dirs| dirs.data_dir().join("bookmarks.json"))
+    }
+
+    /// All bookmarks, oldest first
+    pub fn entries(&self) -> &[Bookmark] {
+        &self.entries
+    }
+
+    /// Check whether `url` is bookmarked
+    pub fn contains(&self, url: &str) -> bool {
+        self.entries.iter().any(|b| b.url == url)
+    }
+
+    /// Bookmark `url`, returning false if it was already bookmarked
+    pub fn add(&mut self, url: &str, title: Option<&str>) -> bool {
+        let added = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.insert(Bookmark {
+            url: url.to_string(),
+            title: title.map(String::from),
+            added,
+        })
+    }
+
+    fn insert(&mut self, bookmark: Bookmark) -> bool {
+        if self.contains(&bookmark.url) {
+            return false;
+        }
+        self.entries.push(bookmark);
+        true
+    }
+
+    /// Import a Netscape bookmarks file, the HTML format Chrome and Firefox
+    /// export, returning the number of bookmarks added
+    ///
+    /// Folders are flattened, and URLs that are already bookmarked or are
+    /// not web pages (such as Firefox `place:` queries) are skipped.
+    pub fn import_netscape(&mut self, html: &str) -> usize {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("dt > a[href]").unwrap();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let mut count = 0;
+        for link in document.select(&selector) {
+            let url = link.value().attr("href").unwrap_or_default().trim();
+            if !is_web_url(url) {
+                continue;
+            }
+            let title = link.text().collect::<String>().trim().to_string();
+            let added = link
+                .value()
+                .attr("add_date")
+                .and_then(|date| date.trim().parse().ok())
+                .unwrap_or(now);
+            let bookmark = Bookmark {
+                url: url.to_string(),
+                title: (!title.is_empty()).then_some(title),
+                added,
+            };
+            if self.insert(bookmark) {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+/// Whether `url` is an http(s) URL
+fn is_web_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETSCAPE: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://www.rust-lang.org/" ADD_DATE="1700000100" ICON="data:image/png;base64,AAAA">Rust &amp; Cargo</A>
+        <DT><H3>Docs</H3>
+        <DL><p>
+            <DT><A HREF="https://docs.rs/">Docs.rs</A>
+        </DL><p>
+    </DL><p>
+    <DT><A HREF="place:sort=8&amp;maxResults=10">Recent Tags</A>
+    <DT><A HREF="https://example.com/" ADD_DATE="1700000200"></A>
+    <DT><A HREF="https://www.rust-lang.org/">Rust again</A>
+</DL><p>
+"#;
+
+    #[test]
+    fn test_import_netscape() {
+        let mut bookmarks = Bookmarks::new();
+        assert_eq!(bookmarks.import_netscape(NETSCAPE), 3);
+
+        let entries = bookmarks.entries();
+        let urls: Vec<_> = entries.iter().map(|b| b.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://www.rust-lang.org/", "https://docs.rs/", "https://example.com/"]
+        );
+        assert_eq!(entries[0].title.as_deref(), Some("Rust & Cargo"));
+        assert_eq!(entries[0].added, 1700000100);
+        assert_eq!(entries[1].title.as_deref(), Some("Docs.rs"));
+        assert_eq!(entries[2].title, None);
+    }
+
+    #[test]
+    fn test_import_skips_existing_bookmarks() {
+        let mut bookmarks = Bookmarks::new();
+        assert!(bookmarks.add("https://docs.rs/", Some("Docs")));
+        assert!(!bookmarks.add("https://docs.rs/", None));
+
+        assert_eq!(bookmarks.import_netscape(NETSCAPE), 2);
+        assert_eq!(bookmarks.entries().len(), 3);
+        assert_eq!(bookmarks.entries()[0].title.as_deref(), Some("Docs"));
+        assert_eq!(bookmarks.import_netscape(NETSCAPE), 0);
+    }
+}
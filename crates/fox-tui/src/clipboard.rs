@@ -0,0 +1,82 @@
+//! Clipboard access
+
+/// Read and write access to a clipboard
+pub trait ClipboardProvider: Send {
+    /// Read the clipboard contents as text
+    fn get(&mut self) -> Option<String>;
+
+    /// Replace the clipboard contents, returning whether it succeeded
+    fn set(&mut self, text: &str) -> bool;
+}
+
+/// System clipboard backed by `arboard`
+#[derive(Debug, Default)]
+pub struct SystemClipboard;
+
+impl ClipboardProvider for SystemClipboard {
+    fn get(&mut self) -> Option<String> {
+        arboard::Clipboard::new()
+            .ok()
+            .and_then(|mut c| c.get_text().ok())
+    }
+
+    fn set(&mut self, text: &str) -> bool {
+        arboard::Clipboard::new()
+            .and_then(|mut c| c.set_text(text))
+            .is_ok()
+    }
+}
+
+/// Clipboard that is always empty, used when integration is disabled
+#[derive(Debug, Default)]
+pub struct NoClipboard;
+
+impl ClipboardProvider for NoClipboard {
+    fn get(&mut self) -> Option<String> {
+        None
+    }
+
+    fn set(&mut self, _text: &str) -> bool {
+        false
+    }
+}
+
+/// Pick the clipboard provider for the given setting
+pub fn provider(enabled: bool) -> Box<dyn ClipboardProvider> {
+    if enabled {
+        Box::new(SystemClipboard)
+    } else {
+        Box::new(NoClipboard)
+    }
+}
+
+/// Clean up pasted text for single-line input
+///
+/// Surrounding whitespace is trimmed and line breaks become spaces.
+pub fn paste_text(raw: &str) -> String {
+    raw.trim()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paste_text() {
+        assert_eq!(paste_text("  https://example.com\n"), "https://example.com");
+        assert_eq!(paste_text("one\r\ntwo\n\nthree"), "one two three");
+        assert_eq!(paste_text("\n \n"), "");
+    }
+
+    #[test]
+    fn test_no_clipboard() {
+        let mut clipboard = NoClipboard;
+        assert!(!clipboard.set("text"));
+        assert_eq!(clipboard.get(), None);
+    }
+}
@@ -1,10 +1,19 @@
 //! Configuration management
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use tracing::warn;
+
+/// Environment variables that override settings, with the `:set` key they map to
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("FOX_JAVASCRIPT", "javascript"),
+    ("FOX_BROWSER_MODE", "browser_mode"),
+    ("FOX_EXTRACTION", "extraction_method"),
+    ("FOX_TIMEOUT", "timeout"),
+];
 
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +33,19 @@ pub struct Config {
     /// Keybindings (custom overrides)
     #[serde(default)]
     pub keybindings: KeybindingsConfig,
+
+    /// Content selectors keyed by host pattern ("example.com", "*.example.com")
+    #[serde(default)]
+    pub site_rules: std::collections::BTreeMap<String, String>,
+
+    /// History settings
+    #[serde(default)]
+    pub history: HistoryConfig,
+
+    /// Settings as loaded, before `FOX_*` overrides, when any override
+    /// applied; saved in place of this config so overrides stay temporary
+    #[serde(skip)]
+    file_config: Option<Box<Config>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +61,62 @@ pub struct GeneralConfig {
     /// Request timeout in seconds
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
+
+    /// Strip tracking parameters (utm_*, fbclid, ...) from links and history
+    #[serde(default)]
+    pub strip_tracking_params: bool,
+
+    /// Clean up page titles (collapse whitespace, trim site suffixes)
+    #[serde(default)]
+    pub clean_titles: bool,
+
+    /// Strip zero-width characters, BOMs, and soft hyphens from page text
+    #[serde(default = "default_true")]
+    pub sanitize_text: bool,
+
+    /// Fetch visible links in the background so following them is instant
+    #[serde(default)]
+    pub prefetch: bool,
+
+    /// Most requests per second sent to one host, for page loads and prefetch
+    #[serde(default)]
+    pub requests_per_second_per_host: Option<f64>,
+
+    /// Load the canonical version of AMP and mobile pages
+    #[serde(default)]
+    pub follow_canonical: bool,
+
+    /// Inline the content of same-origin iframes (browser mode only)
+    #[serde(default)]
+    pub follow_iframes: bool,
+
+    /// Render pages with their print stylesheets (browser mode only)
+    #[serde(default)]
+    pub emulate_print: bool,
+
+    /// Directory for downloaded files (defaults to the user's Downloads folder)
+    #[serde(default)]
+    pub downloads_dir: Option<String>,
+
+    /// Integrate with the system clipboard for yank and paste
+    #[serde(default = "default_true")]
+    pub clipboard: bool,
+
+    /// Program that `:gui` opens pages with (defaults to the system browser)
+    #[serde(default)]
+    pub external_browser: Option<String>,
+
+    /// Ad/tracker hosts to block (subdomains included)
+    #[serde(default)]
+    pub blocklist: Vec<String>,
+
+    /// File of additional hosts to block, one per line or hosts-file style
+    #[serde(default)]
+    pub blocklist_file: Option<String>,
+
+    /// CSS selectors for elements to remove before extraction
+    #[serde(default)]
+    pub strip_selectors: Vec<String>,
 }
 
 impl Default for GeneralConfig {
@@ -47,6 +125,20 @@ impl Default for GeneralConfig {
             default_mode: default_mode(),
             javascript: default_true(),
             timeout_secs: default_timeout(),
+            strip_tracking_params: false,
+            clean_titles: false,
+            sanitize_text: default_true(),
+            prefetch: false,
+            requests_per_second_per_host: None,
+            follow_canonical: false,
+            follow_iframes: false,
+            emulate_print: false,
+            downloads_dir: None,
+            clipboard: default_true(),
+            external_browser: None,
+            blocklist: Vec::new(),
+            blocklist_file: None,
+            strip_selectors: Vec::new(),
         }
     }
 }
@@ -57,13 +149,48 @@ pub struct DisplayConfig {
     #[serde(default = "default_width")]
     pub max_width: usize,
 
-    /// Link display style: "inline", "footnote", "hidden"
+    /// Link display style: "inline" or "hidden" ("none" is an alias)
     #[serde(default = "default_link_style")]
     pub show_links: String,
 
     /// Show images as placeholders
     #[serde(default = "default_true")]
     pub show_images: bool,
+
+    /// Center the `max_width` reader column on wide terminals
+    #[serde(default)]
+    pub center_content: bool,
+
+    /// Characters used to build link hints, most convenient first
+    #[serde(default = "default_hint_chars")]
+    pub hint_chars: String,
+
+    /// Show link hints in uppercase
+    #[serde(default)]
+    pub hint_uppercase: bool,
+
+    /// Hint every link on the page, scrolling to the chosen one, instead of
+    /// only the links on screen
+    #[serde(default)]
+    pub hint_all_links: bool,
+
+    /// Lines moved by `j`/`k`
+    #[serde(default = "default_scroll_lines")]
+    pub scroll_lines: usize,
+
+    /// Lines kept between the selected link and the screen edge
+    #[serde(default)]
+    pub scroll_margin: usize,
+
+    /// Animate page-sized jumps over a few frames
+    #[serde(default)]
+    pub smooth_scroll: bool,
+
+    /// Status bar format with `{mode}`, `{url}`, `{progress}`, `{title}`,
+    /// `{reading_time}`, `{tabcount}`, `{loading}` and `{link}` placeholders
+    /// (unset = built-in layout)
+    #[serde(default)]
+    pub statusline: Option<String>,
 }
 
 impl Default for DisplayConfig {
@@ -72,6 +199,40 @@ impl Default for DisplayConfig {
             max_width: default_width(),
             show_links: default_link_style(),
             show_images: default_true(),
+            center_content: false,
+            hint_chars: default_hint_chars(),
+            hint_uppercase: false,
+            hint_all_links: false,
+            scroll_lines: default_scroll_lines(),
+            scroll_margin: 0,
+            smooth_scroll: false,
+            statusline: None,
+        }
+    }
+}
+
+/// History settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Hosts never saved to history (subdomains included)
+    #[serde(default)]
+    pub ignore_hosts: Vec<String>,
+
+    /// Most entries kept; the oldest are dropped first
+    #[serde(default = "default_history_max_entries")]
+    pub max_entries: usize,
+
+    /// Entries older than this many days are dropped (unset keeps them)
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            ignore_hosts: Vec::new(),
+            max_entries: default_history_max_entries(),
+            max_age_days: None,
         }
     }
 }
@@ -107,6 +268,17 @@ pub struct BrowserConfig {
     /// - readability: Use readability-style extraction (works without JS)
     #[serde(default = "default_extraction_method")]
     pub extraction_method: String,
+
+    /// Persistent Chrome profile directory, so logins and site storage
+    /// survive restarts. A profile can only be used by one browser at a time.
+    #[serde(default)]
+    pub user_data_dir: Option<String>,
+
+    /// Resource types to skip while rendering: "image", "font", "media",
+    /// "stylesheet". Unset blocks images and media for accessibility
+    /// extraction and nothing for readability.
+    #[serde(default)]
+    pub block_resources: Option<Vec<String>>,
 }
 
 impl Default for BrowserConfig {
@@ -116,6 +288,8 @@ impl Default for BrowserConfig {
             chrome_path: None,
             auto_update: default_true(),
             extraction_method: default_extraction_method(),
+            user_data_dir: None,
+            block_resources: None,
         }
     }
 }
@@ -128,6 +302,13 @@ fn default_extraction_method() -> String {
     "accessibility".to_string()
 }
 
+/// Parse a `:set` value, naming the setting in the error
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Invalid value for {}: {}", key, value))
+}
+
 // Default value functions
 fn default_mode() -> String {
     "reader".to_string()
@@ -141,9 +322,35 @@ fn default_timeout() -> u64 {
 fn default_width() -> usize {
     80
 }
+fn default_scroll_lines() -> usize {
+    1
+}
+fn default_history_max_entries() -> usize {
+    10000
+}
 fn default_link_style() -> String {
     "inline".to_string()
 }
+fn default_hint_chars() -> String {
+    // Home row first (easier to type), then the top and bottom rows
+    "asdfghjklqwertyuiopzxcvbnm".to_string()
+}
+
+/// Check that a hint alphabet can build unambiguous hints
+fn validate_hint_chars(value: &str) -> Result<()> {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 2 {
+        bail!("Invalid value for hint_chars: need at least 2 characters");
+    }
+    if chars.iter().any(|c| c.is_whitespace()) {
+        bail!("Invalid value for hint_chars: whitespace is not allowed");
+    }
+    let distinct: std::collections::HashSet<char> = chars.iter().copied().collect();
+    if distinct.len() != chars.len() {
+        bail!("Invalid value for hint_chars: characters must not repeat");
+    }
+    Ok(())
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -152,13 +359,25 @@ impl Default for Config {
             browser: BrowserConfig::default(),
             display: DisplayConfig::default(),
             keybindings: KeybindingsConfig::default(),
+            site_rules: std::collections::BTreeMap::new(),
+            history: HistoryConfig::default(),
+            file_config: None,
         }
     }
 }
 
 impl Config {
     /// Load configuration from file or use defaults
+    ///
+    /// `FOX_*` environment variables take precedence over the file, which
+    /// takes precedence over the defaults.
     pub fn load() -> Result<Self> {
+        let mut config = Self::load_file()?;
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
+    fn load_file() -> Result<Self> {
         if let Some(path) = Self::config_path() {
             if path.exists() {
                 let content = fs::read_to_string(&path)?;
@@ -169,13 +388,38 @@ impl Config {
         Ok(Self::default())
     }
 
+    /// Override settings from `FOX_*` environment variables
+    ///
+    /// Invalid values are logged and ignored. Overrides last for this
+    /// session only: `save` keeps writing the values they replaced.
+    pub fn apply_env_overrides(&mut self) {
+        let before = self.clone();
+        let mut overridden = false;
+        for (var, key) in ENV_OVERRIDES {
+            if let Ok(value) = std::env::var(var) {
+                match self.apply(key, &value) {
+                    Ok(()) => overridden = true,
+                    Err(e) => warn!("Ignoring {}: {}", var, e),
+                }
+            }
+        }
+        if overridden && self.file_config.is_none() {
+            self.file_config = Some(Box::new(before));
+        }
+    }
+
+    /// The settings `save` writes: these, minus any environment overrides
+    fn saved_config(&self) -> &Config {
+        self.file_config.as_deref().unwrap_or(self)
+    }
+
     /// Save configuration to file
     pub fn save(&self) -> Result<()> {
         if let Some(path) = Self::config_path() {
             if let Some(parent) = path.parent() {
                 fs::create_dir_all(parent)?;
             }
-            let content = toml::to_string_pretty(self)?;
+            let content = toml::to_string_pretty(self.saved_config())?;
             fs::write(path, content)?;
         }
         Ok(())
@@ -191,26 +435,89 @@ impl Config {
         self.general.javascript
     }
 
-    /// Set a configuration value
+    /// Set a configuration value and save it
+    ///
+    /// Unknown keys and values that don't parse are rejected with a
+    /// descriptive error, leaving the configuration unchanged.
     pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        self.update(key, value)?;
+        self.save()
+    }
+
+    /// Apply a setting to this session and to the settings `save` writes
+    fn update(&mut self, key: &str, value: &str) -> Result<()> {
+        self.apply(key, value)?;
+        if let Some(file_config) = self.file_config.as_mut() {
+            file_config.apply(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Validate and apply a setting without saving it
+    fn apply(&mut self, key: &str, value: &str) -> Result<()> {
         match key {
             "javascript" | "js" => {
-                self.general.javascript = value.parse().unwrap_or(true);
+                self.general.javascript = parse_value(key, value)?;
+            }
+            "default_mode" | "mode" => {
+                value
+                    .parse::<crate::tabs::ViewMode>()
+                    .map_err(|e| anyhow!("Invalid value for {}: {}", key, e))?;
+                self.general.default_mode = value.to_lowercase();
             }
             "max_width" | "width" => {
-                self.display.max_width = value.parse().unwrap_or(80);
+                self.display.max_width = parse_value(key, value)?;
+            }
+            "center_content" | "center" => {
+                self.display.center_content = parse_value(key, value)?;
+            }
+            "scroll_lines" => {
+                let lines: usize = parse_value(key, value)?;
+                if lines == 0 {
+                    bail!("Invalid value for scroll_lines: must be at least 1");
+                }
+                self.display.scroll_lines = lines;
+            }
+            "scroll_margin" => {
+                self.display.scroll_margin = parse_value(key, value)?;
+            }
+            "smooth_scroll" | "smooth" => {
+                self.display.smooth_scroll = parse_value(key, value)?;
+            }
+            "statusline" => {
+                self.display.statusline = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
             }
             "show_links" | "links" => {
-                self.display.show_links = value.to_string();
+                value
+                    .parse::<fox_core::LinkStyle>()
+                    .map_err(|e| anyhow!("Invalid value for {}: {}", key, e))?;
+                self.display.show_links = value.to_lowercase();
             }
             "timeout" => {
-                self.general.timeout_secs = value.parse().unwrap_or(30);
+                let secs: u64 = parse_value(key, value)?;
+                if secs == 0 {
+                    bail!("Invalid value for timeout: must be at least 1 second");
+                }
+                self.general.timeout_secs = secs;
+            }
+            "strip_tracking_params" | "strip_tracking" => {
+                self.general.strip_tracking_params = parse_value(key, value)?;
             }
             "browser_mode" | "browser" => {
                 let valid = ["auto", "bundled", "system", "none"];
-                if valid.contains(&value) {
-                    self.browser.mode = value.to_string();
+                if !valid.contains(&value) {
+                    bail!(
+                        "Invalid value for {}: {}. Use one of: {}",
+                        key,
+                        value,
+                        valid.join(", ")
+                    );
                 }
+                self.browser.mode = value.to_string();
             }
             "chrome_path" => {
                 self.browser.chrome_path = if value.is_empty() {
@@ -219,28 +526,135 @@ impl Config {
                     Some(value.to_string())
                 };
             }
+            "prefetch" => {
+                self.general.prefetch = parse_value(key, value)?;
+            }
+            "requests_per_second_per_host" | "rate_limit" => {
+                self.general.requests_per_second_per_host = if value.is_empty() {
+                    None
+                } else {
+                    let rate: f64 = parse_value(key, value)?;
+                    if fox_core::ratelimit::request_interval(rate).is_none() {
+                        bail!("Invalid value for {}: must be a positive number", key);
+                    }
+                    Some(rate)
+                };
+            }
+            "clean_titles" => {
+                self.general.clean_titles = parse_value(key, value)?;
+            }
+            "sanitize_text" | "sanitize" => {
+                self.general.sanitize_text = parse_value(key, value)?;
+            }
+            "follow_canonical" | "canonical" => {
+                self.general.follow_canonical = parse_value(key, value)?;
+            }
+            "follow_iframes" | "iframes" => {
+                self.general.follow_iframes = parse_value(key, value)?;
+            }
+            "emulate_print" | "print" => {
+                self.general.emulate_print = parse_value(key, value)?;
+            }
+            "downloads_dir" | "downloads" => {
+                self.general.downloads_dir = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
+            "blocklist_file" => {
+                self.general.blocklist_file = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
+            "clipboard" => {
+                self.general.clipboard = parse_value(key, value)?;
+            }
+            "external_browser" => {
+                self.general.external_browser = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
+            "hint_chars" => {
+                validate_hint_chars(value)?;
+                self.display.hint_chars = value.to_string();
+            }
+            "hint_uppercase" => {
+                self.display.hint_uppercase = parse_value(key, value)?;
+            }
+            "hint_all_links" => {
+                self.display.hint_all_links = parse_value(key, value)?;
+            }
             "auto_update" => {
-                self.browser.auto_update = value.parse().unwrap_or(true);
+                self.browser.auto_update = parse_value(key, value)?;
             }
             "extraction_method" | "extraction" => {
-                let valid = ["accessibility", "ax", "a11y", "readability", "reader"];
-                if valid.contains(&value.to_lowercase().as_str()) {
-                    self.browser.extraction_method = match value.to_lowercase().as_str() {
-                        "accessibility" | "ax" | "a11y" => "accessibility".to_string(),
-                        "readability" | "reader" => "readability".to_string(),
-                        _ => "accessibility".to_string(),
-                    };
-                }
-            }
-            _ => {
-                // Store in custom keybindings
-                self.keybindings.custom.insert(key.to_string(), value.to_string());
+                let method: fox_core::ExtractionMethod = value
+                    .parse()
+                    .map_err(|e| anyhow!("Invalid value for {}: {}", key, e))?;
+                self.browser.extraction_method = method.to_string();
             }
+            _ => match key.strip_prefix("keybindings.") {
+                Some(action) if !action.is_empty() => {
+                    self.keybindings.custom.insert(action.to_string(), value.to_string());
+                }
+                _ => bail!("Unknown setting: {}", key),
+            },
         }
-        self.save()?;
         Ok(())
     }
 
+    /// Directory where downloads are saved
+    pub fn downloads_dir(&self) -> PathBuf {
+        if let Some(ref dir) = self.general.downloads_dir {
+            return PathBuf::from(dir);
+        }
+        directories::UserDirs::new()
+            .and_then(|d| d.download_dir().map(|p| p.to_path_buf()))
+            .or_else(|| ProjectDirs::from("com", "fox", "fox").map(|d| d.data_dir().join("downloads")))
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// The link hint alphabet, falling back to the default if the
+    /// configured one is unusable
+    pub fn hint_chars(&self) -> Vec<char> {
+        let chars = if validate_hint_chars(&self.display.hint_chars).is_ok() {
+            self.display.hint_chars.clone()
+        } else {
+            default_hint_chars()
+        };
+        chars.chars().collect()
+    }
+
+    /// Blocked hosts from `blocklist` plus those in `blocklist_file`
+    ///
+    /// A blocklist file that can't be read is logged and skipped.
+    pub fn blocklist(&self) -> Vec<String> {
+        let mut hosts = self.general.blocklist.clone();
+        if let Some(ref path) = self.general.blocklist_file {
+            match fox_core::blocklist::load_blocklist(std::path::Path::new(path)) {
+                Ok(file_hosts) => hosts.extend(file_hosts),
+                Err(e) => warn!("Ignoring blocklist file {}: {}", path, e),
+            }
+        }
+        hosts
+    }
+
+    /// Site rules in the form fox-core expects
+    pub fn site_rules(&self) -> Vec<fox_core::SiteRule> {
+        self.site_rules
+            .iter()
+            .map(|(host, selector)| fox_core::SiteRule {
+                host: host.clone(),
+                selector: selector.clone(),
+            })
+            .collect()
+    }
+
     /// Convert to fox-core ChromeConfig
     pub fn to_chrome_config(&self) -> fox_core::ChromeConfig {
         let data_dir = ProjectDirs::from("", "", "fox")
@@ -250,12 +664,28 @@ impl Config {
         let extraction_method = self.browser.extraction_method.parse()
             .unwrap_or(fox_core::ExtractionMethod::Accessibility);
 
+        let block_resources = match self.browser.block_resources {
+            Some(ref kinds) => kinds
+                .iter()
+                .filter_map(|kind| match kind.parse() {
+                    Ok(kind) => Some(kind),
+                    Err(e) => {
+                        warn!("Ignoring block_resources entry: {}", e);
+                        None
+                    }
+                })
+                .collect(),
+            None => fox_core::ResourceType::defaults_for(extraction_method),
+        };
+
         fox_core::ChromeConfig {
             mode: self.browser.mode.clone(),
             chrome_path: self.browser.chrome_path.as_ref().map(std::path::PathBuf::from),
             data_dir,
             auto_update: self.browser.auto_update,
             extraction_method,
+            user_data_dir: self.browser.user_data_dir.as_ref().map(std::path::PathBuf::from),
+            block_resources,
         }
     }
 }
@@ -268,3 +698,89 @@ impl std::ops::Deref for Config {
         &self.general
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_rejects_unknown_key() {
+        let mut config = Config::default();
+        let err = config.set("frobnicate", "1").unwrap_err();
+        assert!(err.to_string().contains("Unknown setting"));
+        assert!(config.keybindings.custom.is_empty());
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_values() {
+        let mut config = Config::default();
+        assert!(config.set("browser", "frobnicate").is_err());
+        assert_eq!(config.browser.mode, "auto");
+
+        assert!(config.set("timeout", "abc").is_err());
+        assert_eq!(config.general.timeout_secs, 30);
+
+        assert!(config.set("extraction", "magic").is_err());
+        assert!(config.set("mode", "wide").is_err());
+        assert_eq!(config.general.default_mode, "reader");
+        assert!(config.set("js", "maybe").is_err());
+        assert!(config.general.javascript);
+
+        let err = config.set("show_links", "footnote").unwrap_err();
+        assert!(err.to_string().contains("Use 'inline' or 'hidden'"));
+        assert_eq!(config.display.show_links, "inline");
+
+        assert!(config.set("rate_limit", "0").is_err());
+        assert!(config.set("rate_limit", "-1").is_err());
+        assert!(config.apply("rate_limit", "1e-300").is_err());
+        // `set` would save to the real config file, so accept through `apply`
+        config.apply("rate_limit", "2.5").unwrap();
+        assert_eq!(config.general.requests_per_second_per_host, Some(2.5));
+    }
+
+    #[test]
+    fn test_hint_chars_validation() {
+        let mut config = Config::default();
+        config.apply("hint_chars", "aoeuidhtns").unwrap();
+        assert_eq!(config.hint_chars(), "aoeuidhtns".chars().collect::<Vec<_>>());
+
+        assert!(config.apply("hint_chars", "a").is_err());
+        assert!(config.apply("hint_chars", "abca").is_err());
+        assert!(config.apply("hint_chars", "ab c").is_err());
+        assert_eq!(config.display.hint_chars, "aoeuidhtns");
+
+        // A bad alphabet from the config file falls back to the default
+        config.display.hint_chars = "xx".to_string();
+        assert_eq!(config.hint_chars().len(), 26);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        std::env::set_var("FOX_JAVASCRIPT", "false");
+        std::env::set_var("FOX_BROWSER_MODE", "system");
+        std::env::set_var("FOX_EXTRACTION", "readability");
+        std::env::set_var("FOX_TIMEOUT", "not-a-number");
+
+        let mut config = Config::default();
+        config.apply_env_overrides();
+
+        for (var, _) in ENV_OVERRIDES {
+            std::env::remove_var(var);
+        }
+
+        assert!(!config.general.javascript);
+        assert_eq!(config.browser.mode, "system");
+        assert_eq!(config.browser.extraction_method, "readability");
+        // Invalid values leave the file/default value in place
+        assert_eq!(config.general.timeout_secs, 30);
+
+        // Settings changed later are saved without the overrides
+        config.update("mode", "full").unwrap();
+        assert_eq!(config.general.default_mode, "full");
+        let saved = config.saved_config();
+        assert_eq!(saved.general.default_mode, "full");
+        assert!(saved.general.javascript);
+        assert_eq!(saved.browser.mode, "auto");
+        assert_eq!(saved.browser.extraction_method, "accessibility");
+    }
+}
@@ -2,9 +2,12 @@
 
 use anyhow::Result;
 use directories::ProjectDirs;
+use fox_core::blocklist;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// A single history entry
@@ -15,6 +18,32 @@ pub struct HistoryEntry {
     pub timestamp: u64,
 }
 
+/// File formats [`History::export`] can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    /// Format for a file name: CSV for `.csv`, otherwise JSON
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => ExportFormat::Csv,
+            _ => ExportFormat::Json,
+        }
+    }
+}
+
+/// A visited URL as exported, with its latest title and visit time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedEntry {
+    pub url: String,
+    pub title: Option<String>,
+    pub timestamp: u64,
+    pub visit_count: usize,
+}
+
 /// Navigation history
 #[derive(Debug, Default)]
 pub struct History {
@@ -26,6 +55,12 @@ pub struct History {
     session_stack: Vec<String>,
     /// Maximum entries to keep
     max_entries: usize,
+    /// Age in days after which entries are dropped
+    max_age_days: Option<u64>,
+    /// Hosts never recorded (subdomains included)
+    ignore_hosts: Vec<String>,
+    /// Private mode: nothing is recorded for the rest of the session
+    private: bool,
 }
 
 impl History {
@@ -36,9 +71,53 @@ impl History {
             position: 0,
             session_stack: Vec::new(),
             max_entries: 10000,
+            max_age_days: None,
+            ignore_hosts: Vec::new(),
+            private: false,
         }
     }
 
+    /// Limit history to `max_entries` entries no older than `max_age_days`,
+    /// pruning anything already outside the limits
+    pub fn set_retention(&mut self, max_entries: usize, max_age_days: Option<u64>) {
+        self.max_entries = max_entries;
+        self.max_age_days = max_age_days;
+        self.prune(now());
+    }
+
+    /// Drop entries older than the age limit, then the oldest entries over
+    /// the entry cap
+    fn prune(&mut self, now: u64) {
+        if let Some(days) = self.max_age_days {
+            let cutoff = now.saturating_sub(days.saturating_mul(SECS_PER_DAY));
+            self.entries.retain(|e| e.timestamp >= cutoff);
+        }
+        if self.entries.len() > self.max_entries {
+            let excess = self.entries.len() - self.max_entries;
+            self.entries.drain(..excess);
+        }
+    }
+
+    /// Set the hosts whose pages are never recorded
+    pub fn set_ignore_hosts(&mut self, hosts: Vec<String>) {
+        self.ignore_hosts = hosts;
+    }
+
+    /// Whether private mode is on
+    pub fn is_private(&self) -> bool {
+        self.private
+    }
+
+    /// Turn private mode on or off
+    pub fn set_private(&mut self, private: bool) {
+        self.private = private;
+    }
+
+    /// Whether visiting `url` is saved to history
+    fn records(&self, url: &str) -> bool {
+        !self.private && !blocklist::url_is_blocked(url, &self.ignore_hosts)
+    }
+
     /// Load history from disk
     pub fn load() -> Result<Self> {
         let mut history = Self::new();
@@ -71,22 +150,23 @@ impl History {
     }
 
     /// Add a new URL to history
+    ///
+    /// In private mode, or for an ignored host, the URL only joins the
+    /// back/forward stack and is not saved.
     pub fn add(&mut self, url: &str, title: Option<&str>) {
-        let entry = HistoryEntry {
-            url: url.to_string(),
-            title: title.map(String::from),
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .map(|d| d.as_secs())
-                .unwrap_or(0),
-        };
-
-        // Add to persistent history
-        self.entries.push(entry);
-
-        // Trim if too long
-        if self.entries.len() > self.max_entries {
-            self.entries.remove(0);
+        let record = self.records(url);
+        if record {
+            let timestamp = now();
+            let entry = HistoryEntry {
+                url: url.to_string(),
+                title: title.map(String::from),
+                timestamp,
+            };
+
+            // Add to persistent history, dropping whatever is now too old
+            // or over the cap
+            self.entries.push(entry);
+            self.prune(timestamp);
         }
 
         // Add to session stack
@@ -98,7 +178,9 @@ impl History {
         self.position = self.session_stack.len();
 
         // Auto-save
-        let _ = self.save();
+        if record {
+            let _ = self.save();
+        }
     }
 
     /// Go back in history
@@ -163,6 +245,46 @@ impl History {
             .collect()
     }
 
+    /// One entry per URL, in order of first visit
+    fn exported_entries(&self) -> Vec<ExportedEntry> {
+        let mut exported: Vec<ExportedEntry> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for entry in &self.entries {
+            match index.get(entry.url.as_str()) {
+                Some(&i) => {
+                    let existing = &mut exported[i];
+                    existing.visit_count += 1;
+                    existing.timestamp = entry.timestamp;
+                    if entry.title.is_some() {
+                        existing.title = entry.title.clone();
+                    }
+                }
+                None => {
+                    index.insert(&entry.url, exported.len());
+                    exported.push(ExportedEntry {
+                        url: entry.url.clone(),
+                        title: entry.title.clone(),
+                        timestamp: entry.timestamp,
+                        visit_count: 1,
+                    });
+                }
+            }
+        }
+        exported
+    }
+
+    /// Write history to `path` with one row per URL, returning the number
+    /// of URLs written
+    pub fn export(&self, format: ExportFormat, path: &Path) -> Result<usize> {
+        let entries = self.exported_entries();
+        let content = match format {
+            ExportFormat::Json => serde_json::to_string_pretty(&entries)?,
+            ExportFormat::Csv => to_csv(&entries),
+        };
+        fs::write(path, content)?;
+        Ok(entries.len())
+    }
+
     /// Clear all history
     pub fn clear(&mut self) -> Result<()> {
         self.entries.clear();
@@ -172,6 +294,40 @@ impl History {
     }
 }
 
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Current time in seconds since the Unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Render exported entries as CSV with a header row
+fn to_csv(entries: &[ExportedEntry]) -> String {
+    let mut csv = String::from("url,title,timestamp,visit_count\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            csv_field(&entry.url),
+            csv_field(entry.title.as_deref().unwrap_or("")),
+            entry.timestamp,
+            entry.visit_count
+        ));
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote, or line break
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,4 +365,123 @@ mod tests {
         let results = history.search("rust");
         assert_eq!(results.len(), 2);
     }
+
+    fn entry(url: &str, title: Option<&str>, timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            url: url.to_string(),
+            title: title.map(String::from),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_export_csv_quotes_fields() {
+        let mut history = History::new();
+        history.entries = vec![
+            entry("https://a.com", Some("Plain"), 100),
+            entry("https://b.com/?q=1,2", Some("Say \"hi\", then\nleave"), 200),
+            entry("https://a.com", None, 300),
+        ];
+
+        let path = std::env::temp_dir().join(format!("fox-history-{}.csv", std::process::id()));
+        assert_eq!(ExportFormat::from_path(&path), ExportFormat::Csv);
+        assert_eq!(history.export(ExportFormat::Csv, &path).unwrap(), 2);
+        let csv = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            csv,
+            "url,title,timestamp,visit_count\n\
+             https://a.com,Plain,300,2\n\
+             \"https://b.com/?q=1,2\",\"Say \"\"hi\"\", then\nleave\",200,1\n"
+        );
+    }
+
+    #[test]
+    fn test_export_json_round_trip() {
+        let mut history = History::new();
+        history.entries = vec![
+            entry("https://a.com", Some("A"), 100),
+            entry("https://b.com", None, 200),
+            entry("https://a.com", Some("A again"), 300),
+        ];
+
+        let path = std::env::temp_dir().join(format!("fox-history-{}.json", std::process::id()));
+        assert_eq!(ExportFormat::from_path(&path), ExportFormat::Json);
+        history.export(ExportFormat::Json, &path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let exported: Vec<ExportedEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(exported, history.exported_entries());
+        assert_eq!(exported[0].title.as_deref(), Some("A again"));
+        assert_eq!(exported[0].timestamp, 300);
+        assert_eq!(exported[0].visit_count, 2);
+        assert_eq!(exported[1].url, "https://b.com");
+    }
+
+    #[test]
+    fn test_prune_drops_entries_past_max_age() {
+        let mut history = History::new();
+        let now = 100 * SECS_PER_DAY;
+        history.entries = vec![
+            entry("https://old.com", None, now - 31 * SECS_PER_DAY),
+            entry("https://recent.com", None, now - 29 * SECS_PER_DAY),
+            entry("https://today.com", None, now),
+        ];
+        history.max_age_days = Some(30);
+        history.prune(now);
+
+        let urls: Vec<_> = history.entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://recent.com", "https://today.com"]);
+
+        // Adding prunes against the current time, long after these entries
+        history.add("https://new.com", None);
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].url, "https://new.com");
+    }
+
+    #[test]
+    fn test_max_entries_cap() {
+        let mut history = History::new();
+        history.entries = (0..5)
+            .map(|i| entry(&format!("https://{}.com", i), None, now()))
+            .collect();
+        history.set_retention(3, None);
+        let urls: Vec<_> = history.entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://2.com", "https://3.com", "https://4.com"]);
+
+        history.add("https://5.com", None);
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(history.entries[0].url, "https://3.com");
+    }
+
+    #[test]
+    fn test_ignored_host_not_recorded() {
+        let mut history = History::new();
+        history.set_ignore_hosts(vec!["intranet.example.com".to_string()]);
+
+        history.add("https://wiki.intranet.example.com/page", Some("Wiki"));
+        history.add("https://rust-lang.org", Some("Rust"));
+
+        let urls: Vec<_> = history.recent(10).iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://rust-lang.org"]);
+        // Back still returns to the ignored page
+        assert_eq!(history.back(), Some("https://wiki.intranet.example.com/page".to_string()));
+    }
+
+    #[test]
+    fn test_private_mode_records_nothing() {
+        let mut history = History::new();
+        history.set_private(true);
+
+        history.add("https://a.com", Some("A"));
+        history.add("https://b.com", Some("B"));
+        assert!(history.recent(10).is_empty());
+        assert!(history.search("a.com").is_empty());
+
+        history.set_private(false);
+        history.add("https://c.com", Some("C"));
+        assert_eq!(history.recent(10).len(), 1);
+    }
 }
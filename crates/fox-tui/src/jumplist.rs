@@ -0,0 +1,127 @@
+//! Vim-style jumplist of positions visited in this session
+
+/// Maximum jumps remembered, like vim's 100
+const MAX_JUMPS: usize = 100;
+
+/// A position jumped away from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jump {
+    pub url: String,
+    pub scroll_offset: usize,
+}
+
+/// Positions left by navigations and large scroll jumps, walked with
+/// `Ctrl-o` and `Ctrl-i`
+///
+/// Unlike page history this includes scroll positions within a page, and
+/// is never saved.
+#[derive(Debug, Default)]
+pub struct JumpList {
+    entries: Vec<Jump>,
+    /// Index of the entry `Ctrl-i` returns to; `entries.len()` when not
+    /// walking the list
+    position: usize,
+}
+
+impl JumpList {
+    /// Create an empty jumplist
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember `jump` as the position being jumped away from
+    ///
+    /// Jumping while walking the list discards the entries ahead of the
+    /// current one.
+    pub fn record(&mut self, jump: Jump) {
+        self.entries.truncate(self.position);
+        if self.entries.last() != Some(&jump) {
+            self.entries.push(jump);
+        }
+        if self.entries.len() > MAX_JUMPS {
+            self.entries.remove(0);
+        }
+        self.position = self.entries.len();
+    }
+
+    /// Step back to the previous jump (`Ctrl-o`)
+    ///
+    /// `current` is remembered on the first step back so `Ctrl-i` can
+    /// return to it.
+    pub fn back(&mut self, current: Jump) -> Option<Jump> {
+        if self.position == 0 {
+            return None;
+        }
+        if self.position == self.entries.len() {
+            self.entries.push(current);
+        }
+        self.position -= 1;
+        self.entries.get(self.position).cloned()
+    }
+
+    /// Step forward to the next jump (`Ctrl-i`)
+    pub fn forward(&mut self) -> Option<Jump> {
+        if self.position + 1 >= self.entries.len() {
+            return None;
+        }
+        self.position += 1;
+        self.entries.get(self.position).cloned()
+    }
+
+    /// List jumps as a string, marking the current one
+    pub fn list(&self) -> String {
+        if self.entries.is_empty() {
+            return "No jumps".to_string();
+        }
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, jump)| {
+                let marker = if i == self.position { ">" } else { "" };
+                format!("{}{}:{}", marker, jump.url, jump.scroll_offset)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jump(url: &str, scroll_offset: usize) -> Jump {
+        Jump {
+            url: url.to_string(),
+            scroll_offset,
+        }
+    }
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut jumps = JumpList::new();
+        jumps.record(jump("https://a.com", 0));
+        jumps.record(jump("https://a.com", 40));
+
+        let current = jump("https://b.com", 5);
+        assert_eq!(jumps.back(current.clone()), Some(jump("https://a.com", 40)));
+        assert_eq!(jumps.back(jump("https://a.com", 40)), Some(jump("https://a.com", 0)));
+        assert_eq!(jumps.back(jump("https://a.com", 0)), None);
+
+        assert_eq!(jumps.forward(), Some(jump("https://a.com", 40)));
+        assert_eq!(jumps.forward(), Some(current));
+        assert_eq!(jumps.forward(), None);
+    }
+
+    #[test]
+    fn test_record_while_walking_drops_newer_jumps() {
+        let mut jumps = JumpList::new();
+        jumps.record(jump("https://a.com", 0));
+        jumps.record(jump("https://b.com", 0));
+        jumps.back(jump("https://c.com", 0));
+
+        jumps.record(jump("https://b.com", 10));
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.back(jump("https://d.com", 0)), Some(jump("https://b.com", 10)));
+        assert_eq!(jumps.back(jump("https://b.com", 10)), Some(jump("https://a.com", 0)));
+    }
+}
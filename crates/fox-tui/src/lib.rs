@@ -3,8 +3,12 @@
 //! This crate provides the interactive terminal UI for the Fox browser.
 
 pub mod app;
+pub mod bookmarks;
+pub mod clipboard;
 pub mod config;
 pub mod history;
+pub mod jumplist;
+pub mod prefetch;
 pub mod tabs;
 pub mod ui;
 pub mod vim;
@@ -22,6 +26,11 @@ use std::time::Duration;
 
 /// Run the TUI browser
 pub async fn run(initial_url: Option<String>) -> Result<()> {
+    run_with_tabs(initial_url.into_iter().collect()).await
+}
+
+/// Run the TUI browser with each URL opened in its own tab
+pub async fn run_with_tabs(initial_urls: Vec<String>) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -32,9 +41,11 @@ pub async fn run(initial_url: Option<String>) -> Result<()> {
     // Create app
     let mut app = App::new().await?;
 
-    // Load initial URL if provided
-    if let Some(url) = initial_url {
-        app.navigate(&url).await?;
+    // Load initial URLs if provided
+    match initial_urls.as_slice() {
+        [] => {}
+        [url] => app.navigate(url).await?,
+        urls => app.open_tabs(urls).await?,
     }
 
     // Main event loop
@@ -62,18 +73,23 @@ async fn run_app(
 
         // Handle events with timeout for async operations
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                // Handle Ctrl+C globally
-                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
-                {
-                    return Ok(());
-                }
+            match event::read()? {
+                Event::Key(key) => {
+                    // Handle Ctrl+C globally
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('c')
+                    {
+                        return Ok(());
+                    }
 
-                // Handle key based on current mode
-                let should_quit = app.handle_key(key).await?;
-                if should_quit {
-                    return Ok(());
+                    // Handle key based on current mode
+                    let should_quit = app.handle_key(key).await?;
+                    if should_quit {
+                        return Ok(());
+                    }
                 }
+                Event::Mouse(mouse) => app.handle_mouse(mouse),
+                _ => {}
             }
         }
 
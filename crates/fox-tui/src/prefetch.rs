@@ -0,0 +1,232 @@
+//! Background prefetching of visible links
+//!
+//! While browsing, links on screen are fetched in the background so that
+//! following one can be served from a small in-memory page cache.
+
+use fox_core::fetch::Fetcher;
+use fox_core::{Link, Page};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{RwLock, Semaphore};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// Maximum number of prefetches running at once
+const MAX_CONCURRENT: usize = 3;
+
+/// Maximum number of links prefetched for one viewport
+const MAX_CANDIDATES: usize = 8;
+
+/// Maximum number of pages kept in the cache
+const CACHE_CAPACITY: usize = 32;
+
+/// Bounded cache of fetched pages keyed by URL, evicting the oldest entry
+#[derive(Default)]
+pub struct PageCache {
+    pages: HashMap<String, Page>,
+    order: VecDeque<String>,
+}
+
+impl PageCache {
+    /// Store a page, evicting the oldest entry when full
+    pub fn insert(&mut self, url: String, page: Page) {
+        if self.pages.insert(url.clone(), page).is_none() {
+            self.order.push_back(url);
+        }
+        while self.order.len() > CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.pages.remove(&oldest);
+            }
+        }
+    }
+
+    /// Remove and return a cached page
+    pub fn take(&mut self, url: &str) -> Option<Page> {
+        let page = self.pages.remove(url)?;
+        self.order.retain(|u| u != url);
+        Some(page)
+    }
+
+    /// Check if a page is cached
+    pub fn contains(&self, url: &str) -> bool {
+        self.pages.contains_key(url)
+    }
+}
+
+/// Runs bounded background fetches for visible links
+pub struct Prefetcher {
+    cache: Arc<Mutex<PageCache>>,
+    in_flight: HashMap<String, JoinHandle<()>>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl Prefetcher {
+    /// Create a prefetcher with an empty cache
+    pub fn new() -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(PageCache::default())),
+            in_flight: HashMap::new(),
+            semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT)),
+        }
+    }
+
+    /// Take a prefetched page for `url`, if one is ready
+    pub fn take(&self, url: &str) -> Option<Page> {
+        self.cache.lock().ok()?.take(url)
+    }
+
+    /// Prefetch the given visible links, canceling prefetches for links
+    /// that are no longer on screen
+    pub fn update(&mut self, visible: &[Link], fetcher: &Arc<RwLock<Fetcher>>) {
+        let candidates = match self.cache.lock() {
+            Ok(cache) => prefetch_candidates(visible, &cache, MAX_CANDIDATES),
+            Err(_) => return,
+        };
+
+        self.in_flight.retain(|url, handle| {
+            if handle.is_finished() {
+                false
+            } else if !candidates.contains(url) {
+                debug!("Canceling stale prefetch: {}", url);
+                handle.abort();
+                false
+            } else {
+                true
+            }
+        });
+
+        for url in candidates {
+            if self.in_flight.contains_key(&url) {
+                continue;
+            }
+
+            let cache = Arc::clone(&self.cache);
+            let semaphore = Arc::clone(&self.semaphore);
+            let fetcher = Arc::clone(fetcher);
+            let task_url = url.clone();
+            let handle = tokio::spawn(async move {
+                let _permit = match semaphore.acquire_owned().await {
+                    Ok(permit) => permit,
+                    Err(_) => return,
+                };
+                debug!("Prefetching: {}", task_url);
+                let result = fetcher.read().await.fetch(&task_url).await;
+                match result {
+                    Ok(page) => {
+                        if let Ok(mut cache) = cache.lock() {
+                            cache.insert(task_url, page);
+                        }
+                    }
+                    Err(e) => debug!("Prefetch failed for {}: {}", task_url, e),
+                }
+            });
+            self.in_flight.insert(url, handle);
+        }
+    }
+
+    /// Abort every running prefetch
+    pub fn cancel_all(&mut self) {
+        for (_, handle) in self.in_flight.drain() {
+            handle.abort();
+        }
+    }
+}
+
+impl Default for Prefetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Prefetcher {
+    fn drop(&mut self) {
+        self.cancel_all();
+    }
+}
+
+/// Pick which visible links are worth prefetching
+///
+/// Only http(s) links are considered, in-page anchors and already cached
+/// pages are skipped, duplicates are removed, and at most `max` URLs are
+/// returned in on-screen order.
+pub fn prefetch_candidates(visible: &[Link], cache: &PageCache, max: usize) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for link in visible {
+        let url = link.url.as_str();
+        let lower = url.to_lowercase();
+        if !(lower.starts_with("http://") || lower.starts_with("https://")) {
+            continue;
+        }
+        if cache.contains(url) || candidates.iter().any(|c| c == url) {
+            continue;
+        }
+        candidates.push(url.to_string());
+        if candidates.len() >= max {
+            break;
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(url: &str) -> Link {
+        Link {
+            text: url.to_string(),
+            url: url.to_string(),
+            title: None,
+            position: 0,
+        }
+    }
+
+    fn page(url: &str) -> Page {
+        Page {
+            url: url::Url::parse(url).unwrap(),
+            title: None,
+            html: String::new(),
+            content: None,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_prefetch_candidates() {
+        let mut cache = PageCache::default();
+        cache.insert("https://cached.com/".to_string(), page("https://cached.com/"));
+
+        let visible = vec![
+            link("https://a.com/"),
+            link("mailto:someone@example.com"),
+            link("https://a.com/"),
+            link("https://cached.com/"),
+            link("#section"),
+            link("https://b.com/"),
+            link("https://c.com/"),
+        ];
+
+        assert_eq!(
+            prefetch_candidates(&visible, &cache, 8),
+            vec!["https://a.com/", "https://b.com/", "https://c.com/"]
+        );
+        assert_eq!(
+            prefetch_candidates(&visible, &cache, 2),
+            vec!["https://a.com/", "https://b.com/"]
+        );
+    }
+
+    #[test]
+    fn test_page_cache_evicts_oldest() {
+        let mut cache = PageCache::default();
+        for i in 0..=CACHE_CAPACITY {
+            let url = format!("https://example.com/{}", i);
+            cache.insert(url.clone(), page(&url));
+        }
+        assert!(!cache.contains("https://example.com/0"));
+        assert!(cache.contains(&format!("https://example.com/{}", CACHE_CAPACITY)));
+        assert!(cache.take("https://example.com/1").is_some());
+        assert!(!cache.contains("https://example.com/1"));
+    }
+}
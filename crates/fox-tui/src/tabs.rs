@@ -1,31 +1,73 @@
 //! Tab/buffer management
 
 use fox_core::{Link, Page};
+use std::collections::HashMap;
 use textwrap;
 
+/// How a page is rendered in a tab
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ViewMode {
+    /// Extracted main content only
+    #[default]
+    Reader,
+    /// The whole page, including navigation and footers
+    Full,
+}
+
+impl std::str::FromStr for ViewMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "reader" => Ok(ViewMode::Reader),
+            "full" => Ok(ViewMode::Full),
+            _ => Err(format!("Unknown view mode: {}. Use 'reader' or 'full'", s)),
+        }
+    }
+}
+
 /// A single browser tab
 #[derive(Debug)]
 pub struct Tab {
     /// The loaded page (if any)
     page: Option<Page>,
+    /// Page markdown for the current view mode, kept so resizing only
+    /// re-wraps it
+    markdown: String,
+    /// Times the page was converted to markdown
+    #[cfg(test)]
+    conversions: usize,
     /// Rendered markdown content
     rendered_lines: Vec<String>,
+    /// Index in `rendered_lines` where each line of `markdown` starts
+    line_starts: Vec<usize>,
     /// Current scroll position (line number)
     scroll_offset: usize,
+    /// Offset a smooth scroll is animating towards
+    scroll_target: Option<usize>,
     /// Viewport height in lines
     pub viewport_height: usize,
     /// Viewport width in columns
     pub viewport_width: usize,
+    /// Maximum wrap width in columns (0 = viewport width)
+    max_width: usize,
+    /// Whether to show extracted content or the whole page
+    view_mode: ViewMode,
     /// Currently selected link index
     selected_link: Option<usize>,
     /// Current form field index
     form_field_index: usize,
+    /// Text typed into each form field, keyed by field index
+    form_inputs: HashMap<usize, String>,
 }
 
 impl Default for Tab {
     fn default() -> Self {
         Self {
             page: None,
+            markdown: String::new(),
+            #[cfg(test)]
+            conversions: 0,
             rendered_lines: vec![
                 "".to_string(),
                 "  Welcome to Fox - Terminal Web Browser".to_string(),
@@ -47,11 +89,16 @@ impl Default for Tab {
                 "    d         - Close tab".to_string(),
                 "".to_string(),
             ],
+            line_starts: Vec::new(),
             scroll_offset: 0,
+            scroll_target: None,
             viewport_height: 24,
             viewport_width: 80,
+            max_width: 0,
+            view_mode: ViewMode::Reader,
             selected_link: None,
             form_field_index: 0,
+            form_inputs: HashMap::new(),
         }
     }
 }
@@ -64,10 +111,11 @@ impl Tab {
 
     /// Load a page into the tab
     pub fn load_page(&mut self, page: Page) {
-        let content = page.to_markdown();
-        self.rendered_lines = self.wrap_content(&content);
         self.page = Some(page);
+        self.convert_page();
         self.scroll_offset = 0;
+        self.form_field_index = 0;
+        self.form_inputs.clear();
         self.selected_link = if self.links().map(|l| l.is_empty()).unwrap_or(true) {
             None
         } else {
@@ -75,23 +123,43 @@ impl Tab {
         };
     }
 
-    fn wrap_content(&self, content: &str) -> Vec<String> {
-        let width = self.viewport_width.saturating_sub(2).max(20);
-        content
-            .lines()
-            .flat_map(|line| {
-                if line.trim().is_empty() {
-                    vec![String::new()]
-                } else {
-                    // Convert markdown to plain text before wrapping
-                    let plain = Self::markdown_to_plain(line);
-                    textwrap::wrap(&plain, width)
-                        .into_iter()
-                        .map(|s| s.to_string())
-                        .collect::<Vec<_>>()
-                }
-            })
-            .collect()
+    /// Convert the page to markdown for the tab's view mode and wrap it
+    fn convert_page(&mut self) {
+        let markdown = match (&self.page, self.view_mode) {
+            (Some(page), ViewMode::Reader) => page.to_markdown(),
+            (Some(page), ViewMode::Full) => page.to_full_markdown(),
+            (None, _) => return,
+        };
+        self.markdown = markdown;
+        #[cfg(test)]
+        {
+            self.conversions += 1;
+        }
+        self.wrap_markdown();
+    }
+
+    /// Wrap `markdown` into `rendered_lines`, remembering where each line
+    /// of it starts
+    fn wrap_markdown(&mut self) {
+        let mut width = self.viewport_width.saturating_sub(2);
+        if self.max_width > 0 {
+            width = width.min(self.max_width);
+        }
+        let width = width.max(20);
+
+        self.rendered_lines.clear();
+        self.line_starts.clear();
+        for line in self.markdown.lines() {
+            self.line_starts.push(self.rendered_lines.len());
+            if line.trim().is_empty() {
+                self.rendered_lines.push(String::new());
+            } else {
+                // Convert markdown to plain text before wrapping
+                let plain = Self::markdown_to_plain(line);
+                self.rendered_lines
+                    .extend(textwrap::wrap(&plain, width).into_iter().map(|s| s.to_string()));
+            }
+        }
     }
 
     /// Convert markdown syntax to plain text for display
@@ -190,11 +258,21 @@ impl Tab {
         result
     }
 
+    /// Get the loaded page
+    pub fn page(&self) -> Option<&Page> {
+        self.page.as_ref()
+    }
+
     /// Get the page URL
     pub fn url(&self) -> Option<String> {
         self.page.as_ref().map(|p| p.url.to_string())
     }
 
+    /// Get the page URL, the base for resolving its links
+    pub fn page_url(&self) -> Option<&url::Url> {
+        self.page.as_ref().map(|p| &p.url)
+    }
+
     /// Get the page title
     pub fn title(&self) -> Option<&str> {
         self.page
@@ -210,6 +288,11 @@ impl Tab {
             .map(|c| c.text.as_str())
     }
 
+    /// Markdown for the loaded page as shown in the current view mode
+    pub fn markdown(&self) -> Option<&str> {
+        self.page.as_ref().map(|_| self.markdown.as_str())
+    }
+
     /// Get the visible lines
     pub fn visible_lines(&self) -> &[String] {
         let end = (self.scroll_offset + self.viewport_height).min(self.rendered_lines.len());
@@ -228,27 +311,105 @@ impl Tab {
 
     /// Scroll down by n lines
     pub fn scroll_down(&mut self, n: usize) {
+        self.scroll_target = None;
         let max = self.rendered_lines.len().saturating_sub(self.viewport_height);
         self.scroll_offset = (self.scroll_offset + n).min(max);
     }
 
     /// Scroll up by n lines
     pub fn scroll_up(&mut self, n: usize) {
+        self.scroll_target = None;
         self.scroll_offset = self.scroll_offset.saturating_sub(n);
     }
 
+    /// Scroll by `delta` lines (negative is up)
+    ///
+    /// With `smooth` the move is animated by [`Tab::step_scroll`] instead of
+    /// happening at once. Repeated smooth scrolls add up.
+    pub fn scroll_by(&mut self, delta: isize, smooth: bool) {
+        if !smooth {
+            if delta >= 0 {
+                self.scroll_down(delta.unsigned_abs());
+            } else {
+                self.scroll_up(delta.unsigned_abs());
+            }
+            return;
+        }
+        let max = self.rendered_lines.len().saturating_sub(self.viewport_height);
+        let from = self.scroll_target.unwrap_or(self.scroll_offset);
+        let target = (from as isize + delta).clamp(0, max as isize) as usize;
+        self.scroll_target = Some(target);
+    }
+
+    /// Advance a smooth scroll by one frame, covering half the remaining
+    /// distance
+    ///
+    /// Returns false once there is nothing left to animate.
+    pub fn step_scroll(&mut self) -> bool {
+        let target = match self.scroll_target {
+            Some(target) => target,
+            None => return false,
+        };
+        let step = target.abs_diff(self.scroll_offset).div_ceil(2);
+        if target > self.scroll_offset {
+            self.scroll_offset += step;
+        } else {
+            self.scroll_offset -= step;
+        }
+        if self.scroll_offset == target {
+            self.scroll_target = None;
+        }
+        true
+    }
+
+    /// Scroll just enough to keep `line` at least `margin` lines away from
+    /// the top and bottom of the viewport
+    pub fn reveal_line(&mut self, line: usize, margin: usize) {
+        self.scroll_target = None;
+        let margin = margin.min(self.viewport_height.saturating_sub(1) / 2);
+        if line < self.scroll_offset + margin {
+            self.scroll_offset = line.saturating_sub(margin);
+        } else if line + margin >= self.scroll_offset + self.viewport_height {
+            self.scroll_offset = (line + margin + 1).saturating_sub(self.viewport_height);
+        }
+        let max = self.rendered_lines.len().saturating_sub(self.viewport_height);
+        self.scroll_offset = self.scroll_offset.min(max);
+    }
+
     /// Scroll to top
     pub fn scroll_to_top(&mut self) {
+        self.scroll_target = None;
         self.scroll_offset = 0;
     }
 
     /// Scroll to bottom
     pub fn scroll_to_bottom(&mut self) {
+        self.scroll_target = None;
         self.scroll_offset = self.rendered_lines.len().saturating_sub(self.viewport_height);
     }
 
-    /// Scroll to a specific line
+    /// Restore a scroll offset, clamped to the page
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_target = None;
+        let max = self.rendered_lines.len().saturating_sub(self.viewport_height);
+        self.scroll_offset = offset.min(max);
+    }
+
+    /// Rendered line where line `line` of the page's markdown starts, after
+    /// wrapping
+    pub fn rendered_line(&self, line: usize) -> usize {
+        self.line_starts.get(line).copied().unwrap_or(line)
+    }
+
+    /// Scroll to a line of the page's markdown, such as a heading from
+    /// `extract_toc`
+    pub fn scroll_to_markdown_line(&mut self, line: usize) {
+        self.scroll_to_line(self.rendered_line(line));
+    }
+
+    /// Scroll to a specific rendered line
     pub fn scroll_to_line(&mut self, line: usize) {
+        self.scroll_target = None;
         self.scroll_offset = line.saturating_sub(self.viewport_height / 2);
         let max = self.rendered_lines.len().saturating_sub(self.viewport_height);
         self.scroll_offset = self.scroll_offset.min(max);
@@ -258,11 +419,28 @@ impl Tab {
     pub fn set_viewport_size(&mut self, width: u16, height: u16) {
         self.viewport_width = width as usize;
         self.viewport_height = height.saturating_sub(4) as usize; // Leave room for status bars
+        self.rewrap();
+    }
+
+    /// Limit the wrap width (0 = viewport width) and re-wrap the page
+    pub fn set_max_width(&mut self, max_width: usize) {
+        self.max_width = max_width;
+        self.rewrap();
+    }
+
+    /// Switch between reader and full-page rendering
+    pub fn set_view_mode(&mut self, mode: ViewMode) {
+        if self.view_mode != mode {
+            self.view_mode = mode;
+            self.convert_page();
+            self.set_scroll_offset(self.scroll_offset);
+        }
+    }
 
-        // Re-wrap content if we have a page
-        if let Some(ref page) = self.page {
-            let content = page.to_markdown();
-            self.rendered_lines = self.wrap_content(&content);
+    /// Re-wrap the page's markdown, e.g. after the width changed
+    fn rewrap(&mut self) {
+        if self.page.is_some() {
+            self.wrap_markdown();
         }
     }
 
@@ -285,6 +463,15 @@ impl Tab {
         self.selected_link
     }
 
+    /// Scroll the selected link into view, `margin` lines from the edges
+    pub fn reveal_selected_link(&mut self, margin: usize) {
+        let line = match (self.content(), self.selected_link()) {
+            (Some(content), Some(link)) => crate::app::link_line(content, link),
+            _ => return,
+        };
+        self.reveal_line(self.rendered_line(line), margin);
+    }
+
     /// Move to next link
     pub fn next_link(&mut self) {
         if let Some(links) = self.links() {
@@ -311,14 +498,73 @@ impl Tab {
         }
     }
 
+    /// Rendered line numbers holding a form field placeholder
+    ///
+    /// Matches `[INPUT: ...]`/`[TEXTAREA: ...]` from HTML conversion and
+    /// `[label: ___]` from accessibility tree conversion.
+    pub fn form_field_lines(&self) -> Vec<usize> {
+        self.rendered_lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.contains("[INPUT: ") || line.contains("[TEXTAREA: ") || line.contains(": ___]")
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Focus the first form field and scroll to it
+    ///
+    /// Returns false if the page has no form fields.
+    pub fn focus_first_form_field(&mut self) -> bool {
+        self.form_field_index = 0;
+        self.scroll_to_form_field()
+    }
+
     /// Move to next form field
     pub fn next_form_field(&mut self) {
-        self.form_field_index += 1;
+        let count = self.form_field_lines().len();
+        if count > 0 {
+            self.form_field_index = (self.form_field_index + 1) % count;
+            self.scroll_to_form_field();
+        }
     }
 
     /// Move to previous form field
     pub fn prev_form_field(&mut self) {
-        self.form_field_index = self.form_field_index.saturating_sub(1);
+        let count = self.form_field_lines().len();
+        if count > 0 {
+            self.form_field_index = (self.form_field_index + count - 1) % count;
+            self.scroll_to_form_field();
+        }
+    }
+
+    /// Index of the focused form field
+    pub fn form_field_index(&self) -> usize {
+        self.form_field_index
+    }
+
+    /// Text typed into the focused form field
+    pub fn form_input(&self) -> &str {
+        self.form_inputs
+            .get(&self.form_field_index)
+            .map(|s| s.as_str())
+            .unwrap_or("")
+    }
+
+    /// Mutable buffer for the focused form field
+    pub fn form_input_mut(&mut self) -> &mut String {
+        self.form_inputs.entry(self.form_field_index).or_default()
+    }
+
+    fn scroll_to_form_field(&mut self) -> bool {
+        match self.form_field_lines().get(self.form_field_index) {
+            Some(&line) => {
+                self.scroll_to_line(line);
+                true
+            }
+            None => false,
+        }
     }
 }
 
@@ -327,6 +573,10 @@ impl Tab {
 pub struct TabManager {
     tabs: Vec<Tab>,
     current: usize,
+    /// Wrap width limit applied to every tab
+    max_width: usize,
+    /// View mode applied to every tab
+    view_mode: ViewMode,
 }
 
 impl Default for TabManager {
@@ -334,6 +584,8 @@ impl Default for TabManager {
         Self {
             tabs: vec![Tab::new()],
             current: 0,
+            max_width: 0,
+            view_mode: ViewMode::Reader,
         }
     }
 }
@@ -376,10 +628,29 @@ impl TabManager {
 
     /// Create a new tab
     pub fn new_tab(&mut self) {
-        self.tabs.push(Tab::new());
+        let mut tab = Tab::new();
+        tab.set_max_width(self.max_width);
+        tab.set_view_mode(self.view_mode);
+        self.tabs.push(tab);
         self.current = self.tabs.len() - 1;
     }
 
+    /// Switch every tab between reader and full-page rendering
+    pub fn set_view_mode(&mut self, mode: ViewMode) {
+        self.view_mode = mode;
+        for tab in &mut self.tabs {
+            tab.set_view_mode(mode);
+        }
+    }
+
+    /// Limit the wrap width of every tab (0 = viewport width)
+    pub fn set_max_width(&mut self, max_width: usize) {
+        self.max_width = max_width;
+        for tab in &mut self.tabs {
+            tab.set_max_width(max_width);
+        }
+    }
+
     /// Close the current tab
     pub fn close_current(&mut self) {
         if self.tabs.len() > 1 {
@@ -451,6 +722,149 @@ mod tests {
         assert_eq!(tab.scroll_offset, 0);
     }
 
+    #[test]
+    fn test_smooth_scroll_and_margin() {
+        let mut tab = Tab::new();
+        tab.viewport_height = 10;
+        tab.rendered_lines = (0..50).map(|i| format!("Line {}", i)).collect();
+
+        tab.scroll_by(8, true);
+        assert_eq!(tab.scroll_offset, 0);
+        let mut frames = 0;
+        while tab.step_scroll() {
+            frames += 1;
+        }
+        assert_eq!(tab.scroll_offset, 8);
+        assert!(frames > 1 && frames <= 4);
+
+        tab.reveal_line(16, 3);
+        assert_eq!(tab.scroll_offset, 10);
+        tab.reveal_line(11, 3);
+        assert_eq!(tab.scroll_offset, 8);
+        tab.reveal_line(12, 3);
+        assert_eq!(tab.scroll_offset, 8);
+    }
+
+    #[test]
+    fn test_rendered_line_accounts_for_wrapping() {
+        let mut tab = Tab::new();
+        tab.viewport_width = 42;
+        tab.markdown = format!("{}\n\n# Heading\n\n{}", "word ".repeat(30), "word ".repeat(10));
+        tab.wrap_markdown();
+
+        // The 150-column paragraph wraps onto 4 lines of at most 40
+        assert_eq!(tab.rendered_line(0), 0);
+        assert_eq!(tab.rendered_line(1), 4);
+        assert_eq!(tab.rendered_line(2), 5);
+        assert_eq!(tab.rendered_lines[5], "Heading");
+        assert_eq!(tab.rendered_line(4), 7);
+    }
+
+    #[test]
+    fn test_wrap_respects_max_width() {
+        let mut tab = Tab::new();
+        tab.viewport_width = 120;
+        tab.markdown = "word ".repeat(60);
+
+        let widest = |tab: &mut Tab| {
+            tab.wrap_markdown();
+            tab.rendered_lines.iter().map(|l| l.len()).max().unwrap_or(0)
+        };
+        assert!(widest(&mut tab) > 60);
+
+        tab.set_max_width(60);
+        assert!(widest(&mut tab) <= 60);
+
+        // A viewport narrower than the cap still wins
+        tab.viewport_width = 42;
+        assert!(widest(&mut tab) <= 40);
+
+        let mut manager = TabManager::new();
+        manager.set_max_width(60);
+        manager.new_tab();
+        assert_eq!(manager.current().max_width, 60);
+    }
+
+    #[test]
+    fn test_view_mode_full_includes_whole_page() {
+        let html = format!(
+            "<html><body><nav><a href=\"/\">Site menu</a></nav><article><p>{}</p></article><footer>Copyright</footer></body></html>",
+            "Article body text. ".repeat(10)
+        );
+        let page = Page {
+            url: url::Url::parse("https://example.com/").unwrap(),
+            title: None,
+            content: Some(
+                fox_core::extract::extract_content(&html, &url::Url::parse("https://example.com/").unwrap())
+                    .unwrap(),
+            ),
+            html,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        };
+
+        let mut tab = Tab::new();
+        tab.load_page(page);
+        let text = |tab: &Tab| tab.rendered_lines.join("\n");
+        assert!(text(&tab).contains("Article body text."));
+        assert!(!text(&tab).contains("Site menu"));
+        assert!(!text(&tab).contains("Copyright"));
+
+        tab.set_view_mode(ViewMode::Full);
+        assert!(text(&tab).contains("Article body text."));
+        assert!(text(&tab).contains("Site menu"));
+        assert!(text(&tab).contains("Copyright"));
+
+        assert_eq!("full".parse::<ViewMode>(), Ok(ViewMode::Full));
+        assert!("wide".parse::<ViewMode>().is_err());
+    }
+
+    #[test]
+    fn test_resize_rewraps_without_converting() {
+        let page = Page {
+            url: url::Url::parse("https://example.com/").unwrap(),
+            title: None,
+            html: format!("<p>{}</p>", "word ".repeat(100)),
+            content: None,
+            redirect_chain: Vec::new(),
+            markdown_cache: Default::default(),
+        };
+
+        let mut tab = Tab::new();
+        tab.load_page(page);
+        assert_eq!(tab.conversions, 1);
+
+        tab.set_viewport_size(120, 40);
+        let wide = tab.total_lines();
+        tab.set_viewport_size(40, 40);
+        assert!(tab.total_lines() > wide);
+        tab.set_max_width(30);
+        assert_eq!(tab.conversions, 1);
+
+        tab.set_view_mode(ViewMode::Full);
+        assert_eq!(tab.conversions, 2);
+    }
+
+    #[test]
+    fn test_form_field_focus() {
+        let mut tab = Tab::new();
+        tab.viewport_height = 10;
+        tab.rendered_lines = (0..50).map(|i| format!("Line {}", i)).collect();
+        tab.rendered_lines[30] = "[INPUT: q]".to_string();
+        tab.rendered_lines[40] = "[TEXTAREA: comment]".to_string();
+
+        assert_eq!(tab.form_field_lines(), vec![30, 40]);
+        assert!(tab.focus_first_form_field());
+        assert_eq!(tab.scroll_offset, 25);
+
+        tab.form_input_mut().push_str("rust");
+        tab.next_form_field();
+        assert_eq!(tab.form_field_index(), 1);
+        assert_eq!(tab.form_input(), "");
+        tab.next_form_field();
+        assert_eq!(tab.form_input(), "rust");
+    }
+
     #[test]
     fn test_tab_manager() {
         let mut manager = TabManager::new();
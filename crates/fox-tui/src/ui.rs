@@ -1,7 +1,8 @@
 //! TUI rendering with Ratatui
 
-use crate::app::App;
+use crate::app::{link_line, App, HintAction};
 use crate::vim::VimMode;
+use fox_core::FetchProgress;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -92,16 +93,28 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
     let mut hint_strings: std::collections::HashMap<usize, Vec<String>> = std::collections::HashMap::new();
     for (hint, link) in app.link_hints.iter() {
         if let Some(content) = tab.content() {
-            let line_num = content[..link.position.min(content.len())]
-                .lines()
-                .count()
-                .saturating_sub(1);
+            let line_num = link_line(content, link);
             hint_strings.entry(line_num).or_default().push(hint.clone());
         }
     }
 
+    // Lines holding links that leave the current site
+    let mut external_lines = std::collections::HashSet::new();
+    if let (Some(content), Some(links), Some(base)) = (tab.content(), tab.links(), tab.page_url()) {
+        for link in links.iter().filter(|link| link.is_external(base)) {
+            external_lines.insert(link_line(content, link));
+        }
+    }
+
     // Current hint input for highlighting matched prefix
     let hint_input = &app.hint_input;
+    let hint_label = |text: &str| {
+        if app.config.display.hint_uppercase {
+            text.to_uppercase()
+        } else {
+            text.to_string()
+        }
+    };
 
     // Render content
     let lines: Vec<Line> = tab
@@ -125,7 +138,7 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
                             // Show matched part in dim style (already typed)
                             if !matched.is_empty() {
                                 spans.push(Span::styled(
-                                    matched.to_string(),
+                                    hint_label(matched),
                                     Style::default()
                                         .fg(Color::DarkGray)
                                         .bg(Color::Yellow),
@@ -135,7 +148,7 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
                             // Show remaining part in bold (still to type)
                             if !remaining.is_empty() {
                                 spans.push(Span::styled(
-                                    remaining.to_string(),
+                                    hint_label(remaining),
                                     Style::default()
                                         .fg(Color::Black)
                                         .bg(Color::Yellow)
@@ -153,6 +166,11 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
             let styled_line = style_markdown_line(line);
             spans.extend(styled_line);
 
+            // Mark lines that link off-site
+            if external_lines.contains(&line_idx) {
+                spans.push(Span::styled(" ↗", Style::default().fg(Color::Magenta)));
+            }
+
             Line::from(spans)
         })
         .collect();
@@ -162,7 +180,14 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
 
     let paragraph = Paragraph::new(lines).block(content_block);
 
-    frame.render_widget(paragraph, content_chunks[0]);
+    let mut text_area = content_chunks[0];
+    if app.config.display.center_content {
+        let padding = reader_padding(text_area.width, app.config.display.max_width);
+        text_area.x += padding;
+        text_area.width -= padding;
+    }
+
+    frame.render_widget(paragraph, text_area);
 
     // Render scrollbar
     let scrollbar = Scrollbar::default()
@@ -176,6 +201,18 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_stateful_widget(scrollbar, content_chunks[1], &mut scrollbar_state);
 }
 
+/// Left padding that centers a reader column of `column` cells in `width`
+///
+/// A column of 0 means full width, so no padding is added.
+fn reader_padding(width: u16, column: usize) -> u16 {
+    let width = width as usize;
+    if column == 0 || column >= width {
+        0
+    } else {
+        ((width - column) / 2) as u16
+    }
+}
+
 fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let tab = app.tabs.current();
 
@@ -206,11 +243,47 @@ fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let progress_span = Span::styled(progress, Style::default().fg(Color::DarkGray));
 
     let loading_span = if app.loading {
-        Span::styled(" Loading... ", Style::default().fg(Color::Yellow))
+        Span::styled(
+            format!(" {} ", loading_label(app.load_progress.as_ref())),
+            Style::default().fg(Color::Yellow),
+        )
     } else {
         Span::raw("")
     };
 
+    // A custom statusline replaces the built-in layout
+    if let Some(format) = app.config.display.statusline.as_deref().filter(|f| !f.is_empty()) {
+        let spans: Vec<Span> = parse_statusline(format)
+            .into_iter()
+            .map(|segment| match segment {
+                StatusSegment::Text(text) => Span::raw(text),
+                StatusSegment::Field(field) => {
+                    let (text, style) = match field {
+                        "mode" => (app.vim.mode.indicator().to_string(), mode_style.add_modifier(Modifier::BOLD)),
+                        "url" => (url.clone(), url_span.style),
+                        "progress" => (progress_span.content.trim().to_string(), progress_span.style),
+                        "title" => (tab.title().unwrap_or("").to_string(), Style::default().fg(Color::White)),
+                        "reading_time" => (
+                            tab.content().map(reading_time).unwrap_or_default(),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        "tabcount" => (app.tabs.len().to_string(), Style::default().fg(Color::White)),
+                        "loading" => (loading_span.content.trim().to_string(), loading_span.style),
+                        "link" => (
+                            tab.selected_link().map(|link| link.url.clone()).unwrap_or_default(),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        _ => (String::new(), Style::default()),
+                    };
+                    Span::styled(text, style)
+                }
+            })
+            .collect();
+        let status = Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::DarkGray));
+        frame.render_widget(status, area);
+        return;
+    }
+
     // Build status line
     let mut spans = vec![mode_span, url_span, loading_span];
 
@@ -228,17 +301,97 @@ fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(status, area);
 }
 
+/// Placeholders understood in `display.statusline`
+const STATUSLINE_FIELDS: &[&str] = &[
+    "mode",
+    "url",
+    "progress",
+    "title",
+    "reading_time",
+    "tabcount",
+    "loading",
+    "link",
+];
+
+/// A piece of a parsed statusline format
+#[derive(Debug, PartialEq, Eq)]
+enum StatusSegment {
+    /// Literal text
+    Text(String),
+    /// A placeholder, one of [`STATUSLINE_FIELDS`]
+    Field(&'static str),
+}
+
+/// Split a statusline format into literal text and placeholders
+///
+/// `{{` and `}}` produce literal braces. Unknown placeholders are kept as
+/// text so typos stay visible.
+fn parse_statusline(format: &str) -> Vec<StatusSegment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = format;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            text.push(c);
+            rest = &rest[2..];
+            continue;
+        }
+        if c == '{' {
+            if let Some(end) = rest.find('}') {
+                if let Some(field) = STATUSLINE_FIELDS.iter().find(|f| **f == &rest[1..end]) {
+                    if !text.is_empty() {
+                        segments.push(StatusSegment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(StatusSegment::Field(field));
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        text.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    if !text.is_empty() {
+        segments.push(StatusSegment::Text(text));
+    }
+    segments
+}
+
+/// Status bar text for a page load, naming the stage it has reached
+fn loading_label(progress: Option<&FetchProgress>) -> String {
+    match progress {
+        Some(FetchProgress::Converting { nodes }) => format!("Converting {} nodes...", nodes),
+        None => "Loading...".to_string(),
+    }
+}
+
+/// Estimated reading time of some text at 200 words per minute
+fn reading_time(text: &str) -> String {
+    let words = text.split_whitespace().count();
+    format!("{} min", words.div_ceil(200).max(1))
+}
+
 fn draw_command_line(frame: &mut Frame, app: &App, area: Rect) {
     let content = match app.vim.mode {
         VimMode::Command => format!(":{}", app.input),
         VimMode::Search => format!("/{}", app.input),
         VimMode::Hint => {
+            let label = match app.hint_action {
+                HintAction::Follow => "Follow hint",
+                HintAction::Download => "Download hint",
+            };
             if app.hint_input.is_empty() {
-                "Follow hint...".to_string()
+                format!("{}...", label)
             } else {
-                format!("Follow hint: {}", app.hint_input)
+                format!("{}: {}", label, app.hint_input)
             }
         }
+        VimMode::Insert => {
+            let tab = app.tabs.current();
+            format!("Input {}: {}", tab.form_field_index() + 1, tab.form_input())
+        }
         _ => app.status.clone().unwrap_or_default(),
     };
 
@@ -397,3 +550,62 @@ fn style_markdown_line(line: &str) -> Vec<Span<'static>> {
     spans.push(Span::raw(line.to_string()));
     spans
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader_padding() {
+        assert_eq!(reader_padding(200, 80), 60);
+        assert_eq!(reader_padding(121, 80), 20);
+        assert_eq!(reader_padding(80, 80), 0);
+        assert_eq!(reader_padding(60, 80), 0);
+        assert_eq!(reader_padding(200, 0), 0);
+    }
+
+    #[test]
+    fn test_parse_statusline() {
+        use StatusSegment::{Field, Text};
+
+        assert_eq!(
+            parse_statusline("{mode} {url} | {progress}"),
+            vec![
+                Field("mode"),
+                Text(" ".to_string()),
+                Field("url"),
+                Text(" | ".to_string()),
+                Field("progress"),
+            ]
+        );
+        assert_eq!(
+            parse_statusline("[{tabcount}] {title} ({reading_time})"),
+            vec![
+                Text("[".to_string()),
+                Field("tabcount"),
+                Text("] ".to_string()),
+                Field("title"),
+                Text(" (".to_string()),
+                Field("reading_time"),
+                Text(")".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_statusline("{{url}} {nope} {url"),
+            vec![Text("{url} {nope} {url".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_loading_label() {
+        assert_eq!(loading_label(None), "Loading...");
+        let converting = FetchProgress::Converting { nodes: 12000 };
+        assert_eq!(loading_label(Some(&converting)), "Converting 12000 nodes...");
+    }
+
+    #[test]
+    fn test_reading_time() {
+        assert_eq!(reading_time(""), "1 min");
+        assert_eq!(reading_time(&"word ".repeat(450)), "3 min");
+    }
+}
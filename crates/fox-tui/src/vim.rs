@@ -57,29 +57,89 @@ impl VimState {
     }
 }
 
+/// What `:yank` copies from the current page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YankFormat {
+    /// The page's markdown
+    Markdown,
+    /// The page's text with markdown syntax removed
+    Plain,
+}
+
 /// Parsed command from command mode
 #[derive(Debug, Clone)]
 pub enum Command {
-    /// Quit the application
+    /// Close the current tab, quitting on the last one
     Quit,
+    /// Quit the application regardless of open tabs
+    QuitAll,
+    /// Quit immediately, skipping any confirmation
+    ForceQuit,
+    /// Save page to file, then quit; without a path the file is named
+    /// after the page title
+    WriteQuit(Option<String>),
     /// Open a URL in the current tab
     Open(String),
     /// Open a URL in a new tab
     TabOpen(String),
-    /// Save page to file
-    Write(String),
+    /// Save page to file; without a path the file is named after the page
+    /// title
+    Write(Option<String>),
+    /// Download a URL to the downloads directory
+    Download(String),
+    /// Show the page outline, or jump to the Nth heading
+    Toc(Option<usize>),
     /// List tabs
     Tabs,
     /// Show history
     History,
+    /// Show the jumplist
+    Jumps,
+    /// Export history to a file (CSV for `.csv`, otherwise JSON)
+    HistoryExport(String),
+    /// Toggle private mode, which stops recording history
+    Private,
     /// Set a configuration option
     Set(String, String),
+    /// Reload the configuration file
+    Source,
+    /// Open the URL on the clipboard
+    PasteOpen,
+    /// Copy the whole page to the clipboard
+    Yank(YankFormat),
+    /// Open the current page in the external browser
+    Gui,
+    /// Search the content of every open tab
+    SearchAll(String),
     /// Unknown command
     Unknown(String),
     /// Empty command
     Empty,
 }
 
+/// Command names offered for completion in command mode, with what they do
+pub const COMMAND_NAMES: &[(&str, &str)] = &[
+    ("open", "Open URL"),
+    ("tabopen", "Open URL in new tab"),
+    ("pasteopen", "Open URL from clipboard"),
+    ("gui", "Open the page in the external browser"),
+    ("search-all", "Search every open tab"),
+    ("yank", "Copy the whole page to the clipboard"),
+    ("quit", "Close tab (quit on the last tab)"),
+    ("quitall", "Quit"),
+    ("wq", "Save page as markdown, then quit"),
+    ("write", "Save page (.md, .txt, .html, or .json)"),
+    ("download", "Download a file to the downloads directory"),
+    ("toc", "Show the page outline"),
+    ("tabs", "List open tabs"),
+    ("history", "Show history"),
+    ("history-export", "Export history as CSV or JSON"),
+    ("jumps", "Show the jumplist"),
+    ("private", "Toggle private mode"),
+    ("set", "Change settings"),
+    ("source", "Reload the config file"),
+];
+
 impl Command {
     /// Parse a command string
     pub fn parse(input: &str) -> Self {
@@ -94,6 +154,9 @@ impl Command {
 
         match cmd {
             "q" | "quit" | "exit" => Command::Quit,
+            "qa" | "qall" | "quitall" => Command::QuitAll,
+            "q!" | "quit!" => Command::ForceQuit,
+            "wq" | "x" => Command::WriteQuit(arg),
             "o" | "open" | "e" | "edit" => {
                 if let Some(url) = arg {
                     Command::Open(url)
@@ -108,15 +171,44 @@ impl Command {
                     Command::Unknown("tabopen requires a URL".to_string())
                 }
             }
-            "w" | "write" | "save" => {
-                if let Some(path) = arg {
-                    Command::Write(path)
+            "w" | "write" | "save" => Command::Write(arg),
+            "download" | "dl" => {
+                if let Some(url) = arg {
+                    Command::Download(url)
                 } else {
-                    Command::Write("page.md".to_string())
+                    Command::Unknown("download requires a URL".to_string())
                 }
             }
+            "toc" | "outline" => match arg {
+                Some(n) => match n.parse() {
+                    Ok(n) => Command::Toc(Some(n)),
+                    Err(_) => Command::Unknown(format!("toc expects a heading number: {}", n)),
+                },
+                None => Command::Toc(None),
+            },
             "tabs" | "buffers" | "ls" => Command::Tabs,
             "history" | "hist" => Command::History,
+            "history-export" | "histexport" => match arg {
+                Some(path) => Command::HistoryExport(path),
+                None => Command::Unknown("history-export requires a path".to_string()),
+            },
+            "jumps" | "ju" => Command::Jumps,
+            "private" => Command::Private,
+            "source" | "so" | "reloadconfig" => Command::Source,
+            "pasteopen" | "po" => Command::PasteOpen,
+            "gui" | "external" => Command::Gui,
+            "search-all" | "searchall" | "sa" => match arg {
+                Some(query) => Command::SearchAll(query),
+                None => Command::Unknown("search-all requires a query".to_string()),
+            },
+            "yank" | "y" => match arg.as_deref() {
+                None | Some("markdown") | Some("md") => Command::Yank(YankFormat::Markdown),
+                Some("plain") | Some("text") => Command::Yank(YankFormat::Plain),
+                Some(other) => Command::Unknown(format!(
+                    "yank expects 'markdown' or 'plain': {}",
+                    other
+                )),
+            },
             "set" => {
                 if let Some(setting) = arg {
                     let setting_parts: Vec<&str> = setting.splitn(2, '=').collect();
@@ -147,6 +239,22 @@ mod tests {
         assert!(matches!(Command::parse("quit"), Command::Quit));
     }
 
+    #[test]
+    fn test_parse_quit_variants() {
+        assert!(matches!(Command::parse("qa"), Command::QuitAll));
+        assert!(matches!(Command::parse("qall"), Command::QuitAll));
+        assert!(matches!(Command::parse("q!"), Command::ForceQuit));
+        assert!(matches!(Command::parse("quit!"), Command::ForceQuit));
+        match Command::parse("wq notes.md") {
+            Command::WriteQuit(path) => assert_eq!(path.as_deref(), Some("notes.md")),
+            _ => panic!("Expected WriteQuit command"),
+        }
+        match Command::parse("wq") {
+            Command::WriteQuit(path) => assert_eq!(path, None),
+            _ => panic!("Expected WriteQuit command"),
+        }
+    }
+
     #[test]
     fn test_parse_open() {
         match Command::parse("o example.com") {
@@ -163,6 +271,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_download() {
+        match Command::parse("download https://example.com/a.zip") {
+            Command::Download(url) => assert_eq!(url, "https://example.com/a.zip"),
+            _ => panic!("Expected Download command"),
+        }
+        assert!(matches!(Command::parse("dl"), Command::Unknown(_)));
+    }
+
+    #[test]
+    fn test_parse_toc() {
+        assert!(matches!(Command::parse("toc"), Command::Toc(None)));
+        assert!(matches!(Command::parse("toc 3"), Command::Toc(Some(3))));
+        assert!(matches!(Command::parse("toc x"), Command::Unknown(_)));
+    }
+
     #[test]
     fn test_parse_set() {
         match Command::parse("set javascript=false") {
@@ -173,4 +297,39 @@ mod tests {
             _ => panic!("Expected Set command"),
         }
     }
+
+    #[test]
+    fn test_parse_pasteopen() {
+        assert!(matches!(Command::parse("pasteopen"), Command::PasteOpen));
+        assert!(matches!(Command::parse("po"), Command::PasteOpen));
+    }
+
+    #[test]
+    fn test_parse_yank() {
+        assert!(matches!(Command::parse("yank"), Command::Yank(YankFormat::Markdown)));
+        assert!(matches!(Command::parse("y md"), Command::Yank(YankFormat::Markdown)));
+        assert!(matches!(Command::parse("yank plain"), Command::Yank(YankFormat::Plain)));
+        assert!(matches!(Command::parse("yank html"), Command::Unknown(_)));
+    }
+
+    #[test]
+    fn test_parse_gui() {
+        assert!(matches!(Command::parse("gui"), Command::Gui));
+        assert!(matches!(Command::parse("external"), Command::Gui));
+    }
+
+    #[test]
+    fn test_parse_search_all() {
+        match Command::parse("search-all rust async") {
+            Command::SearchAll(query) => assert_eq!(query, "rust async"),
+            _ => panic!("Expected SearchAll command"),
+        }
+        assert!(matches!(Command::parse("search-all"), Command::Unknown(_)));
+    }
+
+    #[test]
+    fn test_parse_source() {
+        assert!(matches!(Command::parse("source"), Command::Source));
+        assert!(matches!(Command::parse("reloadconfig"), Command::Source));
+    }
 }